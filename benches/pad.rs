@@ -0,0 +1,143 @@
+//! Throughput of padding and unpadding across block sizes, message sizes and RNG backends
+
+use criterion::measurement::WallTime;
+use criterion::{
+    black_box, criterion_group, criterion_main, BenchmarkGroup, BenchmarkId, Criterion, Throughput,
+};
+use rand::rngs::mock::StepRng;
+use rand::{CryptoRng, RngCore};
+
+use tx_padding::consts::{U16, U256, U8};
+use tx_padding::{BlockSize, InsecureRng, Padder, TxPadding, Unpadder};
+
+/// Lengths of the benchmarked messages
+const MSG_LENS: [usize; 4] = [16, 256, 4096, 65536];
+
+/// Size of the chunks fed to the streaming types
+const CHUNK_LEN: usize = 1024;
+
+/// Benchmark padding with `rng` for every message length
+fn pad_with<N: BlockSize>(
+    group: &mut BenchmarkGroup<'_, WallTime>,
+    name: &str,
+    mut rng: impl RngCore + CryptoRng,
+) {
+    for &msg_len in &MSG_LENS {
+        let msg = vec![0x5a; msg_len];
+        let mut out = vec![0; TxPadding::<N>::padded_len(msg_len)];
+        group.throughput(Throughput::Bytes(msg_len as u64));
+        group.bench_with_input(BenchmarkId::new(name, msg_len), &msg, |b, msg| {
+            b.iter(|| {
+                TxPadding::<N>::pad_into_with_rng(&mut rng, black_box(msg), &mut out)
+                    .unwrap()
+                    .len()
+            })
+        });
+    }
+}
+
+/// Padded message of `msg_len` bytes with block size `N`
+fn padded<N: BlockSize>(msg_len: usize) -> Vec<u8> {
+    let mut buf = vec![0x5a; TxPadding::<N>::padded_len(msg_len)];
+    TxPadding::<N>::pad_with_rng(&mut InsecureRng(StepRng::new(1, 1)), &mut buf, msg_len)
+        .unwrap()
+        .to_vec()
+}
+
+fn bench_pad_block_sizes(c: &mut Criterion) {
+    let mut group = c.benchmark_group("pad");
+    pad_with::<U8>(&mut group, "block 8", InsecureRng(StepRng::new(1, 1)));
+    pad_with::<U16>(&mut group, "block 16", InsecureRng(StepRng::new(1, 1)));
+    pad_with::<U256>(&mut group, "block 256", InsecureRng(StepRng::new(1, 1)));
+    group.finish();
+}
+
+fn bench_pad_rngs(c: &mut Criterion) {
+    let mut group = c.benchmark_group("pad rng");
+    pad_with::<U16>(&mut group, "StepRng", InsecureRng(StepRng::new(1, 1)));
+    #[cfg(feature = "os-rng")]
+    pad_with::<U16>(&mut group, "OsRng", rand::rngs::OsRng);
+    #[cfg(feature = "thread-rng")]
+    pad_with::<U16>(&mut group, "ThreadRng", rand::thread_rng());
+    #[cfg(feature = "insecure-fast-rng")]
+    pad_with::<U16>(
+        &mut group,
+        "InsecureFastRng",
+        tx_padding::InsecureFastRng::default(),
+    );
+    #[cfg(all(feature = "rdrand", any(target_arch = "x86", target_arch = "x86_64")))]
+    pad_with::<U16>(&mut group, "RdRandRng", tx_padding::RdRandRng);
+    group.finish();
+}
+
+fn bench_unpad(c: &mut Criterion) {
+    let mut group = c.benchmark_group("unpad");
+    for &msg_len in &MSG_LENS {
+        group.throughput(Throughput::Bytes(msg_len as u64));
+        let padded_msg = padded::<U8>(msg_len);
+        group.bench_with_input(
+            BenchmarkId::new("block 8", msg_len),
+            &padded_msg,
+            |b, padded_msg| b.iter(|| TxPadding::<U8>::unpad(black_box(padded_msg)).unwrap().len()),
+        );
+        let padded_msg = padded::<U256>(msg_len);
+        group.bench_with_input(
+            BenchmarkId::new("block 256", msg_len),
+            &padded_msg,
+            |b, padded_msg| {
+                b.iter(|| {
+                    TxPadding::<U256>::unpad(black_box(padded_msg))
+                        .unwrap()
+                        .len()
+                })
+            },
+        );
+    }
+    group.finish();
+}
+
+fn bench_stream(c: &mut Criterion) {
+    let mut group = c.benchmark_group("stream");
+    for &msg_len in &MSG_LENS {
+        group.throughput(Throughput::Bytes(msg_len as u64));
+        let msg = vec![0x5a; msg_len];
+        let mut out = vec![0; TxPadding::<U16>::padded_len(msg_len)];
+        group.bench_with_input(BenchmarkId::new("Padder", msg_len), &msg, |b, msg| {
+            b.iter(|| {
+                let mut padder =
+                    Padder::<U16>::with_rng(&mut InsecureRng(StepRng::new(1, 1)), msg_len).unwrap();
+                let mut n = 0;
+                for chunk in black_box(msg).chunks(CHUNK_LEN) {
+                    n += padder.update(chunk, &mut out[n..]).unwrap();
+                }
+                n + padder.finalize(&mut out[n..]).unwrap()
+            })
+        });
+        let padded_msg = padded::<U16>(msg_len);
+        group.bench_with_input(
+            BenchmarkId::new("Unpadder", msg_len),
+            &padded_msg,
+            |b, padded_msg| {
+                b.iter(|| {
+                    let mut unpadder = Unpadder::<U16>::new();
+                    let mut n = 0;
+                    for chunk in black_box(padded_msg).chunks(CHUNK_LEN) {
+                        n += unpadder.update(chunk, &mut out[n..]).unwrap();
+                    }
+                    unpadder.finish().unwrap();
+                    n
+                })
+            },
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    bench_pad_block_sizes,
+    bench_pad_rngs,
+    bench_unpad,
+    bench_stream
+);
+criterion_main!(benches);
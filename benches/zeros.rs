@@ -0,0 +1,69 @@
+//! Compare the word-wise check of the trailing zeros against a byte-wise one
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use rand::rngs::mock::StepRng;
+
+use tx_padding::consts::{U256, U65536};
+use tx_padding::{InsecureRng, TxPadding, TxPaddingWide};
+
+/// Unpad like `TxPadding`, checking the trailing zeros one byte at a time
+fn unpad_bytewise(data: &[u8], block_size: usize, header_len: usize) -> Option<&[u8]> {
+    let l = data.len();
+    let pad_zero = block_size - 1;
+    let mut header = [0; 2];
+    header[2 - header_len..].copy_from_slice(data.get(..header_len)?);
+    let pad_len = (u16::from_be_bytes(header) as usize & pad_zero) + 2;
+    if l < header_len + pad_len + pad_zero || data[l - pad_zero..].iter().any(|&v| v != 0) {
+        return None;
+    }
+    Some(&data[header_len + pad_len..l - pad_zero])
+}
+
+fn bench_unpad(c: &mut Criterion) {
+    let mut group = c.benchmark_group("unpad");
+
+    let mut buf = vec![0x5a; TxPadding::<U256>::padded_len(1000)];
+    let padded_msg =
+        TxPadding::<U256>::pad_with_rng(&mut InsecureRng(StepRng::new(1, 1)), &mut buf, 1000)
+            .unwrap()
+            .to_vec();
+    group.bench_function("block 256, word-wise", |b| {
+        b.iter(|| {
+            TxPadding::<U256>::unpad(black_box(&padded_msg))
+                .unwrap()
+                .len()
+        })
+    });
+    group.bench_function("block 256, byte-wise", |b| {
+        b.iter(|| {
+            unpad_bytewise(black_box(&padded_msg), 256, 1)
+                .unwrap()
+                .len()
+        })
+    });
+
+    let mut buf = vec![0x5a; TxPaddingWide::<U65536>::padded_len(1000)];
+    let padded_msg =
+        TxPaddingWide::<U65536>::pad_with_rng(&mut InsecureRng(StepRng::new(1, 1)), &mut buf, 1000)
+            .unwrap()
+            .to_vec();
+    group.bench_function("block 65536, word-wise", |b| {
+        b.iter(|| {
+            TxPaddingWide::<U65536>::unpad(black_box(&padded_msg))
+                .unwrap()
+                .len()
+        })
+    });
+    group.bench_function("block 65536, byte-wise", |b| {
+        b.iter(|| {
+            unpad_bytewise(black_box(&padded_msg), 65536, 2)
+                .unwrap()
+                .len()
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_unpad);
+criterion_main!(benches);
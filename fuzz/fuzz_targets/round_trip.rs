@@ -0,0 +1,38 @@
+//! Pad messages of arbitrary content and length with every block size, and check that unpadding
+//! recovers them
+#![no_main]
+
+use std::convert::TryInto;
+
+use libfuzzer_sys::fuzz_target;
+use rand::rngs::mock::StepRng;
+use tx_padding::consts::{U128, U16, U2, U256, U32, U4, U64, U8};
+use tx_padding::{BlockSize, InsecureRng, Padding, TxPadding};
+
+fn check<N: BlockSize>(seed: u64, msg: &[u8]) {
+    let mut buf = vec![0xa5; TxPadding::<N>::padded_len(msg.len())];
+    buf[..msg.len()].copy_from_slice(msg);
+    let mut rng = InsecureRng(StepRng::new(seed, 0x9e37_79b9_7f4a_7c15));
+    let padded_msg = TxPadding::<N>::pad_with_rng(&mut rng, &mut buf, msg.len()).unwrap();
+    assert_eq!(padded_msg.len() % N::USIZE, 0);
+    assert_eq!(TxPadding::<N>::unpad(padded_msg).unwrap(), msg);
+    assert_eq!(TxPadding::<N>::unpad_strict(padded_msg).unwrap(), msg);
+}
+
+fuzz_target!(|data: &[u8]| {
+    if data.len() < 9 {
+        return;
+    }
+    let (head, msg) = data.split_at(9);
+    let seed = u64::from_le_bytes(head[1..].try_into().unwrap());
+    match head[0] % 8 {
+        0 => check::<U2>(seed, msg),
+        1 => check::<U4>(seed, msg),
+        2 => check::<U8>(seed, msg),
+        3 => check::<U16>(seed, msg),
+        4 => check::<U32>(seed, msg),
+        5 => check::<U64>(seed, msg),
+        6 => check::<U128>(seed, msg),
+        _ => check::<U256>(seed, msg),
+    }
+});
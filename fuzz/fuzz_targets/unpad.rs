@@ -0,0 +1,45 @@
+//! Feed arbitrary bytes to every unpadding method, which must neither panic nor return a message
+//! outside of the input
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use tx_padding::consts::{U128, U16, U2, U256, U32, U4, U64, U8};
+use tx_padding::{BlockSize, Padding, TxPadding, TxPaddingDyn};
+
+/// Assert that `msg` is a subslice of `data`
+fn assert_within(data: &[u8], msg: &[u8]) {
+    let data_range = data.as_ptr_range();
+    let msg_range = msg.as_ptr_range();
+    assert!(data_range.start <= msg_range.start && msg_range.end <= data_range.end);
+}
+
+fn check<N: BlockSize>(data: &[u8]) {
+    let msg = TxPadding::<N>::unpad(data);
+    if let Ok(msg) = msg {
+        assert_within(data, msg);
+        let range = TxPadding::<N>::unpad_range(data).unwrap();
+        assert_eq!(&data[range], msg);
+    } else {
+        assert!(TxPadding::<N>::unpad_range(data).is_err());
+    }
+    if let Ok(strict) = TxPadding::<N>::unpad_strict(data) {
+        assert_within(data, strict);
+        assert_eq!(msg.ok(), Some(strict));
+        assert_eq!(data.len() % N::USIZE, 0);
+    }
+    assert_eq!(
+        <TxPaddingDyn>::unpad(data, N::USIZE).ok(),
+        TxPadding::<N>::unpad(data).ok()
+    );
+}
+
+fuzz_target!(|data: &[u8]| {
+    check::<U2>(data);
+    check::<U4>(data);
+    check::<U8>(data);
+    check::<U16>(data);
+    check::<U32>(data);
+    check::<U64>(data);
+    check::<U128>(data);
+    check::<U256>(data);
+});
@@ -0,0 +1,87 @@
+//! Compile-time padding of byte strings with the `tx-padding` scheme
+//!
+//! Use these through the `macros` feature of `tx-padding`, which re-exports them.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use rand_chacha::rand_core::{RngCore, SeedableRng};
+use rand_chacha::ChaCha20Rng;
+use syn::parse::{Parse, ParseStream};
+use syn::{Ident, LitByteStr, LitInt, Token};
+
+struct TxPadInput {
+    msg: LitByteStr,
+    block_size: Ident,
+    seed: LitInt,
+}
+
+impl Parse for TxPadInput {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let msg = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let block_size = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let key: Ident = input.parse()?;
+        if key != "seed" {
+            return Err(syn::Error::new(key.span(), "expected `seed = <u64>`"));
+        }
+        input.parse::<Token![=]>()?;
+        let seed = input.parse()?;
+        if input.peek(Token![,]) {
+            input.parse::<Token![,]>()?;
+        }
+        Ok(TxPadInput {
+            msg,
+            block_size,
+            seed,
+        })
+    }
+}
+
+/// Pad a byte string at compile time, expanding to a `[u8; _]` array
+///
+/// `tx_pad!(b"...", N, seed = S)` takes the block size `N` as one of the `typenum` constants
+/// `U2` to `U256`, and draws the random prefix from `ChaCha20Rng::seed_from_u64(S)`.
+#[proc_macro]
+pub fn tx_pad(input: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(input as TxPadInput);
+    match expand(input) {
+        Ok(tokens) => tokens.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
+fn expand(input: TxPadInput) -> syn::Result<TokenStream2> {
+    let block_size = input
+        .block_size
+        .to_string()
+        .strip_prefix('U')
+        .and_then(|n| n.parse::<usize>().ok())
+        .filter(|&n| n.is_power_of_two() && (2..=256).contains(&n))
+        .ok_or_else(|| {
+            syn::Error::new(
+                input.block_size.span(),
+                "the block size must be one of `U2`, `U4`, `U8`, `U16`, `U32`, `U64`, `U128` or \
+                 `U256`",
+            )
+        })?;
+    let seed = input.seed.base10_parse::<u64>()?;
+    let padded_msg = pad(
+        &input.msg.value(),
+        block_size,
+        &mut ChaCha20Rng::seed_from_u64(seed),
+    );
+    Ok(quote! { [#(#padded_msg),*] })
+}
+
+/// The padding scheme of `tx-padding`, which this crate cannot depend on as it re-exports it
+fn pad(msg: &[u8], block_size: usize, rng: &mut impl RngCore) -> Vec<u8> {
+    let pad_len = (block_size - (msg.len() % block_size + 2) % block_size) % block_size + 2;
+    let mut padded_msg = vec![0; 1 + pad_len];
+    rng.fill_bytes(&mut padded_msg[1..]);
+    padded_msg[0] = !((block_size - 1) as u8) | (pad_len - 2) as u8;
+    padded_msg.extend_from_slice(msg);
+    padded_msg.resize(padded_msg.len() + block_size - 1, 0);
+    padded_msg
+}
@@ -0,0 +1,55 @@
+//! Python bindings for `tx-padding`, padding with the block size chosen at runtime
+//!
+//! Build and install the module into the current virtual environment with `maturin develop`.
+//!
+//! ```python
+//! import tx_padding
+//!
+//! padded = tx_padding.pad(b"test", 8)
+//! assert tx_padding.unpad(padded, 8) == b"test"
+//! ```
+// The conversions of the errors returned from `#[pyfunction]`s are generated by PyO3
+#![allow(clippy::useless_conversion)]
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyBytes;
+
+use tx_padding::TxPaddingDyn;
+
+/// Pad `data` to a multiple of `block_size`, which must be a power of 2 between 2 and 256
+#[pyfunction]
+fn pad<'py>(py: Python<'py>, data: &[u8], block_size: usize) -> PyResult<Bound<'py, PyBytes>> {
+    let len = <TxPaddingDyn>::padded_len(data.len(), block_size)
+        .ok_or_else(|| PyValueError::new_err(format!("invalid block size {}", block_size)))?;
+    PyBytes::new_bound_with(py, len, |buf| {
+        buf[..data.len()].copy_from_slice(data);
+        <TxPaddingDyn>::pad(buf, data.len(), block_size)
+            .map(|_| ())
+            .map_err(|e| PyValueError::new_err(e.to_string()))
+    })
+}
+
+/// Unpad `data` padded to a multiple of `block_size`
+#[pyfunction]
+fn unpad<'py>(py: Python<'py>, data: &[u8], block_size: usize) -> PyResult<Bound<'py, PyBytes>> {
+    <TxPaddingDyn>::unpad(data, block_size)
+        .map(|msg| PyBytes::new_bound(py, msg))
+        .map_err(|_| PyValueError::new_err("invalid padding"))
+}
+
+/// Length of the padded output for a message of `msg_len` bytes
+#[pyfunction]
+fn padded_len(msg_len: usize, block_size: usize) -> PyResult<usize> {
+    <TxPaddingDyn>::padded_len(msg_len, block_size)
+        .ok_or_else(|| PyValueError::new_err(format!("invalid block size {}", block_size)))
+}
+
+#[pymodule]
+#[pyo3(name = "tx_padding")]
+fn tx_padding_module(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(pad, m)?)?;
+    m.add_function(wrap_pyfunction!(unpad, m)?)?;
+    m.add_function(wrap_pyfunction!(padded_len, m)?)?;
+    Ok(())
+}
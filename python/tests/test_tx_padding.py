@@ -0,0 +1,31 @@
+"""Test the Python bindings against the padding scheme"""
+import pytest
+
+import tx_padding
+
+
+@pytest.mark.parametrize("block_size", [2, 4, 8, 16, 32, 64, 128, 256])
+def test_round_trip(block_size):
+    for n in range(300):
+        msg = bytes([0x5A]) * n
+        padded = tx_padding.pad(msg, block_size)
+        assert len(padded) == tx_padding.padded_len(n, block_size)
+        assert len(padded) % block_size == 0
+        assert padded[len(padded) - block_size + 1 :] == bytes(block_size - 1)
+        assert tx_padding.unpad(padded, block_size) == msg
+
+
+def test_layout():
+    padded = tx_padding.pad(b"ack", 16)
+    pad_len = (padded[0] & 0xF) + 2
+    assert padded[0] & 0xF0 == 0xF0
+    assert padded[1 + pad_len : 4 + pad_len] == b"ack"
+
+
+def test_errors():
+    with pytest.raises(ValueError):
+        tx_padding.pad(b"test", 12)
+    with pytest.raises(ValueError):
+        tx_padding.unpad(b"\xff" * 16, 8)
+    with pytest.raises(ValueError):
+        tx_padding.padded_len(4, 0)
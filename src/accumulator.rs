@@ -0,0 +1,118 @@
+//! Collecting padded frames of a fixed length byte by byte
+
+use core::marker::PhantomData;
+
+use crate::{raw, BlockSize, TxPadError, UnpadError};
+
+/// Fixed-capacity buffer collecting padded messages of `blocks` blocks one byte at a time
+///
+/// [`push`](FrameAccumulator::push) only stores the byte and never fails or panics, so it can be
+/// called from an interrupt handler of a UART or USB peripheral. Once the frame is complete,
+/// [`take_message`](FrameAccumulator::take_message) unpads it and makes room for the next one.
+/// Bytes pushed while a complete frame waits to be taken are dropped.
+///
+/// ```
+/// # #[cfg(any(feature = "os-rng", feature = "thread-rng"))]
+/// # {
+/// use tx_padding::{FrameAccumulator, TxPadding};
+/// use tx_padding::consts::{U8};
+///
+/// let mut padded_msg = [0; 16];
+/// TxPadding::<U8>::pad_into(b"test", &mut padded_msg).unwrap();
+///
+/// let mut acc = FrameAccumulator::<U8, 64>::new(2).unwrap();
+/// for &byte in &padded_msg {
+///     assert!(acc.take_message().is_none());
+///     acc.push(byte);
+/// }
+/// assert_eq!(acc.take_message().unwrap().unwrap(), b"test");
+/// assert!(acc.is_empty());
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct FrameAccumulator<N, const CAP: usize> {
+    buf: [u8; CAP],
+    len: usize,
+    frame_len: usize,
+    _marker: PhantomData<N>,
+}
+
+impl<N: BlockSize, const CAP: usize> FrameAccumulator<N, CAP> {
+    /// Create an empty accumulator for frames of `blocks` blocks
+    ///
+    /// Fails with `BufferTooSmall` if such a frame does not fit in `CAP` bytes, or cannot hold a
+    /// padded message, which is at least two blocks long.
+    pub const fn new(blocks: usize) -> Result<Self, TxPadError> {
+        let frame_len = match blocks.checked_mul(N::USIZE) {
+            Some(frame_len) => frame_len,
+            None => return Err(TxPadError::MessageTooLong),
+        };
+        if blocks < 2 {
+            return Err(TxPadError::BufferTooSmall {
+                required: 2 * N::USIZE,
+                got: frame_len,
+            });
+        }
+        if frame_len > CAP {
+            return Err(TxPadError::BufferTooSmall {
+                required: frame_len,
+                got: CAP,
+            });
+        }
+        Ok(FrameAccumulator {
+            buf: [0; CAP],
+            len: 0,
+            frame_len,
+            _marker: PhantomData,
+        })
+    }
+
+    /// Append `byte` to the frame, returning whether the frame is complete
+    ///
+    /// The byte is dropped if the frame was already complete.
+    #[inline]
+    pub fn push(&mut self, byte: u8) -> bool {
+        if let Some(slot) = self.buf[..self.frame_len].get_mut(self.len) {
+            *slot = byte;
+            self.len += 1;
+        }
+        self.is_complete()
+    }
+
+    /// Whether a complete frame waits to be taken
+    pub fn is_complete(&self) -> bool {
+        self.len == self.frame_len
+    }
+
+    /// Whether no byte of the next frame has arrived yet
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Number of bytes of the frame received so far
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Length of a complete frame
+    pub fn frame_len(&self) -> usize {
+        self.frame_len
+    }
+
+    /// Unpad the complete frame and start collecting the next one
+    ///
+    /// Returns `None` while the frame is incomplete. A malformed frame is discarded all the same.
+    pub fn take_message(&mut self) -> Option<Result<&[u8], UnpadError>> {
+        if !self.is_complete() {
+            return None;
+        }
+        self.len = 0;
+        let frame = &self.buf[..self.frame_len];
+        Some(raw::unpad(frame, N::USIZE).map_err(|_| UnpadError))
+    }
+
+    /// Discard the bytes received so far, e.g. after a framing error on the link
+    pub fn clear(&mut self) {
+        self.len = 0;
+    }
+}
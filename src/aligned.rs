@@ -0,0 +1,97 @@
+//! Padding to a multiple of several blocks
+
+use crate::{
+    raw, BlockSize, CryptoRng, RngCore, TxPadError, TxPadding, TxPaddingConst, UnpadError,
+};
+
+impl<N: BlockSize, R> TxPadding<N, R> {
+    /// Length of the output of [`pad_aligned`](TxPadding::pad_aligned) for a message of length
+    /// `msg_len`, or `None` if `blocks` is zero or the length overflows
+    pub const fn padded_aligned_len(msg_len: usize, blocks: usize) -> Option<usize> {
+        raw::padded_aligned_len(N::USIZE, msg_len, blocks)
+    }
+
+    /// Pads message with length `pos` in the provided buffer to a multiple of `blocks` blocks,
+    /// drawing the random prefix from `rng`
+    ///
+    /// Like [`pad_min_with_rng`](TxPadding::pad_min_with_rng), the message is prefixed with its
+    /// length and the extra bytes are absorbed into the trailing zeros. Such output must be
+    /// unpadded with [`unpad_aligned`](TxPadding::unpad_aligned).
+    pub fn pad_aligned_with_rng<'a>(
+        rng: &mut (impl RngCore + CryptoRng),
+        buf: &'a mut [u8],
+        pos: usize,
+        blocks: usize,
+    ) -> Result<&'a mut [u8], TxPadError> {
+        raw::pad_aligned(rng, buf, pos, blocks, N::USIZE)
+    }
+
+    /// Unpad given `data` padded by [`pad_aligned`](TxPadding::pad_aligned)
+    pub fn unpad_aligned(data: &[u8]) -> Result<&[u8], UnpadError> {
+        raw::unpad_length_prefixed(data, N::USIZE).map_err(|_| UnpadError)
+    }
+}
+
+impl<N, R> TxPadding<N, R>
+where
+    N: BlockSize,
+    R: RngCore + CryptoRng + Default,
+{
+    /// Pads message with length `pos` in the provided buffer to a multiple of `blocks` blocks
+    ///
+    /// ```
+    /// # #[cfg(any(feature = "os-rng", feature = "thread-rng"))]
+    /// # {
+    /// use tx_padding::TxPadding;
+    /// use tx_padding::consts::{U16};
+    ///
+    /// let mut buffer = [0; 1024];
+    /// buffer[..600].copy_from_slice(&[0x5a; 600]);
+    /// let padded_msg = TxPadding::<U16>::pad_aligned(&mut buffer, 600, 32).unwrap();
+    /// assert_eq!(padded_msg.len(), 1024);
+    /// assert_eq!(TxPadding::<U16>::unpad_aligned(padded_msg).unwrap(), &[0x5a; 600][..]);
+    /// # }
+    /// ```
+    pub fn pad_aligned(buf: &mut [u8], pos: usize, blocks: usize) -> Result<&mut [u8], TxPadError> {
+        Self::pad_aligned_with_rng(&mut R::default(), buf, pos, blocks)
+    }
+}
+
+impl<const N: usize, R> TxPaddingConst<N, R> {
+    /// Length of the output of [`pad_aligned`](TxPaddingConst::pad_aligned) for a message of
+    /// length `msg_len`, or `None` if `blocks` is zero or the length overflows
+    pub const fn padded_aligned_len(msg_len: usize, blocks: usize) -> Option<usize> {
+        let () = Self::VALID_BLOCK_SIZE;
+        raw::padded_aligned_len(N, msg_len, blocks)
+    }
+
+    /// Pads message with length `pos` in the provided buffer to a multiple of `blocks` blocks,
+    /// drawing the random prefix from `rng`
+    ///
+    /// See [`TxPadding::pad_aligned_with_rng`].
+    pub fn pad_aligned_with_rng<'a>(
+        rng: &mut (impl RngCore + CryptoRng),
+        buf: &'a mut [u8],
+        pos: usize,
+        blocks: usize,
+    ) -> Result<&'a mut [u8], TxPadError> {
+        let () = Self::VALID_BLOCK_SIZE;
+        raw::pad_aligned(rng, buf, pos, blocks, N)
+    }
+
+    /// Unpad given `data` padded by [`pad_aligned`](TxPaddingConst::pad_aligned)
+    pub fn unpad_aligned(data: &[u8]) -> Result<&[u8], UnpadError> {
+        let () = Self::VALID_BLOCK_SIZE;
+        raw::unpad_length_prefixed(data, N).map_err(|_| UnpadError)
+    }
+}
+
+impl<const N: usize, R> TxPaddingConst<N, R>
+where
+    R: RngCore + CryptoRng + Default,
+{
+    /// Pads message with length `pos` in the provided buffer to a multiple of `blocks` blocks
+    pub fn pad_aligned(buf: &mut [u8], pos: usize, blocks: usize) -> Result<&mut [u8], TxPadError> {
+        Self::pad_aligned_with_rng(&mut R::default(), buf, pos, blocks)
+    }
+}
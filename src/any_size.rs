@@ -0,0 +1,113 @@
+use core::convert::Infallible;
+use core::marker::PhantomData;
+use core::ops::Range;
+
+use crate::error::UnpadErrorKind;
+use crate::{
+    raw, AnyBlockSize, CryptoRng, DefaultRng, PadError, Padding, RngCore, TxPadError, UnpadError,
+};
+
+/// `TxPadding` for block sizes which are not necessarily powers of 2
+///
+/// The header cannot share bits between the number of random bytes and random data without a
+/// bitmask, so here the whole first byte stores the number of random bytes minus 2, which must be
+/// less than `N`. Any `N` between 2 and 256 inclusive is supported, e.g. 24 or 48 for legacy
+/// hardware framing. Apart from the header, the layout and the padded length are the same as
+/// `TxPadding`.
+///
+/// ```
+/// # #[cfg(any(feature = "os-rng", feature = "thread-rng"))]
+/// # {
+/// use tx_padding::{TxPaddingAnySize, Padding};
+/// use tx_padding::consts::{U24};
+///
+/// let mut buffer = [0; 48];
+/// buffer[..4].copy_from_slice(b"test");
+/// let padded_msg = TxPaddingAnySize::<U24>::pad(&mut buffer, 4, 24).unwrap();
+/// assert_eq!(padded_msg.len(), 48);
+/// assert_eq!(padded_msg[0], 18);
+/// assert_eq!(TxPaddingAnySize::<U24>::unpad(padded_msg).unwrap(), b"test");
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub enum TxPaddingAnySize<N, R = DefaultRng> {
+    _Phantom(Infallible, PhantomData<fn() -> (N, R)>),
+}
+
+impl<N: AnyBlockSize, R> TxPaddingAnySize<N, R> {
+    /// Length of the padded output for a message of length `msg_len`
+    ///
+    /// This is also the minimal length of the buffer passed to the padding methods.
+    pub const fn padded_len(msg_len: usize) -> usize {
+        raw::padded_len(N::USIZE, msg_len)
+    }
+
+    /// Pads message with length `pos` in the provided buffer, drawing the random prefix from `rng`
+    pub fn pad_with_rng<'a>(
+        rng: &mut (impl RngCore + CryptoRng),
+        buf: &'a mut [u8],
+        pos: usize,
+    ) -> Result<&'a mut [u8], TxPadError> {
+        let padded_msg = raw::pad(rng, buf, pos, N::USIZE)?;
+        padded_msg[0] = (raw::pad_len(N::USIZE, pos) - 2) as u8;
+        Ok(padded_msg)
+    }
+
+    /// Unpad given `data`, returning the message
+    ///
+    /// `Padding::unpad` delegates here, but this does not require a default RNG.
+    pub fn unpad(data: &[u8]) -> Result<&[u8], UnpadError> {
+        let range = Self::unpad_range(data).map_err(|_| UnpadError)?;
+        Ok(&data[range])
+    }
+
+    /// Unpad given `data`, reporting why it is malformed on failure
+    #[cfg(feature = "diagnostics")]
+    pub fn unpad_verbose(data: &[u8]) -> Result<&[u8], UnpadErrorKind> {
+        Self::unpad_range(data).map(|range| &data[range])
+    }
+
+    /// Position of the message in the padded `data`
+    fn unpad_range(data: &[u8]) -> Result<Range<usize>, UnpadErrorKind> {
+        let header = match data.first() {
+            Some(&header) => header as usize,
+            None => Err(UnpadErrorKind::EmptyInput)?,
+        };
+        if header >= N::USIZE {
+            Err(UnpadErrorKind::InvalidHeader)?
+        }
+        let l = data.len();
+        let pad_zero = N::USIZE - 1;
+        let pad_len = header + 2;
+        if l < pad_len + N::USIZE {
+            Err(UnpadErrorKind::TooShort)?
+        }
+        raw::check_zeros(data, l - pad_zero..l, 0)?;
+
+        Ok(1 + pad_len..l - pad_zero)
+    }
+}
+
+impl<N, R> Padding for TxPaddingAnySize<N, R>
+where
+    N: AnyBlockSize,
+    R: RngCore + CryptoRng + Default,
+{
+    fn pad_block(_block: &mut [u8], _pos: usize) -> Result<(), PadError> {
+        Err(PadError)
+    }
+
+    fn unpad(data: &[u8]) -> Result<&[u8], UnpadError> {
+        Self::unpad(data)
+    }
+
+    fn pad(buf: &mut [u8], pos: usize, block_size: usize) -> Result<&mut [u8], PadError> {
+        if block_size != N::USIZE {
+            Err(TxPadError::BlockSizeMismatch {
+                expected: N::USIZE,
+                got: block_size,
+            })?
+        }
+        Ok(Self::pad_with_rng(&mut R::default(), buf, pos)?)
+    }
+}
@@ -0,0 +1,86 @@
+//! Padding into and unpadding from fixed-capacity `arrayvec` vectors
+
+use core::convert::TryFrom;
+
+use arrayvec::ArrayVec;
+
+use crate::{
+    raw, BlockSize, CryptoRng, RngCore, TxPadError, TxPadding, TxPaddingConst, UnpadError,
+};
+
+/// Pad `msg` into a new vector of capacity `CAP`
+fn pad_into_arrayvec<const CAP: usize>(
+    rng: &mut (impl RngCore + CryptoRng),
+    msg: &[u8],
+    block_size: usize,
+) -> Result<ArrayVec<u8, CAP>, TxPadError> {
+    let len = raw::checked_padded_len(block_size, msg.len()).ok_or(TxPadError::MessageTooLong)?;
+    if len > CAP {
+        Err(TxPadError::BufferTooSmall {
+            required: len,
+            got: CAP,
+        })?
+    }
+    let mut buf = ArrayVec::from([0; CAP]);
+    buf.truncate(len);
+    raw::pad_from(rng, msg, &mut buf, block_size)?;
+    Ok(buf)
+}
+
+impl<N: BlockSize, R> TxPadding<N, R> {
+    /// Pad `msg` into a new vector of capacity `CAP`, drawing the random prefix from `rng`
+    ///
+    /// Fails with `BufferTooSmall` if the padded message does not fit in `CAP` bytes.
+    pub fn pad_arrayvec_with_rng<const CAP: usize>(
+        rng: &mut (impl RngCore + CryptoRng),
+        msg: &[u8],
+    ) -> Result<ArrayVec<u8, CAP>, TxPadError> {
+        pad_into_arrayvec(rng, msg, N::USIZE)
+    }
+
+    /// Unpad `data` into a new vector of capacity `CAP`
+    ///
+    /// Fails if `data` is malformed or the message does not fit in `CAP` bytes.
+    pub fn unpad_arrayvec<const CAP: usize>(data: &[u8]) -> Result<ArrayVec<u8, CAP>, UnpadError> {
+        Self::unpad(data).and_then(|msg| ArrayVec::try_from(msg).map_err(|_| UnpadError))
+    }
+}
+
+impl<N, R> TxPadding<N, R>
+where
+    N: BlockSize,
+    R: RngCore + CryptoRng + Default,
+{
+    /// Pad `msg` into a new vector of capacity `CAP`
+    ///
+    /// ```
+    /// use tx_padding::TxPadding;
+    /// use tx_padding::consts::{U8};
+    ///
+    /// let padded_msg = TxPadding::<U8>::pad_arrayvec::<16>(b"test").unwrap();
+    /// assert_eq!(padded_msg.len(), 16);
+    /// assert_eq!(&TxPadding::<U8>::unpad_arrayvec::<4>(&padded_msg).unwrap()[..], b"test");
+    /// assert!(TxPadding::<U8>::pad_arrayvec::<8>(b"test").is_err());
+    /// ```
+    pub fn pad_arrayvec<const CAP: usize>(msg: &[u8]) -> Result<ArrayVec<u8, CAP>, TxPadError> {
+        Self::pad_arrayvec_with_rng(&mut R::default(), msg)
+    }
+}
+
+impl<const N: usize, R> TxPaddingConst<N, R> {
+    /// Unpad `data` into a new vector of capacity `CAP`
+    pub fn unpad_arrayvec<const CAP: usize>(data: &[u8]) -> Result<ArrayVec<u8, CAP>, UnpadError> {
+        Self::unpad(data).and_then(|msg| ArrayVec::try_from(msg).map_err(|_| UnpadError))
+    }
+}
+
+impl<const N: usize, R> TxPaddingConst<N, R>
+where
+    R: RngCore + CryptoRng + Default,
+{
+    /// Pad `msg` into a new vector of capacity `CAP`
+    pub fn pad_arrayvec<const CAP: usize>(msg: &[u8]) -> Result<ArrayVec<u8, CAP>, TxPadError> {
+        let () = Self::VALID_BLOCK_SIZE;
+        pad_into_arrayvec(&mut R::default(), msg, N)
+    }
+}
@@ -0,0 +1,106 @@
+//! Padding many messages while amortizing the calls into the RNG
+
+use rand::Error;
+
+use crate::{raw, BlockSize, CryptoRng, RngCore, TxPadError, TxPadding};
+
+/// Number of random bytes requested from the underlying RNG at once
+const POOL_LEN: usize = 1024;
+
+/// RNG serving random bytes out of a pool refilled from `inner` in one call
+pub(crate) struct PooledRng<R> {
+    inner: R,
+    pool: [u8; POOL_LEN],
+    pos: usize,
+}
+
+impl<R: RngCore> PooledRng<R> {
+    pub(crate) fn new(inner: R) -> Self {
+        PooledRng {
+            inner,
+            pool: [0; POOL_LEN],
+            pos: POOL_LEN,
+        }
+    }
+}
+
+impl<R: RngCore> RngCore for PooledRng<R> {
+    fn next_u32(&mut self) -> u32 {
+        let mut bytes = [0; 4];
+        self.fill_bytes(&mut bytes);
+        u32::from_le_bytes(bytes)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let mut bytes = [0; 8];
+        self.fill_bytes(&mut bytes);
+        u64::from_le_bytes(bytes)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        if let Err(e) = self.try_fill_bytes(dest) {
+            panic!("{}", e)
+        }
+    }
+
+    fn try_fill_bytes(&mut self, mut dest: &mut [u8]) -> Result<(), Error> {
+        while !dest.is_empty() {
+            if self.pos == POOL_LEN {
+                self.inner.try_fill_bytes(&mut self.pool)?;
+                self.pos = 0;
+            }
+            let n = dest.len().min(POOL_LEN - self.pos);
+            dest[..n].copy_from_slice(&self.pool[self.pos..self.pos + n]);
+            self.pos += n;
+            dest = &mut dest[n..];
+        }
+        Ok(())
+    }
+}
+
+impl<N: BlockSize, R> TxPadding<N, R> {
+    /// Pad every message of `frames` into the buffer paired with it, drawing the random
+    /// prefixes from `rng`
+    ///
+    /// The random bytes are requested from `rng` in chunks of 1 KiB and sliced across the
+    /// frames, instead of once per message. Each output buffer must hold at least
+    /// [`padded_len`](TxPadding::padded_len) bytes of its message, and receives the padded
+    /// message at its start. On error, the frames before the failing one have been padded and
+    /// the rest are left untouched.
+    pub fn pad_batch_with_rng<'m, 'o>(
+        rng: &mut (impl RngCore + CryptoRng),
+        frames: impl IntoIterator<Item = (&'m [u8], &'o mut [u8])>,
+    ) -> Result<(), TxPadError> {
+        let mut rng = PooledRng::new(rng);
+        for (msg, out) in frames {
+            raw::pad_from(&mut rng, msg, out, N::USIZE)?;
+        }
+        Ok(())
+    }
+}
+
+impl<N, R> TxPadding<N, R>
+where
+    N: BlockSize,
+    R: RngCore + CryptoRng + Default,
+{
+    /// Pad every message of `frames` into the buffer paired with it
+    ///
+    /// ```
+    /// # #[cfg(any(feature = "os-rng", feature = "thread-rng"))]
+    /// # {
+    /// use tx_padding::{TxPadding, Padding};
+    /// use tx_padding::consts::{U8};
+    ///
+    /// let (mut a, mut b) = ([0; 16], [0; 16]);
+    /// TxPadding::<U8>::pad_batch([(&b"ping"[..], &mut a[..]), (b"pong", &mut b)]).unwrap();
+    /// assert_eq!(TxPadding::<U8>::unpad(&a).unwrap(), b"ping");
+    /// assert_eq!(TxPadding::<U8>::unpad(&b).unwrap(), b"pong");
+    /// # }
+    /// ```
+    pub fn pad_batch<'m, 'o>(
+        frames: impl IntoIterator<Item = (&'m [u8], &'o mut [u8])>,
+    ) -> Result<(), TxPadError> {
+        Self::pad_batch_with_rng(&mut R::default(), frames)
+    }
+}
@@ -0,0 +1,133 @@
+//! Command line tool to pad, unpad and measure messages, e.g. to inspect captured frames
+//!
+//! ```text
+//! $ printf hello | txpad --block-size 8 pad > frame.bin
+//! $ echo fa010203047465737400000000000000 | txpad --block-size 8 --format hex unpad
+//! 74657374
+//! ```
+
+use std::fs;
+use std::io::{self, Read, Write};
+use std::path::PathBuf;
+use std::process;
+
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use clap::{Parser, Subcommand, ValueEnum};
+
+use tx_padding::TxPaddingDyn;
+
+#[derive(Parser)]
+#[command(
+    name = "txpad",
+    version,
+    about = "Pad and unpad messages with tx-padding"
+)]
+struct Args {
+    /// Block size to pad to, a power of 2 between 2 and 256
+    #[arg(short, long, default_value = "16")]
+    block_size: usize,
+
+    /// Encoding of the input and the output
+    #[arg(short, long, value_enum, default_value = "raw")]
+    format: Format,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Pad the message read from FILE, or stdin if omitted
+    Pad { file: Option<PathBuf> },
+    /// Unpad the padded message read from FILE, or stdin if omitted
+    Unpad { file: Option<PathBuf> },
+    /// Print the padded length of the message read from FILE, or stdin if omitted
+    Len { file: Option<PathBuf> },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Format {
+    Raw,
+    Hex,
+    Base64,
+}
+
+impl Format {
+    fn decode(self, input: Vec<u8>) -> Result<Vec<u8>, String> {
+        let text = || {
+            std::str::from_utf8(&input)
+                .map(|text| text.split_whitespace().collect::<String>())
+                .map_err(|e| format!("invalid input: {}", e))
+        };
+        match self {
+            Format::Raw => Ok(input),
+            Format::Hex => hex::decode(text()?).map_err(|e| format!("invalid hex: {}", e)),
+            Format::Base64 => BASE64
+                .decode(text()?)
+                .map_err(|e| format!("invalid base64: {}", e)),
+        }
+    }
+
+    fn encode(self, output: &[u8]) -> Vec<u8> {
+        match self {
+            Format::Raw => output.to_vec(),
+            Format::Hex => (hex::encode(output) + "\n").into_bytes(),
+            Format::Base64 => (BASE64.encode(output) + "\n").into_bytes(),
+        }
+    }
+}
+
+fn read_input(file: Option<PathBuf>) -> Result<Vec<u8>, String> {
+    match file {
+        Some(path) => fs::read(&path).map_err(|e| format!("{}: {}", path.display(), e)),
+        None => {
+            let mut input = Vec::new();
+            io::stdin()
+                .read_to_end(&mut input)
+                .map_err(|e| format!("stdin: {}", e))?;
+            Ok(input)
+        }
+    }
+}
+
+fn run(args: Args) -> Result<Vec<u8>, String> {
+    let block_size = args.block_size;
+    if !<TxPaddingDyn>::is_valid_block_size(block_size) {
+        Err(format!("invalid block size {}", block_size))?
+    }
+    match args.command {
+        Command::Pad { file } => {
+            let msg = args.format.decode(read_input(file)?)?;
+            let padded_len =
+                <TxPaddingDyn>::padded_len(msg.len(), block_size).ok_or("message too long")?;
+            let mut buf = vec![0; padded_len];
+            buf[..msg.len()].copy_from_slice(&msg);
+            <TxPaddingDyn>::pad(&mut buf, msg.len(), block_size).map_err(|e| e.to_string())?;
+            Ok(args.format.encode(&buf))
+        }
+        Command::Unpad { file } => {
+            let data = args.format.decode(read_input(file)?)?;
+            let msg = <TxPaddingDyn>::unpad(&data, block_size).map_err(|_| "invalid padding")?;
+            Ok(args.format.encode(msg))
+        }
+        Command::Len { file } => {
+            let msg = args.format.decode(read_input(file)?)?;
+            let padded_len =
+                <TxPaddingDyn>::padded_len(msg.len(), block_size).ok_or("message too long")?;
+            Ok(format!("{}\n", padded_len).into_bytes())
+        }
+    }
+}
+
+fn main() {
+    let output = run(Args::parse()).and_then(|output| {
+        io::stdout()
+            .write_all(&output)
+            .map_err(|e| format!("stdout: {}", e))
+    });
+    if let Err(e) = output {
+        eprintln!("txpad: {}", e);
+        process::exit(1);
+    }
+}
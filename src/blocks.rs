@@ -0,0 +1,128 @@
+//! Interoperability with the `Block`-based APIs of `block-padding` 0.3 and 0.4
+//!
+//! `block-padding` 0.3, used by `cipher` 0.4 and the block modes built on it, models paddings
+//! as infallible operations on the last block of the message, and so does 0.4, used by
+//! `cipher` 0.5, on top of `hybrid-array`. TxPadding prepends up to `N + 2` bytes and may append
+//! up to two blocks, so it cannot implement the `Padding` traits of either version. Instead the
+//! message is padded with the helpers below and encrypted with `NoPadding`.
+//!
+//! The helpers for 0.3 are enabled by the `block-padding-03` feature, and those for 0.4 by the
+//! `block-padding-04` feature. Both can be enabled at once during the transition.
+//!
+//! ```
+//! # #[cfg(feature = "block-padding-03")]
+//! # {
+//! use tx_padding::TxPadding;
+//! use tx_padding::consts::{U8};
+//!
+//! let mut buffer = [0; 16];
+//! buffer[..4].copy_from_slice(b"test");
+//! let blocks = TxPadding::<U8>::pad_blocks(&mut buffer, 4).unwrap();
+//! assert_eq!(blocks.count(), 2);
+//! // Encrypt the blocks with `NoPadding` here, then decrypt and unpad them
+//! assert_eq!(TxPadding::<U8>::unpad(&buffer).unwrap(), b"test");
+//! # }
+//! ```
+
+// generic-array 0.14.9 deprecates its whole API in favor of 1.x, which block-padding 0.3 does
+// not support
+#![allow(deprecated)]
+
+#[cfg(feature = "block-padding-03")]
+use block_padding_03::generic_array::{ArrayLength, GenericArray};
+#[cfg(feature = "block-padding-03")]
+use block_padding_03::{Block, NoPadding, Padding as _, UnpadError};
+#[cfg(feature = "block-padding-04")]
+use block_padding_04::array::{Array, ArraySize};
+
+use crate::{raw, BlockSize, CryptoRng, RngCore, TxPadError, TxPadding};
+
+#[cfg(feature = "block-padding-03")]
+impl<N, R> TxPadding<N, R>
+where
+    N: BlockSize + ArrayLength<u8>,
+{
+    /// Pads message with length `pos` in the provided buffer, returning the blocks of the padded
+    /// message and drawing the random prefix from `rng`
+    pub fn pad_blocks_with_rng<'a>(
+        rng: &mut (impl RngCore + CryptoRng),
+        buf: &'a mut [u8],
+        pos: usize,
+    ) -> Result<impl Iterator<Item = &'a mut Block<N>>, TxPadError> {
+        let padded_msg = raw::pad(rng, buf, pos, N::USIZE)?;
+        Ok(padded_msg
+            .chunks_exact_mut(N::USIZE)
+            .map(GenericArray::from_mut_slice))
+    }
+
+    /// Unpad the message stored in `blocks`
+    pub fn unpad_blocks(blocks: &[Block<N>]) -> Result<&[u8], UnpadError> {
+        // Without padding, this only views the blocks as a contiguous byte slice
+        let data = NoPadding::unpad_blocks(blocks)?;
+        raw::unpad(data, N::USIZE).map_err(|_| UnpadError)
+    }
+}
+
+#[cfg(feature = "block-padding-03")]
+impl<N, R> TxPadding<N, R>
+where
+    N: BlockSize + ArrayLength<u8>,
+    R: RngCore + CryptoRng + Default,
+{
+    /// Pads message with length `pos` in the provided buffer, returning the blocks of the padded
+    /// message
+    pub fn pad_blocks(
+        buf: &mut [u8],
+        pos: usize,
+    ) -> Result<impl Iterator<Item = &mut Block<N>>, TxPadError> {
+        Self::pad_blocks_with_rng(&mut R::default(), buf, pos)
+    }
+}
+
+#[cfg(feature = "block-padding-04")]
+impl<N, R> TxPadding<N, R>
+where
+    N: BlockSize + ArraySize,
+{
+    /// Pads message with length `pos` in the provided buffer, returning the padded message as
+    /// `hybrid-array` blocks and drawing the random prefix from `rng`
+    pub fn pad_array_blocks_with_rng<'a>(
+        rng: &mut (impl RngCore + CryptoRng),
+        buf: &'a mut [u8],
+        pos: usize,
+    ) -> Result<&'a mut [Array<u8, N>], TxPadError> {
+        let padded_msg = raw::pad(rng, buf, pos, N::USIZE)?;
+        // The padded message is a whole number of blocks, so nothing is left over
+        Ok(Array::slice_as_chunks_mut(padded_msg).0)
+    }
+
+    /// Unpad the message stored in the `hybrid-array` `blocks`
+    pub fn unpad_array_blocks(blocks: &[Array<u8, N>]) -> Result<&[u8], block_padding_04::Error> {
+        raw::unpad(Array::slice_as_flattened(blocks), N::USIZE).map_err(|_| block_padding_04::Error)
+    }
+}
+
+#[cfg(feature = "block-padding-04")]
+impl<N, R> TxPadding<N, R>
+where
+    N: BlockSize + ArraySize,
+    R: RngCore + CryptoRng + Default,
+{
+    /// Pads message with length `pos` in the provided buffer, returning the padded message as
+    /// `hybrid-array` blocks
+    ///
+    /// ```
+    /// use tx_padding::TxPadding;
+    /// use tx_padding::consts::{U8};
+    ///
+    /// let mut buffer = [0; 16];
+    /// buffer[..4].copy_from_slice(b"test");
+    /// let blocks = TxPadding::<U8>::pad_array_blocks(&mut buffer, 4).unwrap();
+    /// assert_eq!(blocks.len(), 2);
+    /// // Encrypt `blocks` with `NoPadding` here, then decrypt and unpad them
+    /// assert_eq!(TxPadding::<U8>::unpad_array_blocks(blocks).unwrap(), b"test");
+    /// ```
+    pub fn pad_array_blocks(buf: &mut [u8], pos: usize) -> Result<&mut [Array<u8, N>], TxPadError> {
+        Self::pad_array_blocks_with_rng(&mut R::default(), buf, pos)
+    }
+}
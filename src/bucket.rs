@@ -0,0 +1,90 @@
+use core::marker::PhantomData;
+
+use crate::raw::{self, Filler};
+use crate::{BlockSize, CryptoRng, DefaultRng, RngCore, TxPadError, UnpadError};
+
+/// Padding rounding the output up to the nearest size in a table of buckets
+///
+/// Like [`Padme`](crate::Padme), the message is prefixed with its length as a 64-bit big-endian
+/// integer and followed by random filler, and the result is padded with `TxPadding<N>`. The
+/// output is exactly as long as the smallest bucket which fits the plain `TxPadding` output, so
+/// an observer only learns the bucket. Bucket sizes which are not multiples of `N` are rounded up
+/// to the next multiple, and the table does not need to be sorted.
+///
+/// ```
+/// # #[cfg(any(feature = "os-rng", feature = "thread-rng"))]
+/// # {
+/// use tx_padding::BucketPadding;
+/// use tx_padding::consts::{U16};
+///
+/// let padding = BucketPadding::<U16>::new(&[256, 1024, 4096]);
+/// let mut buffer = [0; 1024];
+/// buffer[..300].copy_from_slice(&[0x5a; 300]);
+/// let padded_msg = padding.pad(&mut buffer, 300).unwrap();
+/// assert_eq!(padded_msg.len(), 1024);
+/// assert_eq!(padding.unpad(&padded_msg).unwrap(), &[0x5a; 300][..]);
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct BucketPadding<'a, N, R = DefaultRng> {
+    buckets: &'a [usize],
+    _marker: PhantomData<fn() -> (N, R)>,
+}
+
+impl<'a, N: BlockSize, R> BucketPadding<'a, N, R> {
+    /// Use the bucket sizes in `buckets`
+    pub const fn new(buckets: &'a [usize]) -> Self {
+        BucketPadding {
+            buckets,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Length of the padded output for a message of length `msg_len`, or `None` if it does not
+    /// fit in any bucket
+    ///
+    /// This is also the minimal length of the buffer passed to the padding methods.
+    pub fn padded_len(&self, msg_len: usize) -> Option<usize> {
+        if msg_len > isize::MAX as usize - raw::LEN_FIELD {
+            return None;
+        }
+        let required = raw::padded_len(N::USIZE, raw::LEN_FIELD + msg_len);
+        self.buckets
+            .iter()
+            .filter_map(|&bucket| bucket.checked_add(N::USIZE - 1))
+            .map(|bucket| bucket / N::USIZE * N::USIZE)
+            .filter(|&bucket| bucket >= required)
+            .min()
+    }
+
+    /// Pads message with length `pos` in the provided buffer, drawing the random bytes from `rng`
+    ///
+    /// Fails with `MessageTooLong` if the message does not fit in any bucket.
+    pub fn pad_with_rng<'b>(
+        &self,
+        rng: &mut (impl RngCore + CryptoRng),
+        buf: &'b mut [u8],
+        pos: usize,
+    ) -> Result<&'b mut [u8], TxPadError> {
+        let padded_len = self.padded_len(pos).ok_or(TxPadError::MessageTooLong)?;
+        raw::pad_length_prefixed(rng, buf, pos, padded_len, N::USIZE, Filler::Random)
+    }
+
+    /// Unpad given `data`
+    ///
+    /// The length of `data` is not checked against the buckets.
+    pub fn unpad<'b>(&self, data: &'b [u8]) -> Result<&'b [u8], UnpadError> {
+        raw::unpad_length_prefixed(data, N::USIZE).map_err(|_| UnpadError)
+    }
+}
+
+impl<'a, N, R> BucketPadding<'a, N, R>
+where
+    N: BlockSize,
+    R: RngCore + CryptoRng + Default,
+{
+    /// Pads message with length `pos` in the provided buffer
+    pub fn pad<'b>(&self, buf: &'b mut [u8], pos: usize) -> Result<&'b mut [u8], TxPadError> {
+        self.pad_with_rng(&mut R::default(), buf, pos)
+    }
+}
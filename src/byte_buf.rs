@@ -0,0 +1,76 @@
+//! Padding into and unpadding from `bytes` buffers without extra copies
+
+use bytes::{Bytes, BytesMut};
+
+use crate::{raw, BlockSize, CryptoRng, RngCore, TxPadError, TxPadding, UnpadError};
+
+impl<N: BlockSize, R> TxPadding<N, R> {
+    /// Pad `msg` at the end of `dst`, drawing the random prefix from `rng`
+    ///
+    /// `dst` is grown by exactly the padded length, and left unchanged on failure.
+    pub fn pad_bytes_with_rng(
+        rng: &mut (impl RngCore + CryptoRng),
+        msg: &[u8],
+        dst: &mut BytesMut,
+    ) -> Result<(), TxPadError> {
+        let start = dst.len();
+        let end = raw::checked_padded_len(N::USIZE, msg.len())
+            .and_then(|len| len.checked_add(start))
+            .ok_or(TxPadError::MessageTooLong)?;
+        dst.resize(end, 0);
+        if let Err(e) = raw::pad_from(rng, msg, &mut dst[start..], N::USIZE) {
+            dst.truncate(start);
+            Err(e)?
+        }
+        Ok(())
+    }
+
+    /// Unpad `src`, returning the message as a slice sharing its storage
+    ///
+    /// ```
+    /// use bytes::{Bytes, BytesMut};
+    /// use tx_padding::TxPadding;
+    /// use tx_padding::consts::{U8};
+    ///
+    /// let mut buf = BytesMut::new();
+    /// TxPadding::<U8>::pad_bytes(b"test", &mut buf).unwrap();
+    /// let padded_msg = buf.freeze();
+    /// assert_eq!(TxPadding::<U8>::unpad_bytes(&padded_msg).unwrap(), &b"test"[..]);
+    /// ```
+    pub fn unpad_bytes(src: &Bytes) -> Result<Bytes, UnpadError> {
+        let range = raw::unpad_range(src, N::USIZE).map_err(|_| UnpadError)?;
+        Ok(src.slice(range))
+    }
+
+    /// Unpad `buf` in place, moving the message to its front and truncating it to the message
+    ///
+    /// The allocation is kept, so `buf` can be reused without copying the message out. `buf` is
+    /// left untouched on failure.
+    ///
+    /// ```
+    /// use bytes::BytesMut;
+    /// use tx_padding::TxPadding;
+    /// use tx_padding::consts::{U8};
+    ///
+    /// let mut buf = BytesMut::new();
+    /// TxPadding::<U8>::pad_bytes(b"test", &mut buf).unwrap();
+    /// TxPadding::<U8>::unpad_truncate_bytes(&mut buf).unwrap();
+    /// assert_eq!(&buf[..], b"test");
+    /// ```
+    pub fn unpad_truncate_bytes(buf: &mut BytesMut) -> Result<(), UnpadError> {
+        let len = raw::unpad_to_front(buf, N::USIZE).map_err(|_| UnpadError)?;
+        buf.truncate(len);
+        Ok(())
+    }
+}
+
+impl<N, R> TxPadding<N, R>
+where
+    N: BlockSize,
+    R: RngCore + CryptoRng + Default,
+{
+    /// Pad `msg` at the end of `dst`
+    pub fn pad_bytes(msg: &[u8], dst: &mut BytesMut) -> Result<(), TxPadError> {
+        Self::pad_bytes_with_rng(&mut R::default(), msg, dst)
+    }
+}
@@ -0,0 +1,119 @@
+//! `tokio_util` codec sending tx-padded frames
+
+use core::marker::PhantomData;
+use std::io;
+
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+use tokio_util::codec::{Decoder, Encoder};
+
+use crate::frames::FRAME_HEADER_LEN as HEADER_LEN;
+use crate::io::to_io_error;
+use crate::{raw, BlockSize, CryptoRng, DefaultRng, RngCore, TxPadError};
+
+/// A codec for length-delimited tx-padded frames
+///
+/// Each frame is the length of the padded message as a big-endian `u32`, followed by the padded
+/// message. Decoded frames whose length exceeds the maximum frame length, is not a multiple of
+/// the block size, or whose padding is malformed fail with `ErrorKind::InvalidData`. Nothing is
+/// written to the destination buffer if encoding a frame fails.
+///
+/// ```
+/// use bytes::{Bytes, BytesMut};
+/// use tokio_util::codec::{Decoder, Encoder};
+/// use tx_padding::TxPaddingCodec;
+/// use tx_padding::consts::{U16};
+///
+/// let mut codec = TxPaddingCodec::<U16>::new();
+/// let mut buf = BytesMut::new();
+/// codec.encode(Bytes::from_static(b"hello world"), &mut buf).unwrap();
+/// assert_eq!(buf.len(), 4 + 32);
+/// assert_eq!(codec.decode(&mut buf).unwrap().unwrap(), &b"hello world"[..]);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct TxPaddingCodec<N, R = DefaultRng> {
+    max_frame_len: usize,
+    _marker: PhantomData<fn() -> (N, R)>,
+}
+
+impl<N: BlockSize, R> TxPaddingCodec<N, R> {
+    /// Create a codec accepting padded messages up to 8 MiB
+    pub fn new() -> Self {
+        Self::with_max_frame_len(8 * 1024 * 1024)
+    }
+
+    /// Create a codec accepting padded messages up to `max_frame_len` bytes
+    pub fn with_max_frame_len(max_frame_len: usize) -> Self {
+        TxPaddingCodec {
+            max_frame_len: max_frame_len.min(u32::MAX as usize),
+            _marker: PhantomData,
+        }
+    }
+
+    /// The largest padded message this codec accepts
+    pub fn max_frame_len(&self) -> usize {
+        self.max_frame_len
+    }
+}
+
+impl<N: BlockSize, R> Default for TxPaddingCodec<N, R> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<N, R> Encoder<Bytes> for TxPaddingCodec<N, R>
+where
+    N: BlockSize,
+    R: RngCore + CryptoRng + Default,
+{
+    type Error = io::Error;
+
+    fn encode(&mut self, item: Bytes, dst: &mut BytesMut) -> io::Result<()> {
+        let padded_len = raw::checked_padded_len(N::USIZE, item.len())
+            .filter(|&len| len <= self.max_frame_len)
+            .ok_or(TxPadError::MessageTooLong)
+            .map_err(to_io_error)?;
+        let header_start = dst.len();
+        dst.reserve(HEADER_LEN + padded_len);
+        dst.put_u32(padded_len as u32);
+        let start = dst.len();
+        dst.resize(start + padded_len, 0);
+        if let Err(e) = raw::pad_from(&mut R::default(), &item, &mut dst[start..], N::USIZE) {
+            dst.truncate(header_start);
+            Err(to_io_error(e))?
+        }
+        Ok(())
+    }
+}
+
+impl<N: BlockSize, R> Decoder for TxPaddingCodec<N, R> {
+    type Item = BytesMut;
+    type Error = io::Error;
+
+    fn decode(&mut self, src: &mut BytesMut) -> io::Result<Option<BytesMut>> {
+        if src.len() < HEADER_LEN {
+            return Ok(None);
+        }
+        let mut header = [0; HEADER_LEN];
+        header.copy_from_slice(&src[..HEADER_LEN]);
+        let padded_len = u32::from_be_bytes(header) as usize;
+        if padded_len == 0 || padded_len % N::USIZE != 0 || padded_len > self.max_frame_len {
+            Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "invalid frame length",
+            ))?
+        }
+        if src.len() < HEADER_LEN + padded_len {
+            src.reserve(HEADER_LEN + padded_len - src.len());
+            return Ok(None);
+        }
+
+        src.advance(HEADER_LEN);
+        let mut frame = src.split_to(padded_len);
+        let range = raw::unpad_range(&frame, N::USIZE)
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "malformed padding"))?;
+        frame.truncate(range.end);
+        frame.advance(range.start);
+        Ok(Some(frame))
+    }
+}
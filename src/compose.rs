@@ -0,0 +1,48 @@
+use core::convert::Infallible;
+use core::marker::PhantomData;
+
+use crate::{PadError, Padding, UnpadError};
+
+/// Padding applying `Inner` and then `Outer` to the result
+///
+/// Unpadding removes `Outer` first and then `Inner`. Both paddings are applied in the same buffer
+/// with the same block size, so it must be large enough for the output of `Outer` on the output
+/// of `Inner`, e.g. one more block than `TxPadding` needs when `Outer` is PKCS#7. Padding block
+/// by block is not supported.
+///
+/// ```
+/// # #[cfg(any(feature = "os-rng", feature = "thread-rng"))]
+/// # {
+/// use tx_padding::{Compose, TxPadding, Padding};
+/// use tx_padding::consts::{U8};
+/// use block_padding::Pkcs7;
+///
+/// type Layered = Compose<Pkcs7, TxPadding<U8>>;
+///
+/// let mut buffer = [0; 24];
+/// buffer[..4].copy_from_slice(b"test");
+/// let padded_msg = Layered::pad(&mut buffer, 4, 8).unwrap();
+/// assert_eq!(padded_msg.len(), 24);
+/// assert_eq!(&padded_msg[16..], &[8; 8]);
+/// assert_eq!(Layered::unpad(padded_msg).unwrap(), b"test");
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub enum Compose<Outer, Inner> {
+    _Phantom(Infallible, PhantomData<(Outer, Inner)>),
+}
+
+impl<Outer: Padding, Inner: Padding> Padding for Compose<Outer, Inner> {
+    fn pad_block(_block: &mut [u8], _pos: usize) -> Result<(), PadError> {
+        Err(PadError)
+    }
+
+    fn unpad(data: &[u8]) -> Result<&[u8], UnpadError> {
+        Inner::unpad(Outer::unpad(data)?)
+    }
+
+    fn pad(buf: &mut [u8], pos: usize, block_size: usize) -> Result<&mut [u8], PadError> {
+        let inner_len = Inner::pad(buf, pos, block_size)?.len();
+        Outer::pad(buf, inner_len, block_size)
+    }
+}
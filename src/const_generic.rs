@@ -0,0 +1,186 @@
+use core::convert::Infallible;
+use core::marker::PhantomData;
+use core::ops::Range;
+
+#[cfg(feature = "diagnostics")]
+use crate::UnpadErrorKind;
+use crate::{raw, CryptoRng, DefaultRng, PadError, Padding, RngCore, TxPadError, UnpadError};
+
+/// `TxPadding` with the block size given as a const generic parameter instead of a `typenum` type
+///
+/// The two types produce and accept exactly the same padded messages. `N` must be a power of 2
+/// between 2 and 256 inclusive, which is checked at compile time.
+///
+/// It supports a subset of the API of `TxPadding`: the length helpers and constants, `pad`,
+/// the unpadding methods, `pad_min`, `pad_aligned` and `unpad_prefix`, along with the vector,
+/// `heapless` and constant-time helpers when their features are enabled. The other methods,
+/// such as `pad_into`, the framing or the block cipher helpers, are only provided by
+/// `TxPadding`. Since the padded messages are the same, they can be used through the matching
+/// `TxPadding` type, e.g. `TxPadding<U16>` for `TxPaddingConst<16>`.
+///
+/// ```
+/// # #[cfg(any(feature = "os-rng", feature = "thread-rng"))]
+/// # {
+/// use tx_padding::{TxPaddingConst, Padding};
+///
+/// let mut buffer = [0; TxPaddingConst::<8>::padded_len(4)];
+/// buffer[..4].copy_from_slice(b"test");
+/// let padded_msg = TxPaddingConst::<8>::pad(&mut buffer, 4, 8).unwrap();
+/// assert_eq!(TxPaddingConst::<8>::unpad(&padded_msg).unwrap(), b"test");
+/// # }
+/// ```
+/// ```compile_fail
+/// use tx_padding::TxPaddingConst;
+///
+/// let _ = TxPaddingConst::<12>::padded_len(4);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub enum TxPaddingConst<const N: usize, R = DefaultRng> {
+    _Phantom(Infallible, PhantomData<fn() -> R>),
+}
+
+impl<const N: usize, R> TxPaddingConst<N, R> {
+    pub(crate) const VALID_BLOCK_SIZE: () = assert!(
+        N.is_power_of_two() && N >= 2 && N <= 256,
+        "block size must be a power of 2 between 2 and 256"
+    );
+
+    /// Length of the padded output for a message of length `msg_len`
+    ///
+    /// This is also the minimal length of the buffer passed to the padding methods.
+    pub const fn padded_len(msg_len: usize) -> usize {
+        let () = Self::VALID_BLOCK_SIZE;
+        raw::padded_len(N, msg_len)
+    }
+
+    /// Length of the padded output for a message of length `msg_len`, or `None` if it overflows
+    /// `usize`
+    pub const fn checked_padded_len(msg_len: usize) -> Option<usize> {
+        let () = Self::VALID_BLOCK_SIZE;
+        raw::checked_padded_len(N, msg_len)
+    }
+
+    /// Number of bytes the padding adds to a message of length `msg_len`
+    pub const fn overhead(msg_len: usize) -> usize {
+        Self::padded_len(msg_len) - msg_len
+    }
+
+    /// Largest number of bytes the padding adds to a message of any length
+    pub const fn max_overhead() -> usize {
+        let () = Self::VALID_BLOCK_SIZE;
+        raw::max_overhead(N)
+    }
+
+    /// Block size of the scheme, i.e. `N`
+    pub const BLOCK_SIZE: usize = {
+        let () = Self::VALID_BLOCK_SIZE;
+        N
+    };
+
+    /// Smallest number of bytes the padding adds to a message of any length
+    pub const MIN_OVERHEAD: usize = {
+        let () = Self::VALID_BLOCK_SIZE;
+        raw::min_overhead(N)
+    };
+
+    /// Largest number of bytes the padding adds to a message of any length
+    pub const MAX_OVERHEAD: usize = {
+        let () = Self::VALID_BLOCK_SIZE;
+        raw::max_overhead(N)
+    };
+
+    /// Longest message the padding methods accept, see [`TxPadding::MAX_MSG_LEN`]
+    ///
+    /// [`TxPadding::MAX_MSG_LEN`]: crate::TxPadding::MAX_MSG_LEN
+    pub const MAX_MSG_LEN: usize = {
+        let () = Self::VALID_BLOCK_SIZE;
+        raw::max_msg_len(N)
+    };
+
+    /// Pads message with length `pos` in the provided buffer, drawing the random prefix from `rng`
+    pub fn pad_with_rng<'a>(
+        rng: &mut (impl RngCore + CryptoRng),
+        buf: &'a mut [u8],
+        pos: usize,
+    ) -> Result<&'a mut [u8], TxPadError> {
+        no_panic! {
+            let () = Self::VALID_BLOCK_SIZE;
+            raw::pad(rng, buf, pos, N)
+        }
+    }
+
+    /// Unpad given `data`, returning the message
+    ///
+    /// The same as `Padding::unpad`, but available whichever RNG features are enabled.
+    pub fn unpad(data: &[u8]) -> Result<&[u8], UnpadError> {
+        no_panic! {
+            let () = Self::VALID_BLOCK_SIZE;
+            raw::unpad(data, N).map_err(|_| UnpadError)
+        }
+    }
+
+    /// Position of the message in the padded `data`
+    pub fn unpad_range(data: &[u8]) -> Result<Range<usize>, UnpadError> {
+        no_panic! {
+            let () = Self::VALID_BLOCK_SIZE;
+            raw::unpad_range(data, N).map_err(|_| UnpadError)
+        }
+    }
+
+    /// Unpad given `data`, additionally requiring its length to be a multiple of the block size
+    pub fn unpad_strict(data: &[u8]) -> Result<&[u8], UnpadError> {
+        no_panic! {
+            let () = Self::VALID_BLOCK_SIZE;
+            raw::unpad_strict(data, N).map_err(|_| UnpadError)
+        }
+    }
+
+    /// Whether `data` is a well-formed padded message
+    pub fn is_valid(data: &[u8]) -> bool {
+        no_panic! {
+            let () = Self::VALID_BLOCK_SIZE;
+            raw::is_valid(data, N)
+        }
+    }
+
+    /// Unpad given `data` whose integrity is already established, without checking the trailing
+    /// zeros
+    pub fn unpad_trusted(data: &[u8]) -> Result<&[u8], UnpadError> {
+        no_panic! {
+            let () = Self::VALID_BLOCK_SIZE;
+            raw::unpad_trusted(data, N).map_err(|_| UnpadError)
+        }
+    }
+
+    /// Unpad given `data`, reporting why it is malformed on failure
+    #[cfg(feature = "diagnostics")]
+    pub fn unpad_verbose(data: &[u8]) -> Result<&[u8], UnpadErrorKind> {
+        no_panic! {
+            let () = Self::VALID_BLOCK_SIZE;
+            raw::unpad(data, N)
+        }
+    }
+}
+
+impl<const N: usize, R> Padding for TxPaddingConst<N, R>
+where
+    R: RngCore + CryptoRng + Default,
+{
+    fn pad_block(_block: &mut [u8], _pos: usize) -> Result<(), PadError> {
+        Err(PadError)
+    }
+
+    fn unpad(data: &[u8]) -> Result<&[u8], UnpadError> {
+        Self::unpad(data)
+    }
+
+    fn pad(buf: &mut [u8], pos: usize, block_size: usize) -> Result<&mut [u8], PadError> {
+        if block_size != N {
+            Err(TxPadError::BlockSizeMismatch {
+                expected: N,
+                got: block_size,
+            })?
+        }
+        Ok(Self::pad_with_rng(&mut R::default(), buf, pos)?)
+    }
+}
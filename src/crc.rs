@@ -0,0 +1,182 @@
+use core::convert::Infallible;
+use core::marker::PhantomData;
+use core::ops::Range;
+
+use crate::error::UnpadErrorKind;
+use crate::{
+    raw, BlockSize, CryptoRng, DefaultRng, PadError, Padding, RngCore, TxPadError, UnpadError,
+};
+
+/// Table of the CRC-8 with polynomial 0x07
+const CRC8_TABLE: [u8; 256] = {
+    let mut table = [0; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u8;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 0x80 != 0 {
+                (crc << 1) ^ 0x07
+            } else {
+                crc << 1
+            };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+};
+
+/// Table of the reflected CRC-32 with polynomial 0x04c11db7, as used by Ethernet and zlib
+const CRC32_TABLE: [u32; 256] = {
+    let mut table = [0; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ 0xedb8_8320
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+};
+
+fn crc8(data: &[u8]) -> u8 {
+    data.iter()
+        .fold(0, |crc, &v| CRC8_TABLE[(crc ^ v) as usize])
+}
+
+fn crc32(data: &[u8]) -> u32 {
+    !data.iter().fold(!0, |crc, &v| {
+        CRC32_TABLE[((crc ^ v as u32) & 0xff) as usize] ^ (crc >> 8)
+    })
+}
+
+/// Length of the checksum stored in the padding zeros of blocks of `block_size`
+const fn checksum_len(block_size: usize) -> usize {
+    if block_size >= 8 {
+        4
+    } else {
+        1
+    }
+}
+
+/// Write the checksum of `data` into `out`, which is `checksum_len(block_size)` bytes long
+fn write_checksum(data: &[u8], out: &mut [u8]) {
+    if out.len() == 4 {
+        out.copy_from_slice(&crc32(data).to_be_bytes());
+    } else {
+        out[0] = crc8(data);
+    }
+}
+
+/// The sibling of `TxPadding` which stores a checksum of the padded message in its padding zeros
+///
+/// The first bytes of the `N - 1` trailing zeros are replaced by a checksum of everything
+/// preceding them, i.e. the header, the random bytes and the message: a CRC-8 for blocks of 2 and
+/// 4 bytes and a big-endian CRC-32 for larger ones. The padded length is the same as for
+/// `TxPadding`, and unpadding rejects any padded message whose checksum does not match, which
+/// catches corruption that `TxPadding` would only notice if it hit the header or the zeros.
+///
+/// ```
+/// # #[cfg(any(feature = "os-rng", feature = "thread-rng"))]
+/// # {
+/// use tx_padding::{TxPaddingCrc, Padding};
+/// use tx_padding::consts::{U8};
+///
+/// let mut buffer = [0; 16];
+/// buffer[..4].copy_from_slice(b"test");
+/// let padded_msg = TxPaddingCrc::<U8>::pad(&mut buffer, 4, 8).unwrap();
+/// assert_eq!(TxPaddingCrc::<U8>::unpad(padded_msg).unwrap(), b"test");
+/// padded_msg[6] ^= 1;
+/// assert!(TxPaddingCrc::<U8>::unpad(padded_msg).is_err());
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub enum TxPaddingCrc<N, R = DefaultRng> {
+    _Phantom(Infallible, PhantomData<fn() -> (N, R)>),
+}
+
+impl<N: BlockSize, R> TxPaddingCrc<N, R> {
+    /// Length of the padded output for a message of length `msg_len`
+    pub const fn padded_len(msg_len: usize) -> usize {
+        raw::padded_len(N::USIZE, msg_len)
+    }
+
+    /// Pads message with length `pos` in the provided buffer, drawing the random prefix from `rng`
+    pub fn pad_with_rng<'a>(
+        rng: &mut (impl RngCore + CryptoRng),
+        buf: &'a mut [u8],
+        pos: usize,
+    ) -> Result<&'a mut [u8], TxPadError> {
+        let padded_msg = raw::pad(rng, buf, pos, N::USIZE)?;
+        let (covered, zeros) = padded_msg.split_at_mut(padded_msg.len() - (N::USIZE - 1));
+        write_checksum(covered, &mut zeros[..checksum_len(N::USIZE)]);
+        Ok(padded_msg)
+    }
+
+    /// Unpad given `data`, returning the message
+    ///
+    /// `Padding::unpad` delegates here, but this does not require a default RNG.
+    pub fn unpad(data: &[u8]) -> Result<&[u8], UnpadError> {
+        let range = Self::unpad_range(data).map_err(|_| UnpadError)?;
+        Ok(&data[range])
+    }
+
+    /// Unpad given `data`, reporting why it is malformed on failure
+    #[cfg(feature = "diagnostics")]
+    pub fn unpad_verbose(data: &[u8]) -> Result<&[u8], UnpadErrorKind> {
+        Self::unpad_range(data).map(|range| &data[range])
+    }
+
+    fn unpad_range(data: &[u8]) -> Result<Range<usize>, UnpadErrorKind> {
+        if data.is_empty() {
+            Err(UnpadErrorKind::EmptyInput)?
+        }
+        let l = data.len();
+        let pad_zero = N::USIZE - 1;
+        let pad_len = raw::declared_pad_len(l, data[0], N::USIZE)?;
+        let checksum_end = l - pad_zero + checksum_len(N::USIZE);
+        raw::check_zeros(data, checksum_end..l, 0)?;
+
+        let mut checksum = [0; 4];
+        let checksum = &mut checksum[..checksum_len(N::USIZE)];
+        write_checksum(&data[..l - pad_zero], checksum);
+        if checksum[..] != data[l - pad_zero..checksum_end] {
+            Err(UnpadErrorKind::ChecksumMismatch)?
+        }
+        Ok(1 + pad_len..l - pad_zero)
+    }
+}
+
+impl<N, R> Padding for TxPaddingCrc<N, R>
+where
+    N: BlockSize,
+    R: RngCore + CryptoRng + Default,
+{
+    fn pad_block(_block: &mut [u8], _pos: usize) -> Result<(), PadError> {
+        Err(PadError)
+    }
+
+    fn unpad(data: &[u8]) -> Result<&[u8], UnpadError> {
+        Self::unpad(data)
+    }
+
+    fn pad(buf: &mut [u8], pos: usize, block_size: usize) -> Result<&mut [u8], PadError> {
+        if block_size != N::USIZE {
+            Err(TxPadError::BlockSizeMismatch {
+                expected: N::USIZE,
+                got: block_size,
+            })?
+        }
+        Ok(Self::pad_with_rng(&mut R::default(), buf, pos)?)
+    }
+}
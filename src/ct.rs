@@ -0,0 +1,90 @@
+use subtle::{Choice, ConditionallySelectable, ConstantTimeEq, ConstantTimeLess, CtOption};
+
+use crate::{BlockSize, TxPadding, TxPaddingConst};
+
+impl<N: BlockSize, R> TxPadding<N, R> {
+    /// Unpad given `data` in constant time
+    ///
+    /// Unlike `Padding::unpad`, which returns as soon as it finds the padding malformed, this
+    /// evaluates every check regardless of the outcome of the previous ones, so the time taken
+    /// only depends on the length of `data`. Use it when the unpadding result is observable by
+    /// an attacker, e.g. after CBC decryption.
+    ///
+    /// ```
+    /// use tx_padding::TxPadding;
+    /// use tx_padding::consts::{U8};
+    ///
+    /// let padded_msg = [0xf9, 0, 0, 0, 1, 2, 3, 4, 5, 0, 0, 0, 0, 0, 0, 0];
+    /// let msg = TxPadding::<U8>::unpad_ct(&padded_msg);
+    /// assert_eq!(msg.unwrap(), &[1, 2, 3, 4, 5]);
+    /// assert!(bool::from(TxPadding::<U8>::unpad_ct(&padded_msg[..15]).is_none()));
+    /// ```
+    pub fn unpad_ct(data: &[u8]) -> CtOption<&[u8]> {
+        unpad_ct(data, N::USIZE)
+    }
+
+    /// Whether `data` is a well-formed padded message, computed in constant time
+    ///
+    /// Accepts exactly the same inputs as [`unpad_ct`](TxPadding::unpad_ct), with the same
+    /// timing guarantee.
+    ///
+    /// ```
+    /// use tx_padding::TxPadding;
+    /// use tx_padding::consts::{U8};
+    ///
+    /// let padded_msg = [0xf9, 0, 0, 0, 1, 2, 3, 4, 5, 0, 0, 0, 0, 0, 0, 0];
+    /// assert!(bool::from(TxPadding::<U8>::is_valid_ct(&padded_msg)));
+    /// assert!(!bool::from(TxPadding::<U8>::is_valid_ct(&padded_msg[..15])));
+    /// ```
+    pub fn is_valid_ct(data: &[u8]) -> Choice {
+        validate_ct(data, N::USIZE).0
+    }
+}
+
+impl<const N: usize, R> TxPaddingConst<N, R> {
+    /// Unpad given `data` in constant time
+    ///
+    /// See [`TxPadding::unpad_ct`].
+    pub fn unpad_ct(data: &[u8]) -> CtOption<&[u8]> {
+        let () = Self::VALID_BLOCK_SIZE;
+        unpad_ct(data, N)
+    }
+
+    /// Whether `data` is a well-formed padded message, computed in constant time
+    pub fn is_valid_ct(data: &[u8]) -> Choice {
+        let () = Self::VALID_BLOCK_SIZE;
+        validate_ct(data, N).0
+    }
+}
+
+pub(crate) fn unpad_ct(data: &[u8], block_size: usize) -> CtOption<&[u8]> {
+    let (valid, start, end) = validate_ct(data, block_size);
+    let start = u64::conditional_select(&0, &start, valid);
+    let end = u64::conditional_select(&0, &end, valid);
+
+    // `start <= end` holds whenever `valid` is set, and both are 0 otherwise
+    CtOption::new(&data[start as usize..end as usize], valid)
+}
+
+/// Whether `data` is well-formed, and the bounds of the message if it is
+fn validate_ct(data: &[u8], block_size: usize) -> (Choice, u64, u64) {
+    // The length of `data` is public, only its content is treated as secret
+    let l = data.len() as u64;
+    let block_size = block_size as u64;
+    let pad_zero = block_size - 1;
+
+    let non_empty = !l.ct_eq(&0);
+    let header = data.first().copied().unwrap_or(0);
+    let pad_len = u64::from(header & (pad_zero as u8)) + 2;
+    let long_enough = !l.ct_lt(&(pad_len + block_size));
+
+    let tail = data.len().saturating_sub(pad_zero as usize);
+    let tail_or = data[tail..].iter().fold(0u8, |acc, &v| acc | v);
+    let zero_tail = tail_or.ct_eq(&0);
+
+    (
+        non_empty & long_enough & zero_tail,
+        1 + pad_len,
+        tail as u64,
+    )
+}
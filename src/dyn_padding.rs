@@ -0,0 +1,177 @@
+//! Padding schemes selected at runtime and used through trait objects
+
+use core::marker::PhantomData;
+
+use crate::consts::{U128, U16, U2, U256, U32, U4, U64, U8};
+use crate::{
+    raw, BlockSize, BucketPadding, CryptoRng, Padding, Padme, RngCore, TxPadError, TxPadding,
+    TxPaddingDyn, TxRandomTailPadding, TxTailPadding, UnpadError,
+};
+
+/// Object-safe interface of a padding scheme with its block size fixed
+///
+/// Unlike `Padding`, whose methods are associated functions of the type, every method takes
+/// `&self`, so a scheme chosen at runtime, e.g. from a configuration file, can be stored as
+/// `&dyn DynPadding` or `Box<dyn DynPadding>`.
+///
+/// The padding types of the crate carry no data, so the implementations are on `PhantomData` of
+/// them, e.g. `PhantomData<TxPadding<N>>`, except for [`BucketPadding`] which holds its table.
+/// [`TxPadding::as_dyn`] and [`TxPaddingDyn::select`] return them as `&'static dyn DynPadding`.
+///
+/// ```
+/// # #[cfg(any(feature = "os-rng", feature = "thread-rng"))]
+/// # {
+/// use tx_padding::{DynPadding, TxPaddingDyn};
+///
+/// let block_size = 16; // e.g. read from a configuration file
+/// let scheme: &dyn DynPadding = <TxPaddingDyn>::select(block_size).unwrap();
+/// let mut buffer = [0; 32];
+/// buffer[..4].copy_from_slice(b"test");
+/// let padded_msg = scheme.pad(&mut buffer, 4).unwrap();
+/// assert_eq!(padded_msg.len(), scheme.padded_len(4).unwrap());
+/// assert_eq!(scheme.unpad(padded_msg).unwrap(), b"test");
+/// # }
+/// ```
+pub trait DynPadding {
+    /// Block size of the scheme
+    fn block_size(&self) -> usize;
+
+    /// Length of the padded output for a message of length `msg_len`, or `None` if it overflows
+    /// `usize`
+    fn padded_len(&self, msg_len: usize) -> Option<usize>;
+
+    /// Pads message with length `pos` in the provided buffer
+    fn pad<'a>(&self, buf: &'a mut [u8], pos: usize) -> Result<&'a mut [u8], TxPadError>;
+
+    /// Unpad given `data`
+    fn unpad<'a>(&self, data: &'a [u8]) -> Result<&'a [u8], UnpadError>;
+}
+
+impl<N, R> DynPadding for PhantomData<TxPadding<N, R>>
+where
+    N: BlockSize,
+    R: RngCore + CryptoRng + Default,
+{
+    fn block_size(&self) -> usize {
+        N::USIZE
+    }
+
+    fn padded_len(&self, msg_len: usize) -> Option<usize> {
+        TxPadding::<N, R>::checked_padded_len(msg_len)
+    }
+
+    fn pad<'a>(&self, buf: &'a mut [u8], pos: usize) -> Result<&'a mut [u8], TxPadError> {
+        TxPadding::<N, R>::pad_with_rng(&mut R::default(), buf, pos)
+    }
+
+    fn unpad<'a>(&self, data: &'a [u8]) -> Result<&'a [u8], UnpadError> {
+        raw::unpad(data, N::USIZE).map_err(|_| UnpadError)
+    }
+}
+
+// The siblings of `TxPadding` with the same padded lengths
+macro_rules! impl_dyn_padding {
+    ($($scheme:ident),*) => {
+        $(
+            impl<N, R> DynPadding for PhantomData<$scheme<N, R>>
+            where
+                N: BlockSize,
+                R: RngCore + CryptoRng + Default,
+            {
+                fn block_size(&self) -> usize {
+                    N::USIZE
+                }
+
+                fn padded_len(&self, msg_len: usize) -> Option<usize> {
+                    raw::checked_padded_len(N::USIZE, msg_len)
+                }
+
+                fn pad<'a>(&self, buf: &'a mut [u8], pos: usize) -> Result<&'a mut [u8], TxPadError> {
+                    $scheme::<N, R>::pad_with_rng(&mut R::default(), buf, pos)
+                }
+
+                fn unpad<'a>(&self, data: &'a [u8]) -> Result<&'a [u8], UnpadError> {
+                    <$scheme<N, R> as Padding>::unpad(data)
+                }
+            }
+        )*
+    };
+}
+
+impl_dyn_padding!(TxTailPadding, TxRandomTailPadding);
+
+impl<N, R> DynPadding for PhantomData<Padme<N, R>>
+where
+    N: BlockSize,
+    R: RngCore + CryptoRng + Default,
+{
+    fn block_size(&self) -> usize {
+        N::USIZE
+    }
+
+    fn padded_len(&self, msg_len: usize) -> Option<usize> {
+        if msg_len > isize::MAX as usize - raw::LEN_FIELD {
+            return None;
+        }
+        Some(Padme::<N, R>::padded_len(msg_len))
+    }
+
+    fn pad<'a>(&self, buf: &'a mut [u8], pos: usize) -> Result<&'a mut [u8], TxPadError> {
+        Padme::<N, R>::pad_with_rng(&mut R::default(), buf, pos)
+    }
+
+    fn unpad<'a>(&self, data: &'a [u8]) -> Result<&'a [u8], UnpadError> {
+        <Padme<N, R> as Padding>::unpad(data)
+    }
+}
+
+impl<'b, N, R> DynPadding for BucketPadding<'b, N, R>
+where
+    N: BlockSize,
+    R: RngCore + CryptoRng + Default,
+{
+    fn block_size(&self) -> usize {
+        N::USIZE
+    }
+
+    fn padded_len(&self, msg_len: usize) -> Option<usize> {
+        BucketPadding::padded_len(self, msg_len)
+    }
+
+    fn pad<'a>(&self, buf: &'a mut [u8], pos: usize) -> Result<&'a mut [u8], TxPadError> {
+        BucketPadding::pad(self, buf, pos)
+    }
+
+    fn unpad<'a>(&self, data: &'a [u8]) -> Result<&'a [u8], UnpadError> {
+        BucketPadding::unpad(self, data)
+    }
+}
+
+impl<N, R> TxPadding<N, R>
+where
+    N: BlockSize,
+    R: RngCore + CryptoRng + Default + 'static,
+{
+    /// The scheme as a trait object
+    pub const fn as_dyn() -> &'static dyn DynPadding {
+        &PhantomData::<Self>
+    }
+}
+
+impl<R: RngCore + CryptoRng + Default + 'static> TxPaddingDyn<R> {
+    /// The scheme with blocks of `block_size` as a trait object, or `None` if `block_size` is not
+    /// supported
+    pub const fn select(block_size: usize) -> Option<&'static dyn DynPadding> {
+        Some(match block_size {
+            2 => TxPadding::<U2, R>::as_dyn(),
+            4 => TxPadding::<U4, R>::as_dyn(),
+            8 => TxPadding::<U8, R>::as_dyn(),
+            16 => TxPadding::<U16, R>::as_dyn(),
+            32 => TxPadding::<U32, R>::as_dyn(),
+            64 => TxPadding::<U64, R>::as_dyn(),
+            128 => TxPadding::<U128, R>::as_dyn(),
+            256 => TxPadding::<U256, R>::as_dyn(),
+            _ => return None,
+        })
+    }
+}
@@ -0,0 +1,99 @@
+use core::convert::Infallible;
+use core::marker::PhantomData;
+
+use crate::{raw, CryptoRng, DefaultRng, RngCore, TxPadError, UnpadError};
+
+/// `TxPadding` with the block size chosen at runtime
+///
+/// This is useful when the block size is not known at compile time, e.g. when it is negotiated
+/// during a protocol handshake. The padded messages are the same as the ones of `TxPadding`
+/// with the same block size, which must be a power of 2 between 2 and 256 inclusive.
+///
+/// ```
+/// # #[cfg(any(feature = "os-rng", feature = "thread-rng"))]
+/// # {
+/// use tx_padding::TxPaddingDyn;
+///
+/// let block_size = 8;
+/// let mut buffer = [0; 16];
+/// buffer[..4].copy_from_slice(b"test");
+/// let padded_msg = <TxPaddingDyn>::pad(&mut buffer, 4, block_size).unwrap();
+/// assert_eq!(<TxPaddingDyn>::unpad(&padded_msg, block_size).unwrap(), b"test");
+/// assert!(<TxPaddingDyn>::unpad(&padded_msg, 12).is_err());
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub enum TxPaddingDyn<R = DefaultRng> {
+    _Phantom(Infallible, PhantomData<fn() -> R>),
+}
+
+impl<R> TxPaddingDyn<R> {
+    /// Whether `block_size` is supported by the padding scheme
+    pub const fn is_valid_block_size(block_size: usize) -> bool {
+        block_size.is_power_of_two() && block_size >= 2 && block_size <= 256
+    }
+
+    /// Length of the padded output for a message of length `msg_len`
+    ///
+    /// Returns `None` if `block_size` is not supported or the length overflows `usize`.
+    pub const fn padded_len(msg_len: usize, block_size: usize) -> Option<usize> {
+        if Self::is_valid_block_size(block_size) {
+            raw::checked_padded_len(block_size, msg_len)
+        } else {
+            None
+        }
+    }
+
+    /// Pads message with length `pos` in the provided buffer, drawing the random prefix from `rng`
+    pub fn pad_with_rng<'a>(
+        rng: &mut (impl RngCore + CryptoRng),
+        buf: &'a mut [u8],
+        pos: usize,
+        block_size: usize,
+    ) -> Result<&'a mut [u8], TxPadError> {
+        if !Self::is_valid_block_size(block_size) {
+            Err(TxPadError::InvalidBlockSize { got: block_size })?
+        }
+        raw::pad(rng, buf, pos, block_size)
+    }
+
+    /// Unpad given `data` which was padded with blocks of `block_size`
+    pub fn unpad(data: &[u8], block_size: usize) -> Result<&[u8], UnpadError> {
+        if !Self::is_valid_block_size(block_size) {
+            Err(UnpadError)?
+        }
+        raw::unpad(data, block_size).map_err(|_| UnpadError)
+    }
+
+    /// Unpad given `data` which was padded with blocks of `block_size`, additionally requiring
+    /// its length to be a multiple of `block_size`
+    pub fn unpad_strict(data: &[u8], block_size: usize) -> Result<&[u8], UnpadError> {
+        if !Self::is_valid_block_size(block_size) {
+            Err(UnpadError)?
+        }
+        raw::unpad_strict(data, block_size).map_err(|_| UnpadError)
+    }
+
+    /// Whether `data` is a well-formed message padded with blocks of `block_size`
+    ///
+    /// An invalid `block_size` makes every input invalid.
+    pub fn is_valid(data: &[u8], block_size: usize) -> bool {
+        Self::is_valid_block_size(block_size) && raw::is_valid(data, block_size)
+    }
+
+    /// Unpad given `data` which was padded with blocks of `block_size` and whose integrity is
+    /// already established, without checking the trailing zeros
+    pub fn unpad_trusted(data: &[u8], block_size: usize) -> Result<&[u8], UnpadError> {
+        if !Self::is_valid_block_size(block_size) {
+            Err(UnpadError)?
+        }
+        raw::unpad_trusted(data, block_size).map_err(|_| UnpadError)
+    }
+}
+
+impl<R: RngCore + CryptoRng + Default> TxPaddingDyn<R> {
+    /// Pads message with length `pos` in the provided buffer, in blocks of `block_size`
+    pub fn pad(buf: &mut [u8], pos: usize, block_size: usize) -> Result<&mut [u8], TxPadError> {
+        Self::pad_with_rng(&mut R::default(), buf, pos, block_size)
+    }
+}
@@ -0,0 +1,225 @@
+//! Adapters padding and unpadding through `embedded_io`, mirroring the `std::io` ones
+
+use core::fmt;
+
+use embedded_io::{ErrorKind, ErrorType, Read, Write};
+
+use crate::stream::MAX_PENDING;
+use crate::{BlockSize, CryptoRng, DefaultRng, Padder, RngCore, TxPadError, Unpadder};
+
+/// Size of the scratch buffer the padded output is staged in, kept small for stack usage
+const SCRATCH_LEN: usize = 512;
+
+/// Error of the `embedded_io` adapters
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Error<E> {
+    /// The inner reader or writer failed
+    Io(E),
+    /// Padding the message failed
+    Pad(TxPadError),
+    /// The padded message read is malformed
+    MalformedPadding,
+}
+
+impl<E: fmt::Debug> fmt::Display for Error<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io(e) => write!(f, "I/O error: {:?}", e),
+            Error::Pad(e) => write!(f, "padding failed: {}", e),
+            Error::MalformedPadding => f.write_str("malformed padding"),
+        }
+    }
+}
+
+#[rustversion::since(1.81)]
+impl<E: fmt::Debug> core::error::Error for Error<E> {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            Error::Pad(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+#[rustversion::before(1.81)]
+impl<E: fmt::Debug> std::error::Error for Error<E> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Pad(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl<E: embedded_io::Error> embedded_io::Error for Error<E> {
+    fn kind(&self) -> ErrorKind {
+        match self {
+            Error::Io(e) => e.kind(),
+            Error::Pad(TxPadError::RngFailure) => ErrorKind::Other,
+            Error::Pad(_) => ErrorKind::InvalidInput,
+            Error::MalformedPadding => ErrorKind::InvalidData,
+        }
+    }
+}
+
+/// A writer padding everything written to it before passing it to the inner writer
+///
+/// See [`PadWriter`](crate::PadWriter) for the `std::io` counterpart.
+///
+/// ```
+/// use embedded_io::Write;
+/// use tx_padding::embedded::PadWriter;
+/// use tx_padding::{TxPadding, Padding};
+/// use tx_padding::consts::{U8};
+///
+/// let mut buffer = [0; 24];
+/// let mut writer = PadWriter::<_, U8>::new(&mut buffer[..], 11).unwrap();
+/// writer.write_all(b"hello ").unwrap();
+/// writer.write_all(b"world").unwrap();
+/// writer.finish().unwrap();
+/// assert_eq!(TxPadding::<U8>::unpad(&buffer).unwrap(), b"hello world");
+/// ```
+#[derive(Debug)]
+pub struct PadWriter<W, N, R = DefaultRng> {
+    inner: W,
+    padder: Padder<N, R>,
+}
+
+impl<W: Write, N: BlockSize, R> PadWriter<W, N, R> {
+    /// Pad a message of `msg_len` bytes into `inner`, drawing the random prefix from `rng`
+    pub fn with_rng(
+        inner: W,
+        rng: &mut (impl RngCore + CryptoRng),
+        msg_len: usize,
+    ) -> Result<Self, Error<W::Error>> {
+        Ok(PadWriter {
+            inner,
+            padder: Padder::with_rng(rng, msg_len).map_err(Error::Pad)?,
+        })
+    }
+
+    /// Get a reference to the inner writer
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Write the end of the padded message and return the inner writer
+    pub fn finish(mut self) -> Result<W, Error<W::Error>> {
+        let mut scratch = [0; MAX_PENDING + 256];
+        let n = self.padder.finalize(&mut scratch).map_err(Error::Pad)?;
+        self.inner.write_all(&scratch[..n]).map_err(Error::Io)?;
+        self.inner.flush().map_err(Error::Io)?;
+        Ok(self.inner)
+    }
+}
+
+impl<W, N, R> PadWriter<W, N, R>
+where
+    W: Write,
+    N: BlockSize,
+    R: RngCore + CryptoRng + Default,
+{
+    /// Pad a message of `msg_len` bytes into `inner`
+    pub fn new(inner: W, msg_len: usize) -> Result<Self, Error<W::Error>> {
+        Self::with_rng(inner, &mut R::default(), msg_len)
+    }
+}
+
+impl<W: Write, N, R> ErrorType for PadWriter<W, N, R> {
+    type Error = Error<W::Error>;
+}
+
+impl<W: Write, N: BlockSize, R> Write for PadWriter<W, N, R> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+        let mut scratch = [0; SCRATCH_LEN];
+        let buf = &buf[..buf.len().min(SCRATCH_LEN - MAX_PENDING)];
+        let n = self.padder.update(buf, &mut scratch).map_err(Error::Pad)?;
+        self.inner.write_all(&scratch[..n]).map_err(Error::Io)?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        self.inner.flush().map_err(Error::Io)
+    }
+}
+
+/// A reader unpadding a padded message read from the inner reader
+///
+/// See [`UnpadReader`](crate::UnpadReader) for the `std::io` counterpart. The padding is only
+/// validated once the inner reader reaches the end of file.
+///
+/// ```
+/// use embedded_io::Read;
+/// use tx_padding::embedded::UnpadReader;
+/// use tx_padding::{TxPadding, Padding};
+/// use tx_padding::consts::{U8};
+///
+/// let mut buffer = [0; 24];
+/// buffer[..11].copy_from_slice(b"hello world");
+/// let padded_msg = TxPadding::<U8>::pad(&mut buffer, 11, 8).unwrap();
+/// let mut reader = UnpadReader::<_, U8>::new(&padded_msg[..]);
+/// let mut msg = [0; 11];
+/// reader.read_exact(&mut msg).unwrap();
+/// assert_eq!(reader.read(&mut [0; 1]).unwrap(), 0);
+/// assert_eq!(&msg, b"hello world");
+/// ```
+#[derive(Debug)]
+pub struct UnpadReader<R, N> {
+    inner: R,
+    unpadder: Option<Unpadder<N>>,
+}
+
+impl<R: Read, N: BlockSize> UnpadReader<R, N> {
+    /// Unpad the padded message read from `inner`
+    pub fn new(inner: R) -> Self {
+        UnpadReader {
+            inner,
+            unpadder: Some(Unpadder::new()),
+        }
+    }
+
+    /// Get a reference to the inner reader
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Return the inner reader
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: Read, N> ErrorType for UnpadReader<R, N> {
+    type Error = Error<R::Error>;
+}
+
+impl<R: Read, N: BlockSize> Read for UnpadReader<R, N> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        let mut scratch = [0; SCRATCH_LEN];
+        let scratch = &mut scratch[..buf.len().min(SCRATCH_LEN)];
+        loop {
+            let unpadder = match self.unpadder.as_mut() {
+                Some(unpadder) => unpadder,
+                None => return Ok(0),
+            };
+            let n = self.inner.read(scratch).map_err(Error::Io)?;
+            if n == 0 {
+                return match self.unpadder.take().map(Unpadder::finish) {
+                    Some(Err(_)) => Err(Error::MalformedPadding),
+                    _ => Ok(0),
+                };
+            }
+            let n = unpadder
+                .update(&scratch[..n], buf)
+                .map_err(|_| Error::MalformedPadding)?;
+            if n > 0 {
+                return Ok(n);
+            }
+        }
+    }
+}
@@ -0,0 +1,68 @@
+//! Padding straight to and from text encodings
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+
+use crate::{raw, BlockSize, CryptoRng, RngCore, TxPadError, TxPadding, UnpadError};
+
+impl<N: BlockSize, R> TxPadding<N, R> {
+    /// Decode the hex string `data` and unpad it into a newly allocated vector
+    ///
+    /// Invalid hex is reported as `UnpadError` like malformed padding.
+    pub fn unpad_from_hex(data: &str) -> Result<Vec<u8>, UnpadError> {
+        Self::unpad_owned(hex::decode(data).map_err(|_| UnpadError)?)
+    }
+
+    /// Decode the base64 string `data` with the standard alphabet and padding, and unpad it
+    /// into a newly allocated vector
+    ///
+    /// Invalid base64 is reported as `UnpadError` like malformed padding.
+    pub fn unpad_from_base64(data: &str) -> Result<Vec<u8>, UnpadError> {
+        Self::unpad_owned(BASE64.decode(data).map_err(|_| UnpadError)?)
+    }
+
+    fn unpad_owned(mut data: Vec<u8>) -> Result<Vec<u8>, UnpadError> {
+        let range = raw::unpad_range(&data, N::USIZE).map_err(|_| UnpadError)?;
+        data.truncate(range.end);
+        data.drain(..range.start);
+        Ok(data)
+    }
+}
+
+impl<N, R> TxPadding<N, R>
+where
+    N: BlockSize,
+    R: RngCore + CryptoRng + Default,
+{
+    /// Pad `msg` and encode the padded message as a lowercase hex string
+    ///
+    /// ```
+    /// use tx_padding::TxPadding;
+    /// use tx_padding::consts::{U8};
+    ///
+    /// let padded_msg = TxPadding::<U8>::pad_to_hex(b"test").unwrap();
+    /// assert_eq!(padded_msg.len(), 32);
+    /// assert_eq!(TxPadding::<U8>::unpad_from_hex(&padded_msg).unwrap(), b"test");
+    /// ```
+    pub fn pad_to_hex(msg: &[u8]) -> Result<String, TxPadError> {
+        Ok(hex::encode(Self::pad_to_vec(msg)?))
+    }
+
+    /// Pad `msg` and encode the padded message as base64 with the standard alphabet and
+    /// padding
+    ///
+    /// ```
+    /// use tx_padding::TxPadding;
+    /// use tx_padding::consts::{U8};
+    ///
+    /// let padded_msg = TxPadding::<U8>::pad_to_base64(b"test").unwrap();
+    /// assert_eq!(padded_msg.len(), 24);
+    /// assert_eq!(TxPadding::<U8>::unpad_from_base64(&padded_msg).unwrap(), b"test");
+    /// ```
+    pub fn pad_to_base64(msg: &[u8]) -> Result<String, TxPadError> {
+        Ok(BASE64.encode(Self::pad_to_vec(msg)?))
+    }
+}
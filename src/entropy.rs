@@ -0,0 +1,85 @@
+//! Replaceable source of the entropy drawn by the default RNG
+
+use core::mem;
+use core::ptr;
+use core::sync::atomic::{AtomicPtr, Ordering};
+
+#[cfg(feature = "os-rng")]
+use rand::rngs::OsRng;
+use rand::{CryptoRng, Error, RngCore};
+
+/// Code of the error returned by [`RandomSource`] when no entropy source is available
+pub const NO_ENTROPY_SOURCE: u32 = Error::CUSTOM_START;
+
+/// A function filling its argument with cryptographically secure random bytes
+pub type EntropyFn = fn(&mut [u8]) -> Result<(), Error>;
+
+/// The registered entropy source as a type-erased function pointer, or null if none is
+static SOURCE: AtomicPtr<()> = AtomicPtr::new(ptr::null_mut());
+
+/// Register `source` as the entropy source of [`RandomSource`], in place of the OS generator
+///
+/// This is meant for platforms where `OsRng` is unavailable, e.g. to draw from a hardware TRNG.
+/// The last registered source is used by every thread.
+pub fn set_entropy_source(source: EntropyFn) {
+    SOURCE.store(source as *mut (), Ordering::Release);
+}
+
+/// The registered entropy source, if any
+fn entropy_source() -> Option<EntropyFn> {
+    let source = SOURCE.load(Ordering::Acquire);
+    if source.is_null() {
+        return None;
+    }
+    // SAFETY: the only non-null values stored are `EntropyFn`s cast by `set_entropy_source`
+    Some(unsafe { mem::transmute::<*mut (), EntropyFn>(source) })
+}
+
+/// The RNG drawing from the source registered with [`set_entropy_source`], or from `OsRng` if
+/// none is
+///
+/// `OsRng` is the generator of the `getrandom` crate, so there is no separate `getrandom` step
+/// in this chain. A failure of the source is reported as is, without falling back to the next
+/// one.
+///
+/// Without the `os-rng` feature, generating random bytes fails with [`NO_ENTROPY_SOURCE`] until
+/// a source has been registered. This is not a compile-time error, as the source is meant to be
+/// registered at runtime on the platforms where `OsRng` is unavailable. The padding methods
+/// report it as `RngFailure`, but [`fill_bytes`](RngCore::fill_bytes), like the other infallible
+/// methods of `RngCore`, panics instead.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RandomSource;
+
+impl RngCore for RandomSource {
+    fn next_u32(&mut self) -> u32 {
+        let mut bytes = [0; 4];
+        self.fill_bytes(&mut bytes);
+        u32::from_le_bytes(bytes)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let mut bytes = [0; 8];
+        self.fill_bytes(&mut bytes);
+        u64::from_le_bytes(bytes)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        if let Err(e) = self.try_fill_bytes(dest) {
+            panic!("{}", e)
+        }
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        match entropy_source() {
+            Some(source) => source(dest),
+            #[cfg(feature = "os-rng")]
+            None => OsRng.try_fill_bytes(dest),
+            #[cfg(not(feature = "os-rng"))]
+            None => Err(Error::from(
+                core::num::NonZeroU32::new(NO_ENTROPY_SOURCE).unwrap(),
+            )),
+        }
+    }
+}
+
+impl CryptoRng for RandomSource {}
@@ -0,0 +1,140 @@
+use core::fmt;
+
+use block_padding::{PadError, UnpadError};
+
+/// Error for indicating failed padding operation, with the reason of the failure
+///
+/// It converts into `PadError` so it can be propagated through the `Padding` trait.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum TxPadError {
+    /// The block size passed in does not match the type parameter `N`
+    BlockSizeMismatch { expected: usize, got: usize },
+    /// The block size is not a power of 2 between 2 and 256
+    InvalidBlockSize { got: usize },
+    /// The buffer is not large enough to hold the padded message
+    BufferTooSmall { required: usize, got: usize },
+    /// The RNG failed to generate the random prefix
+    RngFailure,
+    /// The message is longer than the scheme supports, e.g. than
+    /// [`TxPadding::MAX_MSG_LEN`](crate::TxPadding::MAX_MSG_LEN)
+    MessageTooLong,
+    /// A different number of message bytes was supplied than announced
+    MessageLengthMismatch { expected: usize, got: usize },
+    /// The scheme version does not fit in the 2 bits reserved for it
+    InvalidVersion { got: u8 },
+    /// The message length `pos` exceeds the length of the buffer holding it
+    PositionOutOfBounds { pos: usize, len: usize },
+    /// No padding scheme is known or registered under the identifier byte
+    UnknownScheme { id: u8 },
+    /// The length of the buffer is not a multiple of the block size
+    Misaligned { len: usize, block_size: usize },
+}
+
+impl fmt::Display for TxPadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            TxPadError::BlockSizeMismatch { expected, got } => {
+                write!(f, "block size mismatch: expected {}, got {}", expected, got)
+            }
+            TxPadError::InvalidBlockSize { got } => write!(f, "invalid block size {}", got),
+            TxPadError::BufferTooSmall { required, got } => write!(
+                f,
+                "buffer too small: {} bytes required, got {}",
+                required, got
+            ),
+            TxPadError::RngFailure => f.write_str("failed to generate random bytes"),
+            TxPadError::MessageTooLong => f.write_str("message too long"),
+            TxPadError::MessageLengthMismatch { expected, got } => write!(
+                f,
+                "message length mismatch: expected {} bytes, got {}",
+                expected, got
+            ),
+            TxPadError::InvalidVersion { got } => write!(f, "invalid scheme version {}", got),
+            TxPadError::PositionOutOfBounds { pos, len } => {
+                write!(f, "message length {} exceeds buffer length {}", pos, len)
+            }
+            TxPadError::UnknownScheme { id } => write!(f, "unknown padding scheme {}", id),
+            TxPadError::Misaligned { len, block_size } => write!(
+                f,
+                "buffer length {} is not a multiple of the block size {}",
+                len, block_size
+            ),
+        }
+    }
+}
+
+#[rustversion::since(1.81)]
+impl core::error::Error for TxPadError {}
+
+#[cfg(feature = "std")]
+#[rustversion::before(1.81)]
+impl std::error::Error for TxPadError {}
+
+impl From<TxPadError> for PadError {
+    fn from(_: TxPadError) -> PadError {
+        PadError
+    }
+}
+
+/// The reason why unpadding failed
+///
+/// The `Padding` trait collapses all of these into the opaque `UnpadError`, which is what
+/// should be reported to untrusted peers.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum UnpadErrorKind {
+    /// The input is empty
+    EmptyInput,
+    /// The input is shorter than the padding declared in its header
+    TooShort,
+    /// The run of padding zeros contains a non-zero byte, or one differing from the configured
+    /// fill byte, at `index`
+    NonZeroTail { index: usize },
+    /// The input length is not a multiple of the block size
+    LengthNotBlockAligned,
+    /// The header declares more random bytes than the block size allows
+    InvalidHeader,
+    /// The scheme version in the header differs from the expected one
+    VersionMismatch { expected: u8, got: u8 },
+    /// The checksum stored in the padding does not match the padded message
+    ChecksumMismatch,
+    /// The authentication tag stored in the padding does not match the padded message
+    InvalidTag,
+}
+
+impl fmt::Display for UnpadErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            UnpadErrorKind::EmptyInput => f.write_str("empty input"),
+            UnpadErrorKind::TooShort => f.write_str("input too short for the declared padding"),
+            UnpadErrorKind::NonZeroTail { index } => {
+                write!(f, "non-zero byte in the padding zeros at index {}", index)
+            }
+            UnpadErrorKind::LengthNotBlockAligned => {
+                f.write_str("input length is not a multiple of the block size")
+            }
+            UnpadErrorKind::InvalidHeader => f.write_str("invalid header"),
+            UnpadErrorKind::VersionMismatch { expected, got } => write!(
+                f,
+                "scheme version mismatch: expected {}, got {}",
+                expected, got
+            ),
+            UnpadErrorKind::ChecksumMismatch => f.write_str("checksum mismatch"),
+            UnpadErrorKind::InvalidTag => f.write_str("invalid authentication tag"),
+        }
+    }
+}
+
+#[rustversion::since(1.81)]
+impl core::error::Error for UnpadErrorKind {}
+
+#[cfg(feature = "std")]
+#[rustversion::before(1.81)]
+impl std::error::Error for UnpadErrorKind {}
+
+impl From<UnpadErrorKind> for UnpadError {
+    fn from(_: UnpadErrorKind) -> UnpadError {
+        UnpadError
+    }
+}
@@ -0,0 +1,87 @@
+//! Fast, non-cryptographic RNG for simulation and fuzzing workloads
+
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+use rand::rngs::SmallRng;
+use rand::{Error, RngCore, SeedableRng};
+
+use crate::InsecureRng;
+
+/// Seed of the next generator constructed with `Default`
+static NEXT_SEED: AtomicUsize = AtomicUsize::new(0);
+
+/// **Not cryptographically secure.** A `SmallRng` filling the random prefix without the cost of
+/// the OS generator
+///
+/// It does not implement `CryptoRng`, so it is rejected by every padding method until wrapped in
+/// [`InsecureRng`], see [`InsecureFastRng`]. Each generator constructed with `Default` is seeded
+/// from a process-wide counter, so runs are reproducible.
+///
+/// ```compile_fail
+/// use tx_padding::{FastRng, TxPadding};
+/// use tx_padding::consts::{U8};
+///
+/// let mut out = [0; 16];
+/// TxPadding::<U8>::pad_into_with_rng(&mut FastRng::default(), b"test", &mut out).unwrap();
+/// ```
+#[derive(Clone, Debug)]
+pub struct FastRng(SmallRng);
+
+impl FastRng {
+    /// Create a generator from `seed`
+    pub fn seed_from_u64(seed: u64) -> Self {
+        FastRng(SmallRng::seed_from_u64(seed))
+    }
+}
+
+impl Default for FastRng {
+    fn default() -> Self {
+        Self::seed_from_u64(NEXT_SEED.fetch_add(1, Ordering::Relaxed) as u64)
+    }
+}
+
+impl RngCore for FastRng {
+    fn next_u32(&mut self) -> u32 {
+        self.0.next_u32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0.next_u64()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.0.fill_bytes(dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.0.try_fill_bytes(dest)
+    }
+}
+
+/// **Not cryptographically secure.** [`FastRng`] explicitly opted out of the `CryptoRng`
+/// requirement through [`InsecureRng`]
+///
+/// The random prefix is what keeps the length of the message from leaking through the padded
+/// length, and this generator is predictable. Use it only where the padded messages are never
+/// exposed to an adversary, e.g. simulations, fuzzing and benchmarks.
+///
+/// Enabling the `insecure-fast-rng` feature never changes [`DefaultRng`](crate::DefaultRng):
+/// this type must be named at every use site.
+///
+/// ```
+/// use tx_padding::{InsecureFastRng, TxPadding, Padding};
+/// use tx_padding::consts::{U8};
+///
+/// let mut buffer = [0; 16];
+/// buffer[..4].copy_from_slice(b"test");
+/// let padded_msg = TxPadding::<U8, InsecureFastRng>::pad(&mut buffer, 4, 8).unwrap();
+/// assert_eq!(TxPadding::<U8>::unpad(&padded_msg).unwrap(), b"test");
+/// ```
+pub type InsecureFastRng = InsecureRng<FastRng>;
+
+impl InsecureRng<FastRng> {
+    /// Create a generator from `seed`
+    pub fn seed_from_u64(seed: u64) -> Self {
+        InsecureRng(FastRng::seed_from_u64(seed))
+    }
+}
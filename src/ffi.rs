@@ -0,0 +1,169 @@
+//! C interface to the padding, with the block size chosen at runtime
+//!
+//! The functions return [`TXPAD_OK`] or one of the negative `TXPAD_ERR_*` codes. They dispatch
+//! to `TxPadding` of the matching block size, whose paths are verified not to panic by the
+//! `no-panic` feature, so only the RNG could panic. With the `std` feature such a panic is caught
+//! and reported as [`TXPAD_ERR_PANIC`] instead of unwinding into the caller. A C header can be
+//! generated with `cbindgen`, which is configured by `cbindgen.toml` at the root of the
+//! repository.
+
+use core::slice;
+
+use crate::consts::{U128, U16, U2, U256, U32, U4, U64, U8};
+use crate::{BlockSize, DefaultRng, TxPadError, TxPadding, TxPaddingDyn};
+
+/// The call succeeded
+pub const TXPAD_OK: i32 = 0;
+/// A pointer argument is null
+pub const TXPAD_ERR_NULL_POINTER: i32 = -1;
+/// The block size is not a power of 2 between 2 and 256
+pub const TXPAD_ERR_INVALID_BLOCK_SIZE: i32 = -2;
+/// The buffer is not large enough to hold the padded message
+pub const TXPAD_ERR_BUFFER_TOO_SMALL: i32 = -3;
+/// The RNG failed to generate the random prefix
+pub const TXPAD_ERR_RNG_FAILURE: i32 = -4;
+/// The message is longer than the scheme supports
+pub const TXPAD_ERR_MESSAGE_TOO_LONG: i32 = -5;
+/// The input is not a validly padded message
+pub const TXPAD_ERR_INVALID_PADDING: i32 = -6;
+/// The message length exceeds the length of the buffer holding it
+pub const TXPAD_ERR_INVALID_ARGUMENT: i32 = -7;
+/// The RNG panicked
+pub const TXPAD_ERR_PANIC: i32 = -8;
+
+fn error_code(e: TxPadError) -> i32 {
+    match e {
+        TxPadError::BlockSizeMismatch { .. } | TxPadError::InvalidBlockSize { .. } => {
+            TXPAD_ERR_INVALID_BLOCK_SIZE
+        }
+        TxPadError::BufferTooSmall { .. } => TXPAD_ERR_BUFFER_TOO_SMALL,
+        TxPadError::RngFailure => TXPAD_ERR_RNG_FAILURE,
+        TxPadError::MessageTooLong => TXPAD_ERR_MESSAGE_TOO_LONG,
+        TxPadError::MessageLengthMismatch { .. }
+        | TxPadError::InvalidVersion { .. }
+        | TxPadError::PositionOutOfBounds { .. }
+        | TxPadError::UnknownScheme { .. }
+        | TxPadError::Misaligned { .. } => TXPAD_ERR_INVALID_ARGUMENT,
+    }
+}
+
+/// Run `f`, catching panics where unwinding is supported
+fn catch_panic(f: impl FnOnce() -> i32) -> i32 {
+    #[cfg(feature = "std")]
+    {
+        std::panic::catch_unwind(std::panic::AssertUnwindSafe(f)).unwrap_or(TXPAD_ERR_PANIC)
+    }
+    #[cfg(not(feature = "std"))]
+    {
+        f()
+    }
+}
+
+fn pad<N: BlockSize>(buf: &mut [u8], msg_len: usize) -> Result<usize, i32> {
+    TxPadding::<N>::pad_with_rng(&mut DefaultRng::default(), buf, msg_len)
+        .map(|padded_msg| padded_msg.len())
+        .map_err(error_code)
+}
+
+fn unpad<N: BlockSize>(data: &[u8]) -> Result<(usize, usize), i32> {
+    TxPadding::<N>::unpad_range(data)
+        .map(|range| (range.start, range.len()))
+        .map_err(|_| TXPAD_ERR_INVALID_PADDING)
+}
+
+/// Length of the padded output for a message of `msg_len` bytes
+///
+/// Returns 0 if `block_size` is not supported or the length overflows.
+#[no_mangle]
+pub extern "C" fn txpad_padded_len(msg_len: usize, block_size: usize) -> usize {
+    <TxPaddingDyn>::padded_len(msg_len, block_size).unwrap_or(0)
+}
+
+/// Pad the message of `msg_len` bytes at the start of `buf` in place, in blocks of `block_size`
+///
+/// On success, the length of the padded message is stored into `out_len`.
+///
+/// # Safety
+///
+/// `buf` must be valid for reads and writes of `buf_len` bytes, and `out_len` must be valid for
+/// a write.
+#[no_mangle]
+pub unsafe extern "C" fn txpad_pad(
+    buf: *mut u8,
+    buf_len: usize,
+    msg_len: usize,
+    block_size: usize,
+    out_len: *mut usize,
+) -> i32 {
+    if buf.is_null() || out_len.is_null() {
+        return TXPAD_ERR_NULL_POINTER;
+    }
+    // SAFETY: guaranteed by the caller
+    let buf = unsafe { slice::from_raw_parts_mut(buf, buf_len) };
+    catch_panic(|| {
+        let padded_len = match block_size {
+            2 => pad::<U2>(buf, msg_len),
+            4 => pad::<U4>(buf, msg_len),
+            8 => pad::<U8>(buf, msg_len),
+            16 => pad::<U16>(buf, msg_len),
+            32 => pad::<U32>(buf, msg_len),
+            64 => pad::<U64>(buf, msg_len),
+            128 => pad::<U128>(buf, msg_len),
+            256 => pad::<U256>(buf, msg_len),
+            _ => Err(TXPAD_ERR_INVALID_BLOCK_SIZE),
+        };
+        match padded_len {
+            Ok(padded_len) => {
+                // SAFETY: guaranteed by the caller
+                unsafe { out_len.write(padded_len) };
+                TXPAD_OK
+            }
+            Err(code) => code,
+        }
+    })
+}
+
+/// Unpad the `data_len` bytes of `data`, padded in blocks of `block_size`
+///
+/// On success, the message is the `msg_len` bytes of `data` starting at `msg_offset`.
+///
+/// # Safety
+///
+/// `data` must be valid for reads of `data_len` bytes, and `msg_offset` and `msg_len` must be
+/// valid for a write.
+#[no_mangle]
+pub unsafe extern "C" fn txpad_unpad(
+    data: *const u8,
+    data_len: usize,
+    block_size: usize,
+    msg_offset: *mut usize,
+    msg_len: *mut usize,
+) -> i32 {
+    if data.is_null() || msg_offset.is_null() || msg_len.is_null() {
+        return TXPAD_ERR_NULL_POINTER;
+    }
+    // SAFETY: guaranteed by the caller
+    let data = unsafe { slice::from_raw_parts(data, data_len) };
+    let range = match block_size {
+        2 => unpad::<U2>(data),
+        4 => unpad::<U4>(data),
+        8 => unpad::<U8>(data),
+        16 => unpad::<U16>(data),
+        32 => unpad::<U32>(data),
+        64 => unpad::<U64>(data),
+        128 => unpad::<U128>(data),
+        256 => unpad::<U256>(data),
+        _ => Err(TXPAD_ERR_INVALID_BLOCK_SIZE),
+    };
+    match range {
+        Ok((offset, len)) => {
+            // SAFETY: guaranteed by the caller
+            unsafe {
+                msg_offset.write(offset);
+                msg_len.write(len);
+            }
+            TXPAD_OK
+        }
+        Err(code) => code,
+    }
+}
@@ -0,0 +1,115 @@
+use core::ops::{Add, Div, Mul};
+
+use generic_array::{ArrayLength, GenericArray};
+use typenum::operator_aliases::{Add1, Quot, Sum};
+use typenum::{B1, U2};
+
+use crate::{raw, BlockSize, CryptoRng, PaddedLen, RngCore, TxPadError, TxPadding};
+
+impl<N: BlockSize, R> TxPadding<N, R> {
+    /// Pad the fixed-size `msg` into an array of exactly the padded length, drawing the random
+    /// prefix from `rng`
+    ///
+    /// Fails with `RngFailure` if `rng` fails to generate the random prefix.
+    pub fn pad_fixed_with_rng<M>(
+        rng: &mut (impl RngCore + CryptoRng),
+        msg: &GenericArray<u8, M>,
+    ) -> Result<GenericArray<u8, PaddedLen<M, N>>, TxPadError>
+    where
+        M: ArrayLength<u8> + Add<B1>,
+        Add1<M>: Div<N>,
+        Quot<Add1<M>, N>: Add<U2>,
+        N: Mul<Sum<Quot<Add1<M>, N>, U2>>,
+        PaddedLen<M, N>: ArrayLength<u8>,
+    {
+        let mut out = GenericArray::default();
+        raw::pad_from(rng, msg, &mut out, N::USIZE)?;
+        Ok(out)
+    }
+}
+
+impl<N, R> TxPadding<N, R>
+where
+    N: BlockSize,
+    R: RngCore + CryptoRng + Default,
+{
+    /// Pad the fixed-size `msg` into an array of exactly the padded length
+    ///
+    /// The output length is computed by the type system, so there is no buffer to size and no
+    /// length to check. It only fails with `RngFailure`, if `R` fails to generate the random
+    /// prefix.
+    ///
+    /// ```
+    /// use generic_array::GenericArray;
+    /// use tx_padding::TxPadding;
+    /// use tx_padding::consts::{U4, U8, U16};
+    ///
+    /// let msg = GenericArray::<u8, U4>::clone_from_slice(b"test");
+    /// let padded_msg: GenericArray<u8, U16> = TxPadding::<U8>::pad_fixed(&msg).unwrap();
+    /// assert_eq!(TxPadding::<U8>::unpad(&padded_msg).unwrap(), b"test");
+    /// ```
+    pub fn pad_fixed<M>(
+        msg: &GenericArray<u8, M>,
+    ) -> Result<GenericArray<u8, PaddedLen<M, N>>, TxPadError>
+    where
+        M: ArrayLength<u8> + Add<B1>,
+        Add1<M>: Div<N>,
+        Quot<Add1<M>, N>: Add<U2>,
+        N: Mul<Sum<Quot<Add1<M>, N>, U2>>,
+        PaddedLen<M, N>: ArrayLength<u8>,
+    {
+        Self::pad_fixed_with_rng(&mut R::default(), msg)
+    }
+}
+
+impl<N: BlockSize + ArrayLength<u8>, R> TxPadding<N, R> {
+    /// The blocks of the padded message `buf`, e.g. to feed them to a block cipher one at a time
+    ///
+    /// A padded message always consists of whole blocks, so this fails with
+    /// [`TxPadError::Misaligned`] if the length of `buf` is not a multiple of the block size,
+    /// rather than skipping the trailing bytes. The padding itself is not checked.
+    ///
+    /// ```
+    /// use aes::Aes128;
+    /// use aes::cipher::{BlockEncrypt, KeyInit};
+    /// use tx_padding::{TxPadding, Padding};
+    /// use tx_padding::consts::{U16};
+    ///
+    /// let cipher = Aes128::new(&[0; 16].into());
+    /// let mut buffer = [0; 32];
+    /// buffer[..4].copy_from_slice(b"test");
+    /// let padded_msg = TxPadding::<U16>::pad(&mut buffer, 4, 16).unwrap();
+    /// for block in TxPadding::<U16>::padded_blocks_mut(padded_msg).unwrap() {
+    ///     cipher.encrypt_block(block);
+    /// }
+    /// ```
+    pub fn padded_blocks(
+        buf: &[u8],
+    ) -> Result<impl Iterator<Item = &GenericArray<u8, N>>, TxPadError> {
+        if buf.len() % N::USIZE != 0 {
+            Err(TxPadError::Misaligned {
+                len: buf.len(),
+                block_size: N::USIZE,
+            })?
+        }
+        Ok(buf.chunks_exact(N::USIZE).map(GenericArray::from_slice))
+    }
+
+    /// The blocks of the padded message `buf`, mutably
+    ///
+    /// Fails with [`TxPadError::Misaligned`] if the length of `buf` is not a multiple of the block
+    /// size.
+    pub fn padded_blocks_mut(
+        buf: &mut [u8],
+    ) -> Result<impl Iterator<Item = &mut GenericArray<u8, N>>, TxPadError> {
+        if buf.len() % N::USIZE != 0 {
+            Err(TxPadError::Misaligned {
+                len: buf.len(),
+                block_size: N::USIZE,
+            })?
+        }
+        Ok(buf
+            .chunks_exact_mut(N::USIZE)
+            .map(GenericArray::from_mut_slice))
+    }
+}
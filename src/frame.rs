@@ -0,0 +1,210 @@
+//! Self-delimiting frames made of the length of the padded message followed by the padded message
+//!
+//! The length is encoded as an unsigned LEB128 varint: 7 bits per byte, least significant group
+//! first, with the high bit set on every byte but the last. Only the shortest encoding is
+//! accepted.
+
+#[cfg(feature = "std")]
+use std::io::{self, Read, Write};
+#[cfg(feature = "std")]
+use std::vec::Vec;
+
+#[cfg(feature = "std")]
+use crate::io::to_io_error;
+#[cfg(feature = "std")]
+use crate::PadWriter;
+
+use crate::{raw, BlockSize, CryptoRng, RngCore, TxPadError, TxPadding, UnpadError};
+
+/// Largest number of bytes of a varint encoding a `u64`
+const MAX_VARINT_LEN: usize = 10;
+
+/// Number of bytes of the varint encoding `value`
+fn varint_len(mut value: usize) -> usize {
+    let mut len = 1;
+    while value >= 0x80 {
+        value >>= 7;
+        len += 1;
+    }
+    len
+}
+
+/// Encode `value` into the start of `out`, which must be at least `varint_len(value)` bytes long
+fn write_varint(mut value: usize, out: &mut [u8]) -> usize {
+    let mut i = 0;
+    while value >= 0x80 {
+        out[i] = value as u8 | 0x80;
+        value >>= 7;
+        i += 1;
+    }
+    out[i] = value as u8;
+    i + 1
+}
+
+/// Decoder of a varint fed one byte at a time
+#[derive(Default)]
+struct VarintDecoder {
+    value: usize,
+    len: usize,
+}
+
+impl VarintDecoder {
+    /// Feed the next byte, returning the value once it is complete
+    fn push(&mut self, byte: u8) -> Result<Option<usize>, ()> {
+        let shift = 7 * self.len;
+        let group = (byte & 0x7f) as usize;
+        if self.len == MAX_VARINT_LEN
+            || shift >= usize::BITS as usize
+            || group << shift >> shift != group
+        {
+            Err(())?
+        }
+        self.value |= group << shift;
+        self.len += 1;
+        if byte & 0x80 != 0 {
+            return Ok(None);
+        }
+        // Reject a redundant trailing zero group
+        if byte == 0 && self.len > 1 {
+            Err(())?
+        }
+        Ok(Some(self.value))
+    }
+}
+
+/// Check the padded length read from a frame
+fn check_padded_len(padded_len: usize, block_size: usize) -> Result<(), ()> {
+    if padded_len == 0 || padded_len % block_size != 0 {
+        Err(())?
+    }
+    Ok(())
+}
+
+impl<N: BlockSize, R> TxPadding<N, R> {
+    /// Length of the frame holding a message of length `msg_len`
+    pub const fn framed_len(msg_len: usize) -> usize {
+        let padded_len = raw::padded_len(N::USIZE, msg_len);
+        let mut len = padded_len + 1;
+        let mut value = padded_len;
+        while value >= 0x80 {
+            value >>= 7;
+            len += 1;
+        }
+        len
+    }
+
+    /// Write the frame of `msg` into the start of `out`, drawing the random prefix from `rng`
+    ///
+    /// Returns the frame.
+    pub fn pad_framed_with_rng<'a>(
+        rng: &mut (impl RngCore + CryptoRng),
+        msg: &[u8],
+        out: &'a mut [u8],
+    ) -> Result<&'a mut [u8], TxPadError> {
+        let padded_len =
+            raw::checked_padded_len(N::USIZE, msg.len()).ok_or(TxPadError::MessageTooLong)?;
+        let required = padded_len
+            .checked_add(varint_len(padded_len))
+            .ok_or(TxPadError::MessageTooLong)?;
+        if out.len() < required {
+            Err(TxPadError::BufferTooSmall {
+                required,
+                got: out.len(),
+            })?
+        }
+        let header_len = write_varint(padded_len, out);
+        raw::pad_from(rng, msg, &mut out[header_len..], N::USIZE)?;
+        Ok(&mut out[..required])
+    }
+
+    /// Unpad the frame at the start of `data`, returning the message and the length of the frame
+    pub fn unpad_framed(data: &[u8]) -> Result<(&[u8], usize), UnpadError> {
+        let mut decoder = VarintDecoder::default();
+        let mut padded_len = None;
+        for &byte in data.iter().take(MAX_VARINT_LEN) {
+            padded_len = decoder.push(byte).map_err(|_| UnpadError)?;
+            if padded_len.is_some() {
+                break;
+            }
+        }
+        let padded_len = padded_len.ok_or(UnpadError)?;
+        check_padded_len(padded_len, N::USIZE).map_err(|_| UnpadError)?;
+        let header_len = decoder.len;
+        if data.len() - header_len < padded_len {
+            Err(UnpadError)?
+        }
+        let end = header_len + padded_len;
+        let msg = raw::unpad(&data[header_len..end], N::USIZE).map_err(|_| UnpadError)?;
+        Ok((msg, end))
+    }
+
+    /// Write the frame of `msg` to `writer`, drawing the random prefix from `rng`
+    #[cfg(feature = "std")]
+    pub fn write_framed_with_rng(
+        rng: &mut (impl RngCore + CryptoRng),
+        writer: &mut impl Write,
+        msg: &[u8],
+    ) -> io::Result<()> {
+        let padded_len = raw::checked_padded_len(N::USIZE, msg.len())
+            .ok_or_else(|| to_io_error(TxPadError::MessageTooLong))?;
+        let mut header = [0; MAX_VARINT_LEN];
+        let header_len = write_varint(padded_len, &mut header);
+        writer.write_all(&header[..header_len])?;
+        let mut padded_writer = PadWriter::<_, N, R>::with_rng(&mut *writer, rng, msg.len())?;
+        padded_writer.write_all(msg)?;
+        padded_writer.finish()?;
+        Ok(())
+    }
+
+    /// Read a frame from `reader` into `buf`, returning the message
+    ///
+    /// `buf` is cleared first, and grows as the frame arrives rather than to the length it
+    /// declares. Fails with `ErrorKind::InvalidData` if the frame is malformed and
+    /// `ErrorKind::UnexpectedEof` if it is truncated.
+    #[cfg(feature = "std")]
+    pub fn read_framed<'a>(reader: &mut impl Read, buf: &'a mut Vec<u8>) -> io::Result<&'a [u8]> {
+        let malformed = || io::Error::new(io::ErrorKind::InvalidData, "malformed frame");
+        let mut decoder = VarintDecoder::default();
+        let padded_len = loop {
+            let mut byte = [0; 1];
+            reader.read_exact(&mut byte)?;
+            if let Some(padded_len) = decoder.push(byte[0]).map_err(|_| malformed())? {
+                break padded_len;
+            }
+        };
+        check_padded_len(padded_len, N::USIZE).map_err(|_| malformed())?;
+        buf.clear();
+        reader.take(padded_len as u64).read_to_end(buf)?;
+        if buf.len() < padded_len {
+            Err(io::Error::from(io::ErrorKind::UnexpectedEof))?
+        }
+        raw::unpad(buf, N::USIZE).map_err(|_| malformed())
+    }
+}
+
+impl<N, R> TxPadding<N, R>
+where
+    N: BlockSize,
+    R: RngCore + CryptoRng + Default,
+{
+    /// Write the frame of `msg` into the start of `out`
+    ///
+    /// ```
+    /// use tx_padding::TxPadding;
+    /// use tx_padding::consts::{U8};
+    ///
+    /// let mut out = [0; TxPadding::<U8>::framed_len(4)];
+    /// let frame = TxPadding::<U8>::pad_framed(b"test", &mut out).unwrap();
+    /// assert_eq!(frame[0], 16);
+    /// assert_eq!(TxPadding::<U8>::unpad_framed(frame).unwrap(), (&b"test"[..], 17));
+    /// ```
+    pub fn pad_framed<'a>(msg: &[u8], out: &'a mut [u8]) -> Result<&'a mut [u8], TxPadError> {
+        Self::pad_framed_with_rng(&mut R::default(), msg, out)
+    }
+
+    /// Write the frame of `msg` to `writer`
+    #[cfg(feature = "std")]
+    pub fn write_framed(writer: &mut impl Write, msg: &[u8]) -> io::Result<()> {
+        Self::write_framed_with_rng(&mut R::default(), writer, msg)
+    }
+}
@@ -0,0 +1,108 @@
+//! Unpadding streams of back-to-back length-delimited padded messages
+
+use core::convert::TryFrom;
+use core::iter;
+
+use crate::{raw, BlockSize, TxPadding, TxPaddingConst, UnpadError};
+
+/// Length of the frame header storing the length of the padded message
+pub(crate) const FRAME_HEADER_LEN: usize = 4;
+
+impl<N: BlockSize, R> TxPadding<N, R> {
+    /// Unpad the first frame of `data`, returning the message and the length of the frame
+    ///
+    /// A frame is the length of the padded message as a big-endian `u32`, followed by the padded
+    /// message, the same layout as the frames of `TxPaddingCodec`. The length must be a non-zero
+    /// multiple of the block size, and the whole frame must be present in `data`.
+    ///
+    /// ```
+    /// # #[cfg(any(feature = "os-rng", feature = "thread-rng"))]
+    /// # {
+    /// use tx_padding::{TxPadding, Padding};
+    /// use tx_padding::consts::{U8};
+    ///
+    /// let mut frames = [0; 2 * (4 + 16)];
+    /// for (frame, msg) in frames.chunks_mut(20).zip([b"ping", b"pong"]) {
+    ///     frame[..4].copy_from_slice(&16u32.to_be_bytes());
+    ///     TxPadding::<U8>::pad_into(msg, &mut frame[4..]).unwrap();
+    /// }
+    /// assert_eq!(TxPadding::<U8>::unpad_with_consumed(&frames).unwrap(), (&b"ping"[..], 20));
+    /// let msgs: Result<Vec<_>, _> = TxPadding::<U8>::unpad_iter(&frames).collect();
+    /// assert_eq!(msgs.unwrap(), [b"ping", b"pong"]);
+    /// # }
+    /// ```
+    pub fn unpad_with_consumed(data: &[u8]) -> Result<(&[u8], usize), UnpadError> {
+        if data.len() < FRAME_HEADER_LEN {
+            Err(UnpadError)?
+        }
+        let mut header = [0; FRAME_HEADER_LEN];
+        header.copy_from_slice(&data[..FRAME_HEADER_LEN]);
+        let padded_len = usize::try_from(u32::from_be_bytes(header)).map_err(|_| UnpadError)?;
+        if padded_len == 0
+            || padded_len % N::USIZE != 0
+            || data.len() - FRAME_HEADER_LEN < padded_len
+        {
+            Err(UnpadError)?
+        }
+        let consumed = FRAME_HEADER_LEN + padded_len;
+        let msg =
+            raw::unpad(&data[FRAME_HEADER_LEN..consumed], N::USIZE).map_err(|_| UnpadError)?;
+        Ok((msg, consumed))
+    }
+
+    /// Unpad the padded message of `blocks` blocks at the front of `data`, which may be followed
+    /// by unrelated bytes, returning the message and the length of the padded message
+    ///
+    /// The padded message is not preceded by its length, so the caller has to know it, e.g. from
+    /// an outer header. Looking for the trailing zeros instead would cut short any message with
+    /// `N - 1` zeros ending at a block boundary.
+    ///
+    /// ```
+    /// # #[cfg(any(feature = "os-rng", feature = "thread-rng"))]
+    /// # {
+    /// use tx_padding::{TxPadding, Padding};
+    /// use tx_padding::consts::{U8};
+    ///
+    /// let mut buffer = [0xff; 64];
+    /// TxPadding::<U8>::pad_into(b"test", &mut buffer).unwrap();
+    /// assert_eq!(TxPadding::<U8>::unpad_prefix(&buffer, 2).unwrap(), (&b"test"[..], 16));
+    /// # }
+    /// ```
+    pub fn unpad_prefix(data: &[u8], blocks: usize) -> Result<(&[u8], usize), UnpadError> {
+        raw::unpad_prefix(data, blocks, N::USIZE).map_err(|_| UnpadError)
+    }
+
+    /// Unpad every frame of `data` in turn
+    ///
+    /// See [`unpad_with_consumed`](TxPadding::unpad_with_consumed) for the layout of a frame. The
+    /// iterator ends after the first malformed frame.
+    pub fn unpad_iter(data: &[u8]) -> impl Iterator<Item = Result<&[u8], UnpadError>> {
+        let mut rest = data;
+        iter::from_fn(move || {
+            if rest.is_empty() {
+                return None;
+            }
+            match Self::unpad_with_consumed(rest) {
+                Ok((msg, consumed)) => {
+                    rest = &rest[consumed..];
+                    Some(Ok(msg))
+                }
+                Err(e) => {
+                    rest = &[];
+                    Some(Err(e))
+                }
+            }
+        })
+    }
+}
+
+impl<const N: usize, R> TxPaddingConst<N, R> {
+    /// Unpad the padded message of `blocks` blocks at the front of `data`, which may be followed
+    /// by unrelated bytes, returning the message and the length of the padded message
+    ///
+    /// See [`TxPadding::unpad_prefix`].
+    pub fn unpad_prefix(data: &[u8], blocks: usize) -> Result<(&[u8], usize), UnpadError> {
+        let () = Self::VALID_BLOCK_SIZE;
+        raw::unpad_prefix(data, blocks, N).map_err(|_| UnpadError)
+    }
+}
@@ -0,0 +1,57 @@
+//! RNG registered at runtime, for bare-metal targets without an OS generator
+
+use core::cell::Cell;
+use core::num::NonZeroU32;
+
+use critical_section::Mutex;
+use rand::{CryptoRng, Error, RngCore};
+
+/// A cryptographically secure generator which can be shared by the whole program, e.g. a driver
+/// for the TRNG peripheral of a SoC
+pub trait SecureRng: Sync {
+    /// Fill `dest` with random bytes
+    fn try_fill_bytes(&self, dest: &mut [u8]) -> Result<(), Error>;
+}
+
+/// The registered generator
+static RNG: Mutex<Cell<Option<&'static dyn SecureRng>>> = Mutex::new(Cell::new(None));
+
+/// Register `rng` as the generator [`GlobalRng`] draws from, replacing any previous one
+pub fn register_rng(rng: &'static dyn SecureRng) {
+    critical_section::with(|cs| RNG.borrow(cs).set(Some(rng)));
+}
+
+/// The RNG drawing from the generator registered with [`register_rng`]
+///
+/// Generating random bytes fails if no generator has been registered yet.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct GlobalRng;
+
+impl RngCore for GlobalRng {
+    fn next_u32(&mut self) -> u32 {
+        let mut bytes = [0; 4];
+        self.fill_bytes(&mut bytes);
+        u32::from_le_bytes(bytes)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let mut bytes = [0; 8];
+        self.fill_bytes(&mut bytes);
+        u64::from_le_bytes(bytes)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        if let Err(e) = self.try_fill_bytes(dest) {
+            panic!("{}", e)
+        }
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        match critical_section::with(|cs| RNG.borrow(cs).get()) {
+            Some(rng) => rng.try_fill_bytes(dest),
+            None => Err(Error::from(NonZeroU32::new(Error::CUSTOM_START).unwrap())),
+        }
+    }
+}
+
+impl CryptoRng for GlobalRng {}
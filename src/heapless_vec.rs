@@ -0,0 +1,113 @@
+//! Padding into and unpadding from fixed-capacity `heapless` vectors
+
+use heapless::Vec;
+
+use crate::{
+    raw, BlockSize, CryptoRng, RngCore, TxPadError, TxPadding, TxPaddingConst, UnpadError,
+};
+
+/// Pad `msg` into a new vector of capacity `CAP`
+fn pad_into_heapless<const CAP: usize>(
+    rng: &mut (impl RngCore + CryptoRng),
+    msg: &[u8],
+    block_size: usize,
+) -> Result<Vec<u8, CAP>, TxPadError> {
+    let len = raw::checked_padded_len(block_size, msg.len()).ok_or(TxPadError::MessageTooLong)?;
+    let mut buf = Vec::new();
+    if buf.resize(len, 0).is_err() {
+        Err(TxPadError::BufferTooSmall {
+            required: len,
+            got: CAP,
+        })?
+    }
+    raw::pad_from(rng, msg, &mut buf, block_size)?;
+    Ok(buf)
+}
+
+impl<N: BlockSize, R> TxPadding<N, R> {
+    /// Pad `msg` into a new vector of capacity `CAP`, drawing the random prefix from `rng`
+    ///
+    /// Fails with `BufferTooSmall` if the padded message does not fit in `CAP` bytes.
+    pub fn pad_heapless_with_rng<const CAP: usize>(
+        rng: &mut (impl RngCore + CryptoRng),
+        msg: &[u8],
+    ) -> Result<Vec<u8, CAP>, TxPadError> {
+        pad_into_heapless(rng, msg, N::USIZE)
+    }
+
+    /// Unpad `data` into a new vector of capacity `CAP`
+    ///
+    /// Fails if `data` is malformed or the message does not fit in `CAP` bytes.
+    pub fn unpad_heapless<const CAP: usize>(data: &[u8]) -> Result<Vec<u8, CAP>, UnpadError> {
+        Self::unpad(data).and_then(|msg| Vec::from_slice(msg).map_err(|_| UnpadError))
+    }
+
+    /// Unpad `buf` in place, moving the message to its front and truncating it to the message
+    ///
+    /// `buf` is left untouched on failure.
+    ///
+    /// ```
+    /// use tx_padding::TxPadding;
+    /// use tx_padding::consts::{U8};
+    ///
+    /// let mut buf = TxPadding::<U8>::pad_heapless::<16>(b"test").unwrap();
+    /// TxPadding::<U8>::unpad_truncate_heapless(&mut buf).unwrap();
+    /// assert_eq!(buf, b"test");
+    /// ```
+    pub fn unpad_truncate_heapless<const CAP: usize>(
+        buf: &mut Vec<u8, CAP>,
+    ) -> Result<(), UnpadError> {
+        let len = raw::unpad_to_front(buf, N::USIZE).map_err(|_| UnpadError)?;
+        buf.truncate(len);
+        Ok(())
+    }
+}
+
+impl<N, R> TxPadding<N, R>
+where
+    N: BlockSize,
+    R: RngCore + CryptoRng + Default,
+{
+    /// Pad `msg` into a new vector of capacity `CAP`
+    ///
+    /// ```
+    /// use tx_padding::TxPadding;
+    /// use tx_padding::consts::{U8};
+    ///
+    /// let padded_msg = TxPadding::<U8>::pad_heapless::<16>(b"test").unwrap();
+    /// assert_eq!(padded_msg.len(), 16);
+    /// assert_eq!(TxPadding::<U8>::unpad_heapless::<4>(&padded_msg).unwrap(), b"test");
+    /// assert!(TxPadding::<U8>::pad_heapless::<8>(b"test").is_err());
+    /// ```
+    pub fn pad_heapless<const CAP: usize>(msg: &[u8]) -> Result<Vec<u8, CAP>, TxPadError> {
+        Self::pad_heapless_with_rng(&mut R::default(), msg)
+    }
+}
+
+impl<const N: usize, R> TxPaddingConst<N, R> {
+    /// Unpad `data` into a new vector of capacity `CAP`
+    pub fn unpad_heapless<const CAP: usize>(data: &[u8]) -> Result<Vec<u8, CAP>, UnpadError> {
+        Self::unpad(data).and_then(|msg| Vec::from_slice(msg).map_err(|_| UnpadError))
+    }
+
+    /// Unpad `buf` in place, moving the message to its front and truncating it to the message
+    pub fn unpad_truncate_heapless<const CAP: usize>(
+        buf: &mut Vec<u8, CAP>,
+    ) -> Result<(), UnpadError> {
+        let () = Self::VALID_BLOCK_SIZE;
+        let len = raw::unpad_to_front(buf, N).map_err(|_| UnpadError)?;
+        buf.truncate(len);
+        Ok(())
+    }
+}
+
+impl<const N: usize, R> TxPaddingConst<N, R>
+where
+    R: RngCore + CryptoRng + Default,
+{
+    /// Pad `msg` into a new vector of capacity `CAP`
+    pub fn pad_heapless<const CAP: usize>(msg: &[u8]) -> Result<Vec<u8, CAP>, TxPadError> {
+        let () = Self::VALID_BLOCK_SIZE;
+        pad_into_heapless(&mut R::default(), msg, N)
+    }
+}
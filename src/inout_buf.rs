@@ -0,0 +1,60 @@
+use core::slice;
+
+use inout::{InOutBuf, InOutBufReserved};
+
+use crate::{raw, BlockSize, CryptoRng, RngCore, TxPadError, TxPadding};
+
+impl<N: BlockSize, R> TxPadding<N, R> {
+    /// Pad the input of `buf` into its output, drawing the random prefix from `rng`
+    ///
+    /// The output of `buf` must be at least [`padded_len`](TxPadding::padded_len) of the input
+    /// length. Returns the padded message as an in-place `InOutBuf` over the output, ready to be
+    /// split into blocks and passed to a `cipher` 0.4 mode. When the input and output are
+    /// separate slices the message is copied exactly once, straight to its final position.
+    pub fn pad_inout_with_rng<'out>(
+        rng: &mut (impl RngCore + CryptoRng),
+        buf: InOutBufReserved<'_, 'out, u8>,
+    ) -> Result<InOutBuf<'out, 'out, u8>, TxPadError> {
+        let msg_len = buf.get_in_len();
+        let out_len = buf.get_out_len();
+        let (in_ptr, out_ptr) = buf.into_raw();
+        // SAFETY: `InOutBufReserved` guarantees the output is valid for writes of `out_len`
+        // bytes during `'out` and is not accessed through any other pointer.
+        let out = unsafe { slice::from_raw_parts_mut(out_ptr, out_len) };
+        let padded_msg = if core::ptr::eq(in_ptr, out_ptr) {
+            raw::pad(rng, out, msg_len, N::USIZE)?
+        } else {
+            // SAFETY: the input is valid for reads of `msg_len` bytes, is not mutated during the
+            // call and does not overlap the output since the pointers differ.
+            let msg = unsafe { slice::from_raw_parts(in_ptr, msg_len) };
+            raw::pad_from(rng, msg, out, N::USIZE)?
+        };
+        Ok(InOutBuf::from(padded_msg))
+    }
+}
+
+impl<N, R> TxPadding<N, R>
+where
+    N: BlockSize,
+    R: RngCore + CryptoRng + Default,
+{
+    /// Pad the input of `buf` into its output
+    ///
+    /// ```
+    /// use inout::InOutBufReserved;
+    /// use tx_padding::TxPadding;
+    /// use tx_padding::consts::{U8};
+    ///
+    /// let msg = b"test";
+    /// let mut out = [0; 16];
+    /// let buf = InOutBufReserved::from_slices(msg, &mut out).unwrap();
+    /// let padded_msg = TxPadding::<U8>::pad_inout(buf).unwrap();
+    /// assert_eq!(padded_msg.len(), 16);
+    /// assert_eq!(&out[5..9], b"test");
+    /// ```
+    pub fn pad_inout<'out>(
+        buf: InOutBufReserved<'_, 'out, u8>,
+    ) -> Result<InOutBuf<'out, 'out, u8>, TxPadError> {
+        Self::pad_inout_with_rng(&mut R::default(), buf)
+    }
+}
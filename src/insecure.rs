@@ -0,0 +1,45 @@
+//! Escape hatch for generators which are not cryptographically secure
+
+use rand::Error;
+
+use crate::{CryptoRng, RngCore};
+
+/// **Not cryptographically secure.** Wrapper marking any generator as a `CryptoRng`
+///
+/// Every method taking an RNG requires `CryptoRng`, since the padding only hides the message
+/// length if the random prefix is unpredictable. Wrapping a generator in `InsecureRng` opts out
+/// of this at the use site, e.g. to pad with a mock generator in tests.
+///
+/// ```
+/// use tx_padding::{InsecureRng, TxPadding};
+/// use tx_padding::consts::{U8};
+/// use rand::rngs::mock::StepRng;
+///
+/// let mut rng = InsecureRng(StepRng::new(0, 0));
+/// let mut buffer = [0xff; 16];
+/// buffer[..4].copy_from_slice(b"test");
+/// let padded_msg = TxPadding::<U8>::pad_with_rng(&mut rng, &mut buffer, 4).unwrap();
+/// assert_eq!(&padded_msg[..], b"\xfa\x00\x00\x00\x00test\x00\x00\x00\x00\x00\x00\x00");
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+pub struct InsecureRng<R>(pub R);
+
+impl<R: RngCore> RngCore for InsecureRng<R> {
+    fn next_u32(&mut self) -> u32 {
+        self.0.next_u32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0.next_u64()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.0.fill_bytes(dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.0.try_fill_bytes(dest)
+    }
+}
+
+impl<R: RngCore> CryptoRng for InsecureRng<R> {}
@@ -0,0 +1,162 @@
+//! Block cipher modes of `cipher` 0.4 combined with TxPadding
+//!
+//! ```
+//! use aes::Aes128;
+//! use cipher::KeyIvInit;
+//! use tx_padding::integration::{TxDecryptor, TxEncryptor};
+//! use tx_padding::TxPadding;
+//! use tx_padding::consts::{U16};
+//!
+//! type Encryptor = TxEncryptor<cbc::Encryptor<Aes128>, U16>;
+//! type Decryptor = TxDecryptor<cbc::Decryptor<Aes128>, U16>;
+//!
+//! let key = [0x42; 16];
+//! let iv = [0x24; 16];
+//! let mut buffer = [0; TxPadding::<U16>::padded_len(11)];
+//! buffer[..11].copy_from_slice(b"hello world");
+//! let ciphertext = Encryptor::new(cbc::Encryptor::new(&key.into(), &iv.into()))
+//!     .encrypt_padded(&mut buffer, 11)
+//!     .unwrap();
+//! assert_eq!(ciphertext.len(), 32);
+//! let msg = Decryptor::new(cbc::Decryptor::new(&key.into(), &iv.into()))
+//!     .decrypt_unpadded(&mut buffer)
+//!     .unwrap();
+//! assert_eq!(msg, b"hello world");
+//! ```
+
+use core::marker::PhantomData;
+
+use cipher::block_padding::NoPadding;
+use cipher::{BlockDecryptMut, BlockEncryptMut, BlockSizeUser};
+
+use crate::ct::unpad_ct;
+use crate::{raw, BlockSize, CryptoRng, DefaultRng, RngCore, TxPadError, UnpadError};
+
+/// Whether messages padded to blocks of `N` can be processed by `C`
+fn is_compatible<C: BlockSizeUser, N: BlockSize>() -> bool {
+    N::USIZE % C::block_size() == 0
+}
+
+/// A block cipher mode encryptor padding the message with TxPadding before encrypting it
+///
+/// The block size `N` of the padding must be a multiple of the block size of the cipher.
+#[derive(Clone, Debug)]
+pub struct TxEncryptor<C, N, R = DefaultRng> {
+    cipher: C,
+    _marker: PhantomData<fn() -> (N, R)>,
+}
+
+impl<C: BlockEncryptMut, N: BlockSize, R> TxEncryptor<C, N, R> {
+    /// Wrap an initialized block cipher mode encryptor
+    pub fn new(cipher: C) -> Self {
+        TxEncryptor {
+            cipher,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Return the wrapped encryptor
+    pub fn into_inner(self) -> C {
+        self.cipher
+    }
+
+    /// Pad the message with length `msg_len` in `buf` and encrypt it, drawing the random prefix
+    /// from `rng`
+    ///
+    /// `buf` must be at least [`TxPadding::padded_len(msg_len)`](crate::TxPadding::padded_len)
+    /// bytes long. Returns the ciphertext.
+    pub fn encrypt_padded_with_rng<'a>(
+        self,
+        rng: &mut (impl RngCore + CryptoRng),
+        buf: &'a mut [u8],
+        msg_len: usize,
+    ) -> Result<&'a [u8], TxPadError> {
+        if !is_compatible::<C, N>() {
+            Err(TxPadError::BlockSizeMismatch {
+                expected: C::block_size(),
+                got: N::USIZE,
+            })?
+        }
+        let padded_msg = raw::pad(rng, buf, msg_len, N::USIZE)?;
+        let padded_len = padded_msg.len();
+        // The padded message is a multiple of the cipher block size, so this cannot fail
+        self.cipher
+            .encrypt_padded_mut::<NoPadding>(padded_msg, padded_len)
+            .map_err(|_| TxPadError::BlockSizeMismatch {
+                expected: C::block_size(),
+                got: N::USIZE,
+            })
+    }
+}
+
+impl<C, N, R> TxEncryptor<C, N, R>
+where
+    C: BlockEncryptMut,
+    N: BlockSize,
+    R: RngCore + CryptoRng + Default,
+{
+    /// Pad the message with length `msg_len` in `buf` and encrypt it
+    pub fn encrypt_padded(self, buf: &mut [u8], msg_len: usize) -> Result<&[u8], TxPadError> {
+        self.encrypt_padded_with_rng(&mut R::default(), buf, msg_len)
+    }
+
+    /// Pad and encrypt `msg` into a newly allocated vector
+    #[cfg(feature = "alloc")]
+    pub fn encrypt_padded_vec(self, msg: &[u8]) -> Result<alloc::vec::Vec<u8>, TxPadError> {
+        let mut buf = alloc::vec![0; raw::padded_len(N::USIZE, msg.len())];
+        buf[..msg.len()].copy_from_slice(msg);
+        self.encrypt_padded(&mut buf, msg.len())?;
+        Ok(buf)
+    }
+}
+
+/// A block cipher mode decryptor unpadding the message with TxPadding after decrypting it
+///
+/// The block size `N` of the padding must be a multiple of the block size of the cipher. The
+/// padding is checked in constant time, see [`TxPadding::unpad_ct`](crate::TxPadding::unpad_ct),
+/// but whether decryption fails is still observable. Unless the ciphertext is authenticated,
+/// e.g. with a MAC, before it is decrypted, an attacker able to submit ciphertexts may learn
+/// about the plaintext from it.
+#[derive(Clone, Debug)]
+pub struct TxDecryptor<C, N> {
+    cipher: C,
+    _marker: PhantomData<N>,
+}
+
+impl<C: BlockDecryptMut, N: BlockSize> TxDecryptor<C, N> {
+    /// Wrap an initialized block cipher mode decryptor
+    pub fn new(cipher: C) -> Self {
+        TxDecryptor {
+            cipher,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Return the wrapped decryptor
+    pub fn into_inner(self) -> C {
+        self.cipher
+    }
+
+    /// Decrypt the ciphertext in `buf` and unpad it in constant time, returning the message
+    pub fn decrypt_unpadded(self, buf: &mut [u8]) -> Result<&[u8], UnpadError> {
+        if !is_compatible::<C, N>() {
+            Err(UnpadError)?
+        }
+        let padded_msg = self
+            .cipher
+            .decrypt_padded_mut::<NoPadding>(buf)
+            .map_err(|_| UnpadError)?;
+        Option::from(unpad_ct(padded_msg, N::USIZE)).ok_or(UnpadError)
+    }
+
+    /// Decrypt and unpad `ciphertext` into a newly allocated vector
+    #[cfg(feature = "alloc")]
+    pub fn decrypt_unpadded_vec(
+        self,
+        ciphertext: &[u8],
+    ) -> Result<alloc::vec::Vec<u8>, UnpadError> {
+        let mut buf = ciphertext.to_vec();
+        let msg = self.decrypt_unpadded(&mut buf)?;
+        Ok(msg.to_vec())
+    }
+}
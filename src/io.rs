@@ -0,0 +1,174 @@
+//! Adapters padding and unpadding through `std::io`
+
+use std::io::{self, Read, Write};
+
+use crate::stream::MAX_PENDING;
+use crate::{BlockSize, CryptoRng, DefaultRng, Padder, RngCore, TxPadError, Unpadder};
+
+/// Size of the scratch buffer the padded output is staged in before writing it out
+const SCRATCH_LEN: usize = 4096;
+
+pub(crate) fn to_io_error(e: TxPadError) -> io::Error {
+    let kind = match e {
+        TxPadError::RngFailure => io::ErrorKind::Other,
+        _ => io::ErrorKind::InvalidInput,
+    };
+    io::Error::new(kind, e)
+}
+
+fn malformed_padding() -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, "malformed padding")
+}
+
+/// A writer padding everything written to it before passing it to the inner writer
+///
+/// The length of the message must be announced up front, see [`Padder`]. Complete blocks are
+/// written to the inner writer as soon as they are available, and [`finish`](PadWriter::finish)
+/// writes the end of the padded message.
+///
+/// ```
+/// use std::io::Write;
+/// use tx_padding::{PadWriter, TxPadding, Padding};
+/// use tx_padding::consts::{U8};
+///
+/// let mut writer = PadWriter::<_, U8>::new(Vec::new(), 11).unwrap();
+/// writer.write_all(b"hello ").unwrap();
+/// writer.write_all(b"world").unwrap();
+/// let padded_msg = writer.finish().unwrap();
+/// assert_eq!(TxPadding::<U8>::unpad(&padded_msg).unwrap(), b"hello world");
+/// ```
+#[derive(Debug)]
+pub struct PadWriter<W: Write, N, R = DefaultRng> {
+    inner: W,
+    padder: Padder<N, R>,
+}
+
+impl<W: Write, N: BlockSize, R> PadWriter<W, N, R> {
+    /// Pad a message of `msg_len` bytes into `inner`, drawing the random prefix from `rng`
+    pub fn with_rng(
+        inner: W,
+        rng: &mut (impl RngCore + CryptoRng),
+        msg_len: usize,
+    ) -> io::Result<Self> {
+        Ok(PadWriter {
+            inner,
+            padder: Padder::with_rng(rng, msg_len).map_err(to_io_error)?,
+        })
+    }
+
+    /// Get a reference to the inner writer
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Write the end of the padded message and return the inner writer
+    ///
+    /// Fails if fewer bytes than announced were written.
+    pub fn finish(mut self) -> io::Result<W> {
+        let mut scratch = [0; MAX_PENDING + 256];
+        let n = self.padder.finalize(&mut scratch).map_err(to_io_error)?;
+        self.inner.write_all(&scratch[..n])?;
+        self.inner.flush()?;
+        Ok(self.inner)
+    }
+}
+
+impl<W, N, R> PadWriter<W, N, R>
+where
+    W: Write,
+    N: BlockSize,
+    R: RngCore + CryptoRng + Default,
+{
+    /// Pad a message of `msg_len` bytes into `inner`
+    pub fn new(inner: W, msg_len: usize) -> io::Result<Self> {
+        Self::with_rng(inner, &mut R::default(), msg_len)
+    }
+}
+
+impl<W: Write, N: BlockSize, R> Write for PadWriter<W, N, R> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let mut scratch = [0; SCRATCH_LEN];
+        let buf = &buf[..buf.len().min(SCRATCH_LEN - MAX_PENDING)];
+        let n = self.padder.update(buf, &mut scratch).map_err(to_io_error)?;
+        self.inner.write_all(&scratch[..n])?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// A reader unpadding a padded message read from the inner reader
+///
+/// Message bytes are returned as soon as they are known not to be part of the padding, see
+/// [`Unpadder`]. The padding is validated when the inner reader reaches the end of file, and
+/// the final read fails with `ErrorKind::InvalidData` if it is malformed, so the message must
+/// not be trusted before the reader returned `Ok(0)`.
+///
+/// ```
+/// use std::io::Read;
+/// use tx_padding::{UnpadReader, TxPadding, Padding};
+/// use tx_padding::consts::{U8};
+///
+/// let mut buffer = [0; 24];
+/// buffer[..11].copy_from_slice(b"hello world");
+/// let padded_msg = TxPadding::<U8>::pad(&mut buffer, 11, 8).unwrap();
+/// let mut msg = Vec::new();
+/// UnpadReader::<_, U8>::new(&padded_msg[..]).read_to_end(&mut msg).unwrap();
+/// assert_eq!(msg, b"hello world");
+/// ```
+#[derive(Debug)]
+pub struct UnpadReader<R: Read, N> {
+    inner: R,
+    unpadder: Option<Unpadder<N>>,
+}
+
+impl<R: Read, N: BlockSize> UnpadReader<R, N> {
+    /// Unpad the padded message read from `inner`
+    pub fn new(inner: R) -> Self {
+        UnpadReader {
+            inner,
+            unpadder: Some(Unpadder::new()),
+        }
+    }
+
+    /// Get a reference to the inner reader
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Return the inner reader
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: Read, N: BlockSize> Read for UnpadReader<R, N> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        let mut scratch = [0; SCRATCH_LEN];
+        let scratch = &mut scratch[..buf.len().min(SCRATCH_LEN)];
+        loop {
+            let unpadder = match self.unpadder.as_mut() {
+                Some(unpadder) => unpadder,
+                None => return Ok(0),
+            };
+            let n = self.inner.read(scratch)?;
+            if n == 0 {
+                return match self.unpadder.take().map(Unpadder::finish) {
+                    Some(Err(_)) => Err(malformed_padding()),
+                    _ => Ok(0),
+                };
+            }
+            let n = unpadder
+                .update(&scratch[..n], buf)
+                .map_err(|_| malformed_padding())?;
+            if n > 0 {
+                return Ok(n);
+            }
+        }
+    }
+}
@@ -4,17 +4,27 @@
 //! in which random bytes are prepended and zeros are appended.
 //!
 //! For a message of length `size`, a buffer of length
-//! `block_size * ((size + 1) / block_size) + 2 * block_size` is required for padding. Let `pad_len` be
+//! `block_size * ((size + 1) / block_size) + 2 * block_size` is required for padding, which is
+//! what [`TxPadding::padded_len`] computes. Let `pad_len` be
 //! `(-size - 2) % block_size + 2`. Apparently `pad_len` is the number of bytes to pad the message into
 //! multiple of `block_size`. The padding scheme appends `pad_len + 1` bytes at the front of the
 //! message, where the lower `log(block_size)` bits of the first byte stores `pad_len - 2` and the rest
 //! of the bits in the padding are random. At this point the message needs `block_size - 1` more
 //! bytes to form multiple of `block_size` and we will just pad `\0` at the end.
 //!
+//! For compatibility with earlier versions, the upper bits of the first byte are set to ones rather
+//! than random. [`TxPadding::pad_random_header_with_rng`] makes them random as well, so the first
+//! byte is not a constant distinguisher. Unpadding ignores these bits, so both kinds of output are
+//! unpadded the same way.
+//!
 //! So `TxPadding<N>` comes with a type parameter `N` which specify the block size to use which is
-//! essential for unpadding. `N` must be a power of 2.
+//! essential for unpadding. `N` must be a power of 2. [`TxPaddingConst`] implements the same
+//! scheme with the block size given as a const generic parameter instead, and [`TxPaddingDyn`]
+//! with the block size chosen at runtime.
 //!
 //! ```
+//! # #[cfg(any(feature = "os-rng", feature = "thread-rng"))]
+//! # {
 //! use tx_padding::{TxPadding, Padding};
 //! use tx_padding::consts::{U8};
 //!
@@ -26,94 +36,1044 @@
 //! assert_eq!(&padded_msg[5..], b"test\x00\x00\x00\x00\x00\x00\x00");
 //! assert_eq!((padded_msg[0] & 0x7) + 2, 4);
 //! assert_eq!(TxPadding::<U8>::unpad(&padded_msg).unwrap(), msg);
+//! # }
 //! ```
 //! ```
+//! # #[cfg(any(feature = "os-rng", feature = "thread-rng"))]
+//! # {
 //! use tx_padding::{TxPadding, Padding};
 //! use tx_padding::consts::{U8};
 //! let mut buffer = [0xff; 8];
 //! assert!(TxPadding::<U8>::pad(&mut buffer, 5, 8).is_err());
+//! # }
 //! ```
 //!
 //! `pad_block` will always return `PadError` since it is not intended to be called. `pad` will
-//! return `PadError` if `block_size > 511`, `block_size` mismatch type parameter `N` or buffer
-//! is not sufficiently large, which is stricter than the requirement of the `Padding` trait.
+//! return `PadError` if `block_size` mismatch type parameter `N`, `pos` exceeds the buffer or
+//! buffer is not sufficiently large, which is stricter than the requirement of the `Padding`
+//! trait.
+//! The inherent padding methods return [`TxPadError`] instead, which tells these cases apart.
+//!
+//! With the block cipher modes of the `block-modes` crate, the buffer passed to `encrypt` must
+//! hold [`TxPadding::padded_len`] bytes. `encrypt_vec` only reserves a single block for the
+//! padding, so it panics for most messages, and `decrypt_vec` assumes the message starts the
+//! buffer, so it returns the random prefix instead. Neither should be used with `TxPadding`.
+//!
+//! ```
+//! # #[cfg(any(feature = "os-rng", feature = "thread-rng"))]
+//! # {
+//! # use aes_07 as aes;
+//! use aes::Aes128;
+//! use block_modes::{BlockMode, Cbc};
+//! use tx_padding::TxPadding;
+//! use tx_padding::consts::{U16};
+//!
+//! type Aes128Cbc = Cbc<Aes128, TxPadding<U16>>;
+//!
+//! let (key, iv) = ([0x42; 16], [0x24; 16]);
+//! let msg = b"hello world";
+//! let mut buffer = [0; TxPadding::<U16>::padded_len(11)];
+//! buffer[..msg.len()].copy_from_slice(msg);
+//! let cipher = Aes128Cbc::new_from_slices(&key, &iv).unwrap();
+//! let n = cipher.encrypt(&mut buffer, msg.len()).unwrap().len();
+//! let cipher = Aes128Cbc::new_from_slices(&key, &iv).unwrap();
+//! assert_eq!(cipher.decrypt(&mut buffer[..n]).unwrap(), msg);
+//! # }
+//! ```
+//!
+//! Unless one of the `uninit`, `inout`, `no-panic`, `entropy-source`, `rdrand` or `ffi`
+//! features is enabled, the crate is built with `#![forbid(unsafe_code)]`. None of them is
+//! enabled by default, so neither is any unsafe code.
+//!
+//! The crate supports targets with a 16-bit `usize`, such as MSP430 and AVR. Lengths are only
+//! computed with checked or overflow-free arithmetic, so a message whose padded output would not
+//! fit in the address space is rejected with `MessageTooLong`.
+//!
+//! The minimum supported Rust version is 1.61 for the default features and the features without
+//! an optional dependency. The integrations with other crates, such as `tokio`, `cli`, `rayon`
+//! or `proptest`, require the minimum Rust version of the crate they integrate with.
+//!
+//! None of the padding and unpadding methods of `TxPadding` and `TxPaddingConst` panic on any
+//! input. With the `no-panic` feature, linking an optimized binary which calls them fails unless
+//! the compiler proved this for every call, provided the RNG in use does not panic either.
+//!
+//! The random bytes are drawn from the second type parameter `R`, which defaults to
+//! [`DefaultRng`]. Any generator implementing `RngCore + CryptoRng + Default` can be plugged in,
+//! e.g. a driver for a hardware TRNG on platforms where the OS generator is not available.
+//! The methods taking an RNG argument require `CryptoRng` as well; other generators, such as
+//! mocks in tests, must be wrapped in [`InsecureRng`] to opt out explicitly.
+//! Which generator [`DefaultRng`] is depends on the features:
+//!
+//! - `thread-rng`: `ThreadRng`, which requires `std`
+//! - `custom-rng`: `GlobalRng`, which draws from the `SecureRng` registered with `register_rng`
+//!   behind a `critical-section` lock, for bare-metal targets
+//! - `entropy-source`: `RandomSource`, which draws from the function registered with
+//!   `set_entropy_source`, or from `OsRng` if none is and `os-rng` is enabled
+//! - `os-rng` (enabled by default): `OsRng`
+//!
+//! The `thread_rng` feature is an alias of `thread-rng`. Up to version 0.1.2 it had no effect, as
+//! it was checked with a misspelled `cfg`, so enabling it now changes the default RNG from
+//! `OsRng` to `ThreadRng`.
+//!
+//! `ThreadRng` is a CSPRNG cached per thread and periodically reseeded from `OsRng`, which saves
+//! a system call per padded message. It is only selected by `thread-rng`, never by `std` alone,
+//! so callers requiring fresh OS entropy for every message keep `OsRng` unless they opt in. The
+//! types of this crate only carry the RNG as a marker, so they are `Send` and `Sync` whichever
+//! generator is selected, although `ThreadRng` itself is neither.
+//!
+//! Without any of them, [`DefaultRng`] is [`NoDefaultRng`]: the padding methods drawing from the
+//! default RNG do not compile, while those taking an RNG argument and the inherent unpadding
+//! methods do. The [`Padding`] implementations, and so `Compose` and the scheme registry, still
+//! require a default RNG.
+//!
+//! `os-rng` can be combined with any of the others, which take precedence over it, but enabling
+//! more than one of `thread-rng`, `custom-rng` and `entropy-source` is a compile-time error, as
+//! one choice would silently override the other. Cargo unifies the features of a dependency
+//! across the dependency graph, so libraries should leave this choice to the final binary and
+//! take an RNG argument or type parameter instead. For the same reason `--all-features` is not a
+//! valid configuration of this crate.
+//!
+//! The `insecure-fast-rng` feature provides `FastRng`, a fast generator which is **not
+//! cryptographically secure**, for simulations and fuzzing. It does not implement `CryptoRng`,
+//! so it is only accepted as `InsecureFastRng`, i.e. wrapped in [`InsecureRng`]. It never becomes
+//! [`DefaultRng`] and has to be named explicitly.
+//!
+//! On x86 and x86-64, the `rdrand` feature provides `RdRandRng`, which draws from the RDRAND and
+//! RDSEED instructions instead of making a system call per padded message, falling back to
+//! `OsRng` where they are unavailable. It has to be named explicitly as well.
+//!
+//! ```
+//! # #[cfg(any(feature = "os-rng", feature = "thread-rng"))]
+//! # {
+//! use tx_padding::{TxPadding, Padding};
+//! use tx_padding::consts::{U8};
+//! use rand::rngs::OsRng;
+//!
+//! let mut buffer = [0; 16];
+//! buffer[..4].copy_from_slice(b"test");
+//! let padded_msg = TxPadding::<U8, OsRng>::pad(&mut buffer, 4, 8).unwrap();
+//! assert_eq!(TxPadding::<U8, OsRng>::unpad(&padded_msg).unwrap(), b"test");
+//! # }
+//! ```
 #![no_std]
+#![cfg_attr(
+    not(any(
+        feature = "uninit",
+        feature = "inout",
+        feature = "no-panic",
+        feature = "entropy-source",
+        feature = "rdrand",
+        feature = "ffi"
+    )),
+    forbid(unsafe_code)
+)]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
 
 pub use block_padding::{PadError, Padding, UnpadError};
 
 use core::convert::Infallible;
 use core::marker::PhantomData;
+#[cfg(feature = "uninit")]
+use core::mem::MaybeUninit;
+use core::ops::Range;
 
 use consts::{U1, U256};
 pub use typenum::consts;
 
-use typenum::marker_traits::{NonZero, PowerOfTwo, Unsigned};
+use typenum::marker_traits::{NonZero, Unsigned};
 use typenum::operator_aliases::{Gr, LeEq};
 use typenum::type_operators::{IsGreater, IsLessOrEqual};
 
-use rand::RngCore;
+pub use rand::{CryptoRng, RngCore};
+
+#[macro_use]
+mod no_panic;
+
+mod macros;
+
+/// Pad a byte string at compile time, expanding to a `[u8; _]` array
+///
+/// `tx_pad!(b"...", N, seed = S)` produces the same bytes as
+/// [`pad_with_rng`](TxPadding::pad_with_rng) drawing from `ChaCha20Rng::seed_from_u64(S)`. The
+/// random prefix is fully determined by the seed, so this is meant for fixtures and golden
+/// frames rather than for hiding the length of secret messages.
+///
+/// ```
+/// use tx_padding::{tx_pad, TxPadding, Padding};
+/// use tx_padding::consts::{U8};
+///
+/// const FRAME: [u8; 16] = tx_pad!(b"test", U8, seed = 42);
+/// assert_eq!(TxPadding::<U8>::unpad(&FRAME).unwrap(), b"test");
+/// ```
+/// ```compile_fail
+/// use tx_padding::tx_pad;
+///
+/// const FRAME: [u8; 16] = tx_pad!(b"test", U12, seed = 42);
+/// ```
+#[cfg(feature = "macros")]
+pub use tx_padding_macros::tx_pad;
+
+mod error;
+pub use error::TxPadError;
+#[cfg(feature = "diagnostics")]
+pub use error::UnpadErrorKind;
+
+mod const_generic;
+pub use const_generic::TxPaddingConst;
+
+mod dynamic;
+pub use dynamic::TxPaddingDyn;
+
+mod type_len;
+pub use type_len::PaddedLen;
+
+mod dyn_padding;
+pub use dyn_padding::DynPadding;
+
+mod registry;
+pub use registry::{SchemeId, SchemeRegistry};
+
+mod raw;
+mod trace;
+
+mod insecure;
+pub use insecure::InsecureRng;
+
+mod tail;
+pub use tail::TxTailPadding;
+
+mod crc;
+pub use crc::TxPaddingCrc;
+
+#[cfg(feature = "mac")]
+mod mac;
+#[cfg(feature = "mac")]
+pub use mac::TxPaddingMac;
+
+mod wide;
+pub use wide::TxPaddingWide;
+
+mod any_size;
+pub use any_size::TxPaddingAnySize;
+
+mod random_tail;
+pub use random_tail::TxRandomTailPadding;
+
+mod padme;
+pub use padme::Padme;
 
-#[cfg(not(features = "thread_rng"))]
-type DefaultRng = rand::rngs::OsRng;
-#[cfg(features = "thread_rng")]
-type DefaultRng = rand::ThreadRng;
+mod bucket;
+pub use bucket::BucketPadding;
 
+mod compose;
+pub use compose::Compose;
+
+mod randomize;
+pub use randomize::Randomize;
+
+mod stream;
+pub use stream::{Padder, Unpadder};
+
+mod vectored;
+pub use vectored::PaddedParts;
+
+mod padded;
+pub use padded::Padded;
+
+mod versioned;
+
+mod min_len;
+
+mod batch;
+
+mod frames;
+
+mod accumulator;
+pub use accumulator::FrameAccumulator;
+
+#[cfg(feature = "frame")]
+mod frame;
+
+#[cfg(feature = "rayon")]
+mod par;
+
+mod aligned;
+
+#[cfg(feature = "std")]
+mod io;
+#[cfg(feature = "std")]
+pub use io::{PadWriter, UnpadReader};
+
+#[cfg(any(feature = "block-padding-03", feature = "block-padding-04"))]
+pub mod blocks;
+
+#[cfg(feature = "embedded-io")]
+pub mod embedded;
+
+#[cfg(feature = "integration")]
+pub mod integration;
+
+#[cfg(feature = "inout")]
+mod inout_buf;
+
+#[cfg(feature = "zeroize")]
+mod secret;
+#[cfg(feature = "zeroize")]
+pub use secret::SecretPadded;
+
+#[cfg(feature = "generic-array")]
+mod fixed;
+
+#[cfg(feature = "bytes")]
+mod byte_buf;
+
+#[cfg(feature = "tokio")]
+mod codec;
+#[cfg(feature = "tokio")]
+pub use codec::TxPaddingCodec;
+
+#[cfg(feature = "subtle")]
+mod ct;
+#[cfg(feature = "replay")]
+mod replay;
+#[cfg(feature = "seeded")]
+mod seeded;
+#[cfg(feature = "replay")]
+pub use replay::ReplayRng;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(any(feature = "arbitrary", feature = "proptest"))]
+mod property;
+#[cfg(feature = "test-utils")]
+pub mod testing;
+#[cfg(feature = "alloc")]
+mod vec;
+#[cfg(feature = "test-vectors")]
+pub mod vectors;
+#[cfg(any(feature = "arbitrary", feature = "proptest"))]
+pub use property::{PaddedFrame, SizedMessage};
+#[cfg(feature = "wasm-js")]
+pub mod wasm;
+#[cfg(feature = "alloc")]
+pub use vec::PaddedVec;
+#[cfg(feature = "alloc")]
+mod session;
+#[cfg(feature = "alloc")]
+pub use session::{Session, SessionEvent};
+#[cfg(feature = "arrayvec")]
+mod array_vec;
+#[cfg(feature = "encoding")]
+mod encoding;
+#[cfg(feature = "heapless")]
+mod heapless_vec;
+#[cfg(feature = "serde")]
+mod padded_message;
+#[cfg(feature = "serde")]
+pub use padded_message::PaddedMessage;
+
+#[cfg(feature = "entropy-source")]
+mod entropy;
+#[cfg(feature = "entropy-source")]
+pub use entropy::{set_entropy_source, EntropyFn, RandomSource, NO_ENTROPY_SOURCE};
+
+#[cfg(feature = "custom-rng")]
+mod global_rng;
+#[cfg(feature = "custom-rng")]
+pub use global_rng::{register_rng, GlobalRng, SecureRng};
+
+#[cfg(feature = "insecure-fast-rng")]
+mod fast_rng;
+#[cfg(feature = "insecure-fast-rng")]
+pub use fast_rng::{FastRng, InsecureFastRng};
+
+#[cfg(all(feature = "rdrand", any(target_arch = "x86", target_arch = "x86_64")))]
+mod rdrand;
+#[cfg(all(feature = "rdrand", any(target_arch = "x86", target_arch = "x86_64")))]
+pub use rdrand::RdRandRng;
+
+#[cfg(any(
+    all(feature = "thread-rng", feature = "custom-rng"),
+    all(feature = "thread-rng", feature = "entropy-source"),
+    all(feature = "custom-rng", feature = "entropy-source")
+))]
+compile_error!(
+    "at most one of the `thread-rng`, `custom-rng` and `entropy-source` features can be enabled"
+);
+
+/// The RNG used to generate the random prefix when none is specified
+#[cfg(feature = "thread-rng")]
+pub type DefaultRng = rand::rngs::ThreadRng;
+/// The RNG used to generate the random prefix when none is specified
+#[cfg(all(feature = "custom-rng", not(feature = "thread-rng")))]
+pub type DefaultRng = GlobalRng;
+/// The RNG used to generate the random prefix when none is specified
+#[cfg(all(
+    feature = "entropy-source",
+    not(any(feature = "thread-rng", feature = "custom-rng"))
+))]
+pub type DefaultRng = RandomSource;
+/// The RNG used to generate the random prefix when none is specified
+#[cfg(all(
+    feature = "os-rng",
+    not(any(
+        feature = "thread-rng",
+        feature = "custom-rng",
+        feature = "entropy-source"
+    ))
+))]
+pub type DefaultRng = rand::rngs::OsRng;
+/// The RNG used to generate the random prefix when none is specified
+#[cfg(not(any(
+    feature = "os-rng",
+    feature = "thread-rng",
+    feature = "custom-rng",
+    feature = "entropy-source"
+)))]
+pub type DefaultRng = NoDefaultRng;
+
+/// Stand-in for [`DefaultRng`] when none of the RNG features is enabled
+///
+/// It is not a generator, so using a method which draws from the default RNG is a compile-time
+/// error until one of the `os-rng`, `thread-rng`, `custom-rng` or `entropy-source` features is
+/// enabled. The methods taking an RNG argument and the unpadding methods are always available.
+///
+/// ```compile_fail
+/// use tx_padding::{NoDefaultRng, TxPadding};
+/// use tx_padding::consts::{U8};
+///
+/// let mut out = [0; 16];
+/// TxPadding::<U8, NoDefaultRng>::pad_into(b"test", &mut out).unwrap();
+/// ```
 #[derive(Clone, Copy, Debug)]
-pub enum TxPadding<N> {
-    _Phantom(Infallible, PhantomData<N>),
+pub enum NoDefaultRng {}
+
+/// Block sizes supported by the padding scheme
+///
+/// This is implemented for every `typenum` unsigned integer which is a power of 2 between 2 and
+/// 256 inclusive, and cannot be implemented outside of this crate.
+#[rustversion::attr(
+    since(1.78),
+    diagnostic::on_unimplemented(
+        message = "unsupported block size",
+        label = "the block size must be a power of 2 between 2 and 256",
+        note = "use one of `U2`, `U4`, `U8`, `U16`, `U32`, `U64`, `U128` or `U256` from `tx_padding::consts`"
+    )
+)]
+pub trait BlockSize: Unsigned + private::Sealed {}
+
+/// Block sizes supported by [`TxPaddingWide`]
+///
+/// This is implemented for every `typenum` unsigned integer which is a power of 2 between 2 and
+/// 65536 inclusive, and cannot be implemented outside of this crate. On 16-bit targets `U65536`
+/// is left out, since it does not fit in a `usize`.
+#[rustversion::attr(
+    since(1.78),
+    diagnostic::on_unimplemented(
+        message = "unsupported block size for `TxPaddingWide`",
+        label = "the block size must be a power of 2 between 2 and 65536"
+    )
+)]
+pub trait WideBlockSize: Unsigned + private::WideSealed {}
+
+// The sizes are listed one by one rather than derived from `typenum` bounds, so a rejected size
+// is reported against `BlockSize` itself instead of some `typenum` bit deep in the bounds.
+macro_rules! impl_block_size {
+    ($($n:ident),*) => {
+        $(
+            impl private::Sealed for consts::$n {}
+            impl BlockSize for consts::$n {}
+        )*
+    };
 }
 
-impl<N> Padding for TxPadding<N>
+macro_rules! impl_wide_block_size {
+    ($($n:ident),*) => {
+        $(
+            impl private::WideSealed for consts::$n {}
+            impl WideBlockSize for consts::$n {}
+        )*
+    };
+}
+
+impl_block_size!(U2, U4, U8, U16, U32, U64, U128, U256);
+impl_wide_block_size!(
+    U2, U4, U8, U16, U32, U64, U128, U256, U512, U1024, U2048, U4096, U8192, U16384, U32768
+);
+#[cfg(not(target_pointer_width = "16"))]
+impl_wide_block_size!(U65536);
+
+/// Block sizes supported by [`TxPaddingAnySize`]
+///
+/// This is implemented for every `typenum` unsigned integer between 2 and 256 inclusive, and
+/// cannot be implemented outside of this crate.
+pub trait AnyBlockSize: Unsigned + private::AnySealed {}
+
+impl<N> private::AnySealed for N
 where
-    N: PowerOfTwo + Unsigned + IsLessOrEqual<U256> + IsGreater<U1>,
+    N: Unsigned + IsLessOrEqual<U256> + IsGreater<U1>,
     LeEq<N, U256>: NonZero,
     Gr<N, U1>: NonZero,
 {
-    fn pad_block(_block: &mut [u8], _pos: usize) -> Result<(), PadError> {
-        Err(PadError)
+}
+
+impl<N: Unsigned + private::AnySealed> AnyBlockSize for N {}
+
+mod private {
+    pub trait Sealed {}
+    pub trait WideSealed {}
+    pub trait AnySealed {}
+}
+
+#[derive(Clone, Copy, Debug)]
+pub enum TxPadding<N, R = DefaultRng> {
+    _Phantom(Infallible, PhantomData<fn() -> (N, R)>),
+}
+
+impl<N: BlockSize, R> TxPadding<N, R> {
+    /// Block size of the scheme, i.e. `N` as a `usize`
+    pub const BLOCK_SIZE: usize = N::USIZE;
+
+    /// Smallest number of bytes the padding adds to a message of any length
+    ///
+    /// It is reached when `msg_len + 2` is a multiple of the block size.
+    pub const MIN_OVERHEAD: usize = raw::min_overhead(N::USIZE);
+
+    /// Largest number of bytes the padding adds to a message of any length
+    ///
+    /// It is reached when `msg_len + 1` is a multiple of the block size.
+    ///
+    /// ```
+    /// use tx_padding::TxPadding;
+    /// use tx_padding::consts::{U8};
+    ///
+    /// let buffer = [0u8; 32 + TxPadding::<U8>::MAX_OVERHEAD];
+    /// assert!(buffer.len() >= TxPadding::<U8>::padded_len(32));
+    /// ```
+    pub const MAX_OVERHEAD: usize = raw::max_overhead(N::USIZE);
+
+    /// Longest message the padding methods accept
+    ///
+    /// The padded output of any longer message would exceed `isize::MAX` bytes, so it could not
+    /// be held in a slice on the target. Padding such a message fails with
+    /// [`TxPadError::MessageTooLong`].
+    ///
+    /// ```
+    /// use tx_padding::TxPadding;
+    /// use tx_padding::consts::{U8};
+    ///
+    /// let max = TxPadding::<U8>::MAX_MSG_LEN;
+    /// assert!(TxPadding::<U8>::padded_len(max) <= isize::MAX as usize);
+    /// ```
+    pub const MAX_MSG_LEN: usize = raw::max_msg_len(N::USIZE);
+
+    /// Length of the padded output for a message of length `msg_len`
+    ///
+    /// This is also the minimal length of the buffer passed to the padding methods.
+    ///
+    /// ```
+    /// use tx_padding::TxPadding;
+    /// use tx_padding::consts::{U8};
+    ///
+    /// const BUF_LEN: usize = TxPadding::<U8>::padded_len(4);
+    /// let buffer = [0u8; BUF_LEN];
+    /// assert_eq!(buffer.len(), 16);
+    /// ```
+    pub const fn padded_len(msg_len: usize) -> usize {
+        raw::padded_len(N::USIZE, msg_len)
     }
 
-    fn unpad(data: &[u8]) -> Result<&[u8], UnpadError> {
-        if data.is_empty() {
-            Err(UnpadError)?
+    /// Length of the padded output for a message of length `msg_len`, or `None` if it overflows
+    /// `usize`
+    ///
+    /// Prefer this over [`padded_len`](TxPadding::padded_len) for untrusted lengths, since the
+    /// latter overflows for lengths close to `usize::MAX`.
+    pub const fn checked_padded_len(msg_len: usize) -> Option<usize> {
+        raw::checked_padded_len(N::USIZE, msg_len)
+    }
+
+    /// Number of bytes the padding adds to a message of length `msg_len`
+    pub const fn overhead(msg_len: usize) -> usize {
+        Self::padded_len(msg_len) - msg_len
+    }
+
+    /// Largest number of bytes the padding adds to a message of any length, the same as
+    /// [`MAX_OVERHEAD`](TxPadding::MAX_OVERHEAD)
+    pub const fn max_overhead() -> usize {
+        Self::MAX_OVERHEAD
+    }
+
+    /// Pads message with length `pos` in the provided buffer, drawing the random prefix from `rng`
+    ///
+    /// This is what `Padding::pad` calls with a fresh `R::default()`. Use it directly to supply a
+    /// caller-owned generator, e.g. a seeded one for reproducible output or a hardware TRNG.
+    ///
+    /// ```
+    /// use tx_padding::{InsecureRng, Padding, TxPadding};
+    /// use tx_padding::consts::{U8};
+    /// use rand::rngs::mock::StepRng;
+    ///
+    /// let mut rng = InsecureRng(StepRng::new(0, 0));
+    /// let mut buffer = [0xff; 16];
+    /// buffer[..4].copy_from_slice(b"test");
+    /// let padded_msg = TxPadding::<U8>::pad_with_rng(&mut rng, &mut buffer, 4).unwrap();
+    /// assert_eq!(&padded_msg[..], b"\xfa\x00\x00\x00\x00test\x00\x00\x00\x00\x00\x00\x00");
+    /// ```
+    pub fn pad_with_rng<'a>(
+        rng: &mut (impl RngCore + CryptoRng),
+        buf: &'a mut [u8],
+        pos: usize,
+    ) -> Result<&'a mut [u8], TxPadError> {
+        no_panic! {
+            raw::pad(rng, buf, pos, N::USIZE)
         }
-        let l = data.len();
-        let block_size = N::to_usize();
-        let pad_zero = block_size - 1;
-        let pad_len = (data[0] & (pad_zero as u8)) as usize + 2;
-        if l < pad_len + block_size {
-            Err(UnpadError)?
+    }
+
+    /// Pads message with length `pos` in the provided buffer like
+    /// [`pad_with_rng`](TxPadding::pad_with_rng), but with random upper bits in the first byte
+    ///
+    /// Only the lower `log(N)` bits of the first byte are needed to unpad, so the output is
+    /// accepted by every unpadding method.
+    ///
+    /// ```
+    /// use tx_padding::{InsecureRng, Padding, TxPadding};
+    /// use tx_padding::consts::{U8};
+    /// use rand::rngs::mock::StepRng;
+    ///
+    /// let mut rng = InsecureRng(StepRng::new(0, 0));
+    /// let mut buffer = [0xff; 16];
+    /// buffer[..4].copy_from_slice(b"test");
+    /// let padded_msg = TxPadding::<U8>::pad_random_header_with_rng(&mut rng, &mut buffer, 4).unwrap();
+    /// assert_eq!(padded_msg[0], 0x02);
+    /// assert_eq!(TxPadding::<U8>::unpad(padded_msg).unwrap(), b"test");
+    /// ```
+    pub fn pad_random_header_with_rng<'a>(
+        rng: &mut (impl RngCore + CryptoRng),
+        buf: &'a mut [u8],
+        pos: usize,
+    ) -> Result<&'a mut [u8], TxPadError> {
+        no_panic! {
+            let padded_msg = raw::pad(rng, buf, pos, N::USIZE)?;
+            raw::randomize_header(rng, &mut padded_msg[0], N::USIZE)?;
+            Ok(padded_msg)
         }
-        if data[l - pad_zero..l].iter().any(|&v| v != 0) {
-            Err(UnpadError)?
+    }
+
+    /// Pads message with length `pos` in the provided buffer like
+    /// [`pad_with_rng`](TxPadding::pad_with_rng), but without writing the trailing zeros
+    ///
+    /// The caller guarantees that the buffer is zero from `pos` up to the padded length, as in a
+    /// freshly zeroed allocation. Otherwise the output is malformed; this is only checked in
+    /// debug builds.
+    pub fn pad_assume_zeroed_with_rng<'a>(
+        rng: &mut (impl RngCore + CryptoRng),
+        buf: &'a mut [u8],
+        pos: usize,
+    ) -> Result<&'a mut [u8], TxPadError> {
+        no_panic! {
+            raw::pad_assume_zeroed(rng, buf, pos, N::USIZE)
         }
+    }
 
-        Ok(&data[1 + pad_len..l - pad_zero])
+    /// Pads message with length `pos` in the provided buffer like
+    /// [`pad_with_rng`](TxPadding::pad_with_rng), but with the trailing zeros replaced by `fill`
+    ///
+    /// This suits storage or protocols expecting a particular sentinel, e.g. `0xff` for NOR flash.
+    /// Such output must be unpadded with [`unpad_filled`](TxPadding::unpad_filled) and the same
+    /// `fill`.
+    ///
+    /// ```
+    /// use tx_padding::{InsecureRng, TxPadding};
+    /// use tx_padding::consts::{U8};
+    /// use rand::rngs::mock::StepRng;
+    ///
+    /// let mut rng = InsecureRng(StepRng::new(0, 0));
+    /// let mut buffer = [0; 16];
+    /// buffer[..4].copy_from_slice(b"test");
+    /// let padded_msg =
+    ///     TxPadding::<U8>::pad_filled_with_rng(&mut rng, &mut buffer, 4, 0xff).unwrap();
+    /// assert_eq!(&padded_msg[5..], b"test\xff\xff\xff\xff\xff\xff\xff");
+    /// assert_eq!(TxPadding::<U8>::unpad_filled(padded_msg, 0xff).unwrap(), b"test");
+    /// ```
+    pub fn pad_filled_with_rng<'a>(
+        rng: &mut (impl RngCore + CryptoRng),
+        buf: &'a mut [u8],
+        pos: usize,
+        fill: u8,
+    ) -> Result<&'a mut [u8], TxPadError> {
+        no_panic! {
+            raw::pad_filled(rng, buf, pos, N::USIZE, fill)
+        }
     }
 
-    fn pad(buf: &mut [u8], pos: usize, block_size: usize) -> Result<&mut [u8], PadError> {
-        if block_size != N::to_usize() {
-            Err(PadError)?
+    /// Unpad given `data` whose trailing zeros were replaced by `fill`
+    pub fn unpad_filled(data: &[u8], fill: u8) -> Result<&[u8], UnpadError> {
+        no_panic! {
+            let range = raw::unpad_filled_range(data, N::USIZE, fill).map_err(|_| UnpadError)?;
+            Ok(&data[range])
+        }
+    }
+
+    /// Pad `msg` into the separate buffer `out`, drawing the random prefix from `rng`
+    ///
+    /// `msg` is never mutated, so it may live in read-only memory. `out` must be at least
+    /// [`padded_len(msg.len())`](TxPadding::padded_len) bytes long.
+    pub fn pad_into_with_rng<'a>(
+        rng: &mut (impl RngCore + CryptoRng),
+        msg: &[u8],
+        out: &'a mut [u8],
+    ) -> Result<&'a mut [u8], TxPadError> {
+        no_panic! {
+            raw::pad_from(rng, msg, out, N::USIZE)
+        }
+    }
+
+    /// Pad `msg` into the possibly uninitialized buffer `out`, drawing the random prefix from
+    /// `rng`
+    ///
+    /// Every byte of the padded message is written, so `out` does not need to be initialized
+    /// beforehand. Returns the initialized padded message.
+    #[cfg(feature = "uninit")]
+    pub fn pad_uninit_with_rng<'a>(
+        rng: &mut (impl RngCore + CryptoRng),
+        msg: &[u8],
+        out: &'a mut [MaybeUninit<u8>],
+    ) -> Result<&'a mut [u8], TxPadError> {
+        no_panic! {
+            raw::pad_uninit(rng, msg, out, N::USIZE)
+        }
+    }
+
+    /// Offset at which to place a message of length `msg_len` in `buf` before calling
+    /// [`finish_with_rng`](TxPadding::finish_with_rng)
+    ///
+    /// Writing the message there directly saves the move `pad_with_rng` performs to make room
+    /// for the prefix. Fails if `buf` is too small for the padded message.
+    ///
+    /// ```
+    /// # #[cfg(any(feature = "os-rng", feature = "thread-rng"))]
+    /// # {
+    /// use tx_padding::{TxPadding, Padding};
+    /// use tx_padding::consts::{U8};
+    ///
+    /// let mut buffer = [0; 16];
+    /// let offset = TxPadding::<U8>::reserve(&buffer, 4).unwrap();
+    /// buffer[offset..offset + 4].copy_from_slice(b"test");
+    /// let padded_msg = TxPadding::<U8>::finish(&mut buffer, 4).unwrap();
+    /// assert_eq!(TxPadding::<U8>::unpad(padded_msg).unwrap(), b"test");
+    /// # }
+    /// ```
+    pub fn reserve(buf: &[u8], msg_len: usize) -> Result<usize, TxPadError> {
+        no_panic! {
+            raw::reserve(buf.len(), msg_len, N::USIZE)
+        }
+    }
+
+    /// Pad the message of length `msg_len` placed at the offset given by
+    /// [`reserve`](TxPadding::reserve), drawing the random prefix from `rng`
+    pub fn finish_with_rng<'a>(
+        rng: &mut (impl RngCore + CryptoRng),
+        buf: &'a mut [u8],
+        msg_len: usize,
+    ) -> Result<&'a mut [u8], TxPadError> {
+        no_panic! {
+            raw::finish(rng, buf, msg_len, N::USIZE)
+        }
+    }
+
+    /// Unpad `buf` in place, overwriting the header and the random bytes with zeros
+    ///
+    /// Accepts exactly the same inputs as `Padding::unpad`. The random prefix is scrubbed so it
+    /// does not linger in a buffer which is reused afterwards. `buf` is left untouched on failure.
+    ///
+    /// ```
+    /// # #[cfg(any(feature = "os-rng", feature = "thread-rng"))]
+    /// # {
+    /// use tx_padding::{TxPadding, Padding};
+    /// use tx_padding::consts::{U8};
+    ///
+    /// let mut buffer = [0; 16];
+    /// buffer[..4].copy_from_slice(b"test");
+    /// TxPadding::<U8>::pad(&mut buffer, 4, 8).unwrap();
+    /// assert_eq!(TxPadding::<U8>::unpad_in_place(&mut buffer).unwrap(), b"test");
+    /// assert_eq!(&buffer[..5], &[0; 5]);
+    /// # }
+    /// ```
+    pub fn unpad_in_place(buf: &mut [u8]) -> Result<&mut [u8], UnpadError> {
+        no_panic! {
+            let range = raw::unpad_range(buf, N::USIZE).map_err(|_| UnpadError)?;
+            buf[..range.start].fill(0);
+            Ok(&mut buf[range])
+        }
+    }
+
+    /// Unpad given `data`, returning the message
+    ///
+    /// The same as `Padding::unpad`, which only delegates here, but without requiring a default
+    /// RNG, so it is available whichever RNG features are enabled.
+    ///
+    /// ```
+    /// use tx_padding::TxPadding;
+    /// use tx_padding::consts::{U8};
+    ///
+    /// let padded_msg = [0xf9, 0, 0, 0, 1, 2, 3, 4, 5, 0, 0, 0, 0, 0, 0, 0];
+    /// assert_eq!(TxPadding::<U8>::unpad(&padded_msg).unwrap(), &[1, 2, 3, 4, 5]);
+    /// ```
+    pub fn unpad(data: &[u8]) -> Result<&[u8], UnpadError> {
+        no_panic! {
+            raw::unpad(data, N::USIZE).map_err(|_| UnpadError)
+        }
+    }
+
+    /// Length of the message in a padded message of length `total_len`, given only its first
+    /// block
+    ///
+    /// Only the header byte is inspected, so the rest of the padded message is not validated. It
+    /// lets the exact output size be allocated before the whole message has been processed.
+    ///
+    /// ```
+    /// # #[cfg(any(feature = "os-rng", feature = "thread-rng"))]
+    /// # {
+    /// use tx_padding::{TxPadding, Padding};
+    /// use tx_padding::consts::{U8};
+    ///
+    /// let mut buffer = [0; 24];
+    /// buffer[..11].copy_from_slice(b"hello world");
+    /// let padded_msg = TxPadding::<U8>::pad(&mut buffer, 11, 8).unwrap();
+    /// assert_eq!(TxPadding::<U8>::peek_msg_len(&padded_msg[..8], 24).unwrap(), 11);
+    /// # }
+    /// ```
+    pub fn peek_msg_len(first_block: &[u8], total_len: usize) -> Result<usize, UnpadError> {
+        no_panic! {
+            raw::peek_msg_len(first_block, total_len, N::USIZE).map_err(|_| UnpadError)
         }
-        let block_size = N::to_usize();
-        let be = block_size * ((pos + 1) / block_size + 2);
-        if buf.len() < be {
-            Err(PadError)?
+    }
+
+    /// Position of the message in the padded `data`
+    ///
+    /// Accepts exactly the same inputs as `Padding::unpad`, but returns the bounds of the message
+    /// instead of borrowing it from `data`.
+    ///
+    /// ```
+    /// # #[cfg(any(feature = "os-rng", feature = "thread-rng"))]
+    /// # {
+    /// use tx_padding::{TxPadding, Padding};
+    /// use tx_padding::consts::{U8};
+    ///
+    /// let mut buffer = [0; 16];
+    /// buffer[..4].copy_from_slice(b"test");
+    /// TxPadding::<U8>::pad(&mut buffer, 4, 8).unwrap();
+    /// let range = TxPadding::<U8>::unpad_range(&buffer).unwrap();
+    /// assert_eq!(&buffer[range], b"test");
+    /// # }
+    /// ```
+    pub fn unpad_range(data: &[u8]) -> Result<Range<usize>, UnpadError> {
+        no_panic! {
+            raw::unpad_range(data, N::USIZE).map_err(|_| UnpadError)
+        }
+    }
+
+    /// Whether `data` is a well-formed padded message
+    ///
+    /// Accepts exactly the same inputs as `Padding::unpad`, without slicing out the message.
+    ///
+    /// ```
+    /// # #[cfg(any(feature = "os-rng", feature = "thread-rng"))]
+    /// # {
+    /// use tx_padding::{TxPadding, Padding};
+    /// use tx_padding::consts::{U8};
+    ///
+    /// let mut buffer = [0; 16];
+    /// buffer[..4].copy_from_slice(b"test");
+    /// let padded_msg = TxPadding::<U8>::pad(&mut buffer, 4, 8).unwrap();
+    /// assert!(TxPadding::<U8>::is_valid(&padded_msg));
+    /// assert!(!TxPadding::<U8>::is_valid(&padded_msg[..15]));
+    /// # }
+    /// ```
+    pub fn is_valid(data: &[u8]) -> bool {
+        no_panic! {
+            raw::is_valid(data, N::USIZE)
+        }
+    }
+
+    /// Unpad given `data` whose integrity is already established, e.g. by a MAC or an AEAD
+    ///
+    /// Only the header is checked against the length of `data`, so the message can be sliced
+    /// without panicking. The trailing zeros are not scanned, which saves a pass over the last
+    /// block, and a corrupted frame yields garbage instead of an error. Use `Padding::unpad` for
+    /// anything which has not been authenticated.
+    ///
+    /// ```
+    /// # #[cfg(any(feature = "os-rng", feature = "thread-rng"))]
+    /// # {
+    /// use tx_padding::{TxPadding, Padding};
+    /// use tx_padding::consts::{U8};
+    ///
+    /// let mut buffer = [0; 16];
+    /// buffer[..4].copy_from_slice(b"test");
+    /// let padded_msg = TxPadding::<U8>::pad(&mut buffer, 4, 8).unwrap();
+    /// assert_eq!(TxPadding::<U8>::unpad_trusted(&padded_msg).unwrap(), b"test");
+    /// assert!(TxPadding::<U8>::unpad_trusted(&padded_msg[..8]).is_err());
+    /// # }
+    /// ```
+    pub fn unpad_trusted(data: &[u8]) -> Result<&[u8], UnpadError> {
+        no_panic! {
+            raw::unpad_trusted(data, N::USIZE).map_err(|_| UnpadError)
+        }
+    }
+
+    /// Unpad given `data`, additionally requiring its length to be a multiple of the block size
+    ///
+    /// Every padded message is block aligned, but `Padding::unpad` only validates the prefix and
+    /// the trailing zeros, so it also accepts some truncated or extended inputs.
+    ///
+    /// ```
+    /// use tx_padding::TxPadding;
+    /// use tx_padding::consts::{U8};
+    ///
+    /// let data = [0xf8, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+    /// assert!(TxPadding::<U8>::unpad_strict(&data[..16]).is_ok());
+    /// assert!(TxPadding::<U8>::unpad_strict(&data).is_err());
+    /// ```
+    pub fn unpad_strict(data: &[u8]) -> Result<&[u8], UnpadError> {
+        no_panic! {
+            raw::unpad_strict(data, N::USIZE).map_err(|_| UnpadError)
         }
+    }
 
-        let pad_zero = block_size - 1;
-        let pad_len = ((-(pos as isize) - 2).rem_euclid(block_size as isize)) as usize + 2;
-        buf.copy_within(..pos, 1 + pad_len);
-        DefaultRng::default()
-            .try_fill_bytes(&mut buf[1..1 + pad_len])
-            .map_err(|_| PadError)?;
-        buf[0] = !((block_size - 1) as u8) | (pad_len - 2) as u8;
+    /// Unpad given `data` strictly like [`unpad_strict`](TxPadding::unpad_strict), reporting why
+    /// it is malformed on failure
+    #[cfg(feature = "diagnostics")]
+    pub fn unpad_strict_verbose(data: &[u8]) -> Result<&[u8], UnpadErrorKind> {
+        no_panic! {
+            raw::unpad_strict(data, N::USIZE)
+        }
+    }
 
-        // SAFETY: will use slice::fill after it stabilizes
-        unsafe {
-            core::ptr::write_bytes(buf[be - pad_zero..be].as_mut_ptr(), 0, pad_zero);
+    /// Unpad given `data`, reporting why it is malformed on failure
+    ///
+    /// Accepts exactly the same inputs as `Padding::unpad`.
+    ///
+    /// ```
+    /// use tx_padding::{TxPadding, UnpadErrorKind};
+    /// use tx_padding::consts::{U8};
+    ///
+    /// assert_eq!(TxPadding::<U8>::unpad_verbose(&[]), Err(UnpadErrorKind::EmptyInput));
+    /// assert_eq!(
+    ///     TxPadding::<U8>::unpad_verbose(&[0xf8, 0, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0]),
+    ///     Err(UnpadErrorKind::NonZeroTail { index: 9 })
+    /// );
+    /// ```
+    #[cfg(feature = "diagnostics")]
+    pub fn unpad_verbose(data: &[u8]) -> Result<&[u8], UnpadErrorKind> {
+        no_panic! {
+            raw::unpad(data, N::USIZE)
         }
+    }
+}
+
+impl<N, R> TxPadding<N, R>
+where
+    N: BlockSize,
+    R: RngCore + CryptoRng + Default,
+{
+    /// Pads message with length `pos` in the provided buffer, with random upper bits in the first
+    /// byte
+    pub fn pad_random_header(buf: &mut [u8], pos: usize) -> Result<&mut [u8], TxPadError> {
+        Self::pad_random_header_with_rng(&mut R::default(), buf, pos)
+    }
+
+    /// Pads message with length `pos` in the provided buffer already zero past the message
+    ///
+    /// ```
+    /// # #[cfg(any(feature = "os-rng", feature = "thread-rng"))]
+    /// # {
+    /// use tx_padding::{TxPadding, Padding};
+    /// use tx_padding::consts::{U8};
+    ///
+    /// let mut buffer = [0; 16];
+    /// buffer[..4].copy_from_slice(b"test");
+    /// let padded_msg = TxPadding::<U8>::pad_assume_zeroed(&mut buffer, 4).unwrap();
+    /// assert_eq!(TxPadding::<U8>::unpad(padded_msg).unwrap(), b"test");
+    /// # }
+    /// ```
+    pub fn pad_assume_zeroed(buf: &mut [u8], pos: usize) -> Result<&mut [u8], TxPadError> {
+        Self::pad_assume_zeroed_with_rng(&mut R::default(), buf, pos)
+    }
 
-        Ok(&mut buf[..be])
+    /// Pads message with length `pos` in the provided buffer, with the trailing zeros replaced by
+    /// `fill`
+    pub fn pad_filled(buf: &mut [u8], pos: usize, fill: u8) -> Result<&mut [u8], TxPadError> {
+        Self::pad_filled_with_rng(&mut R::default(), buf, pos, fill)
+    }
+
+    /// Pad `msg` into the separate buffer `out`
+    ///
+    /// ```
+    /// # #[cfg(any(feature = "os-rng", feature = "thread-rng"))]
+    /// # {
+    /// use tx_padding::{TxPadding, Padding};
+    /// use tx_padding::consts::{U8};
+    ///
+    /// const MSG: &[u8] = b"test";
+    /// let mut out = [0; 16];
+    /// let padded_msg = TxPadding::<U8>::pad_into(MSG, &mut out).unwrap();
+    /// assert_eq!(TxPadding::<U8>::unpad(padded_msg).unwrap(), MSG);
+    /// # }
+    /// ```
+    pub fn pad_into<'a>(msg: &[u8], out: &'a mut [u8]) -> Result<&'a mut [u8], TxPadError> {
+        Self::pad_into_with_rng(&mut R::default(), msg, out)
+    }
+
+    /// Pad `msg` into the possibly uninitialized buffer `out`
+    ///
+    /// ```
+    /// use core::mem::MaybeUninit;
+    /// use tx_padding::{TxPadding, Padding};
+    /// use tx_padding::consts::{U8};
+    ///
+    /// let mut out = [MaybeUninit::uninit(); 16];
+    /// let padded_msg = TxPadding::<U8>::pad_uninit(b"test", &mut out).unwrap();
+    /// assert_eq!(TxPadding::<U8>::unpad(padded_msg).unwrap(), b"test");
+    /// ```
+    #[cfg(feature = "uninit")]
+    pub fn pad_uninit<'a>(
+        msg: &[u8],
+        out: &'a mut [MaybeUninit<u8>],
+    ) -> Result<&'a mut [u8], TxPadError> {
+        Self::pad_uninit_with_rng(&mut R::default(), msg, out)
+    }
+
+    /// Pad the message of length `msg_len` placed at the offset given by
+    /// [`reserve`](TxPadding::reserve)
+    pub fn finish(buf: &mut [u8], msg_len: usize) -> Result<&mut [u8], TxPadError> {
+        Self::finish_with_rng(&mut R::default(), buf, msg_len)
+    }
+}
+
+impl<N, R> Padding for TxPadding<N, R>
+where
+    N: BlockSize,
+    R: RngCore + CryptoRng + Default,
+{
+    fn pad_block(_block: &mut [u8], _pos: usize) -> Result<(), PadError> {
+        Err(PadError)
+    }
+
+    fn unpad(data: &[u8]) -> Result<&[u8], UnpadError> {
+        Self::unpad(data)
+    }
+
+    fn pad(buf: &mut [u8], pos: usize, block_size: usize) -> Result<&mut [u8], PadError> {
+        if block_size != N::to_usize() {
+            Err(TxPadError::BlockSizeMismatch {
+                expected: N::to_usize(),
+                got: block_size,
+            })?
+        }
+        Ok(Self::pad_with_rng(&mut R::default(), buf, pos)?)
     }
 }
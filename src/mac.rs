@@ -0,0 +1,111 @@
+use core::convert::Infallible;
+use core::marker::PhantomData;
+use core::ops::Range;
+
+use digest::{Mac, OutputSizeUser};
+
+use crate::error::UnpadErrorKind;
+use crate::{raw, BlockSize, CryptoRng, DefaultRng, RngCore, TxPadError, UnpadError};
+
+/// The sibling of `TxPadding` which stores a truncated MAC of the padded message in its padding
+/// zeros
+///
+/// The first bytes of the `N - 1` trailing zeros are replaced by the leftmost bytes of the tag
+/// computed by `M` over everything preceding them, i.e. the header, the random bytes and the
+/// message. The tag is truncated to the length of the MAC output or `N - 1` bytes, whichever is
+/// shorter, so small block sizes give little protection: prefer blocks of at least 16 bytes. The
+/// padded length is the same as for `TxPadding`.
+///
+/// The MAC is passed already keyed, and the tag is verified in constant time. Since unpadding
+/// needs the key, this does not implement `Padding`.
+///
+/// ```
+/// use hmac::{Hmac, Mac};
+/// use sha2::Sha256;
+/// use tx_padding::TxPaddingMac;
+/// use tx_padding::consts::{U16};
+///
+/// type HmacSha256 = Hmac<Sha256>;
+/// let mac = || HmacSha256::new_from_slice(b"secret key").unwrap();
+///
+/// let mut buffer = [0; 32];
+/// buffer[..4].copy_from_slice(b"test");
+/// let padded_msg = TxPaddingMac::<U16, HmacSha256>::pad(mac(), &mut buffer, 4).unwrap();
+/// assert_eq!(TxPaddingMac::<U16, HmacSha256>::unpad(mac(), padded_msg).unwrap(), b"test");
+/// padded_msg[14] ^= 1;
+/// assert!(TxPaddingMac::<U16, HmacSha256>::unpad(mac(), padded_msg).is_err());
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub enum TxPaddingMac<N, M, R = DefaultRng> {
+    _Phantom(Infallible, PhantomData<(N, M)>, PhantomData<fn() -> R>),
+}
+
+impl<N: BlockSize, M: Mac, R> TxPaddingMac<N, M, R> {
+    /// Length of the tag stored in the padding
+    pub fn tag_len() -> usize {
+        (N::USIZE - 1).min(<M as OutputSizeUser>::output_size())
+    }
+
+    /// Length of the padded output for a message of length `msg_len`
+    pub const fn padded_len(msg_len: usize) -> usize {
+        raw::padded_len(N::USIZE, msg_len)
+    }
+
+    /// Pads message with length `pos` in the provided buffer, authenticating it with `mac` and
+    /// drawing the random prefix from `rng`
+    pub fn pad_with_rng<'a>(
+        rng: &mut (impl RngCore + CryptoRng),
+        mut mac: M,
+        buf: &'a mut [u8],
+        pos: usize,
+    ) -> Result<&'a mut [u8], TxPadError> {
+        let padded_msg = raw::pad(rng, buf, pos, N::USIZE)?;
+        let (covered, zeros) = padded_msg.split_at_mut(padded_msg.len() - (N::USIZE - 1));
+        mac.update(covered);
+        let tag = mac.finalize().into_bytes();
+        let tag_len = Self::tag_len();
+        zeros[..tag_len].copy_from_slice(&tag[..tag_len]);
+        Ok(padded_msg)
+    }
+
+    /// Unpad given `data` after verifying its tag with `mac`
+    pub fn unpad(mac: M, data: &[u8]) -> Result<&[u8], UnpadError> {
+        let range = Self::unpad_range(mac, data).map_err(|_| UnpadError)?;
+        Ok(&data[range])
+    }
+
+    /// Unpad given `data` after verifying its tag with `mac`, reporting why it is malformed on
+    /// failure
+    #[cfg(feature = "diagnostics")]
+    pub fn unpad_verbose(mac: M, data: &[u8]) -> Result<&[u8], UnpadErrorKind> {
+        Self::unpad_range(mac, data).map(|range| &data[range])
+    }
+
+    fn unpad_range(mut mac: M, data: &[u8]) -> Result<Range<usize>, UnpadErrorKind> {
+        if data.is_empty() {
+            Err(UnpadErrorKind::EmptyInput)?
+        }
+        let l = data.len();
+        let pad_zero = N::USIZE - 1;
+        let pad_len = raw::declared_pad_len(l, data[0], N::USIZE)?;
+        let tag_end = l - pad_zero + Self::tag_len();
+        raw::check_zeros(data, tag_end..l, 0)?;
+
+        mac.update(&data[..l - pad_zero]);
+        mac.verify_truncated_left(&data[l - pad_zero..tag_end])
+            .map_err(|_| UnpadErrorKind::InvalidTag)?;
+        Ok(1 + pad_len..l - pad_zero)
+    }
+}
+
+impl<N, M, R> TxPaddingMac<N, M, R>
+where
+    N: BlockSize,
+    M: Mac,
+    R: RngCore + CryptoRng + Default,
+{
+    /// Pads message with length `pos` in the provided buffer, authenticating it with `mac`
+    pub fn pad(mac: M, buf: &mut [u8], pos: usize) -> Result<&mut [u8], TxPadError> {
+        Self::pad_with_rng(&mut R::default(), mac, buf, pos)
+    }
+}
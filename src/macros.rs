@@ -0,0 +1,32 @@
+//! Macros for the common setup around the padding methods
+
+/// A zeroed `[u8; _]` array long enough to pad a message of `msg_len` bytes with block size `N`
+///
+/// `padded_buf!(N, msg_len)` expands to `[0u8; TxPadding::<N>::padded_len(msg_len)]`, so the
+/// length is computed at compile time and `msg_len` must be a constant expression.
+///
+/// ```
+/// # #[cfg(any(feature = "os-rng", feature = "thread-rng"))]
+/// # {
+/// use tx_padding::{padded_buf, TxPadding, Padding};
+/// use tx_padding::consts::{U16};
+///
+/// let mut buffer = padded_buf!(U16, 11);
+/// buffer[..11].copy_from_slice(b"hello world");
+/// let padded_msg = TxPadding::<U16>::pad(&mut buffer, 11, 16).unwrap();
+/// assert_eq!(TxPadding::<U16>::unpad(padded_msg).unwrap(), b"hello world");
+/// # }
+/// ```
+/// ```compile_fail
+/// use tx_padding::padded_buf;
+/// use tx_padding::consts::{U16};
+///
+/// let msg_len = 11;
+/// let buffer = padded_buf!(U16, msg_len);
+/// ```
+#[macro_export]
+macro_rules! padded_buf {
+    ($n:ty, $msg_len:expr) => {
+        [0u8; $crate::TxPadding::<$n>::padded_len($msg_len)]
+    };
+}
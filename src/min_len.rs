@@ -0,0 +1,96 @@
+//! Padding to a minimum output length
+
+use crate::{
+    raw, BlockSize, CryptoRng, RngCore, TxPadError, TxPadding, TxPaddingConst, UnpadError,
+};
+
+impl<N: BlockSize, R> TxPadding<N, R> {
+    /// Length of the output of [`pad_min`](TxPadding::pad_min) for a message of length `msg_len`
+    pub const fn padded_min_len(msg_len: usize, min_len: usize) -> usize {
+        raw::padded_min_len(N::USIZE, msg_len, min_len)
+    }
+
+    /// Pads message with length `pos` in the provided buffer to at least `min_len` bytes, drawing
+    /// the random prefix from `rng`
+    ///
+    /// Since the header cannot describe the extra bytes, the message is prefixed with its length
+    /// as a 64-bit big-endian integer and the extra bytes are absorbed into the trailing zeros.
+    /// Such output must be unpadded with [`unpad_min`](TxPadding::unpad_min).
+    pub fn pad_min_with_rng<'a>(
+        rng: &mut (impl RngCore + CryptoRng),
+        buf: &'a mut [u8],
+        pos: usize,
+        min_len: usize,
+    ) -> Result<&'a mut [u8], TxPadError> {
+        raw::pad_min(rng, buf, pos, min_len, N::USIZE)
+    }
+
+    /// Unpad given `data` padded by [`pad_min`](TxPadding::pad_min)
+    pub fn unpad_min(data: &[u8]) -> Result<&[u8], UnpadError> {
+        raw::unpad_length_prefixed(data, N::USIZE).map_err(|_| UnpadError)
+    }
+}
+
+impl<N, R> TxPadding<N, R>
+where
+    N: BlockSize,
+    R: RngCore + CryptoRng + Default,
+{
+    /// Pads message with length `pos` in the provided buffer to at least `min_len` bytes
+    ///
+    /// ```
+    /// # #[cfg(any(feature = "os-rng", feature = "thread-rng"))]
+    /// # {
+    /// use tx_padding::TxPadding;
+    /// use tx_padding::consts::{U16};
+    ///
+    /// let mut buffer = [0; 256];
+    /// buffer[..3].copy_from_slice(b"ack");
+    /// let padded_msg = TxPadding::<U16>::pad_min(&mut buffer, 3, 256).unwrap();
+    /// assert_eq!(padded_msg.len(), 256);
+    /// assert_eq!(TxPadding::<U16>::unpad_min(padded_msg).unwrap(), b"ack");
+    /// # }
+    /// ```
+    pub fn pad_min(buf: &mut [u8], pos: usize, min_len: usize) -> Result<&mut [u8], TxPadError> {
+        Self::pad_min_with_rng(&mut R::default(), buf, pos, min_len)
+    }
+}
+
+impl<const N: usize, R> TxPaddingConst<N, R> {
+    /// Length of the output of [`pad_min`](TxPaddingConst::pad_min) for a message of length
+    /// `msg_len`
+    pub const fn padded_min_len(msg_len: usize, min_len: usize) -> usize {
+        let () = Self::VALID_BLOCK_SIZE;
+        raw::padded_min_len(N, msg_len, min_len)
+    }
+
+    /// Pads message with length `pos` in the provided buffer to at least `min_len` bytes, drawing
+    /// the random prefix from `rng`
+    ///
+    /// See [`TxPadding::pad_min_with_rng`].
+    pub fn pad_min_with_rng<'a>(
+        rng: &mut (impl RngCore + CryptoRng),
+        buf: &'a mut [u8],
+        pos: usize,
+        min_len: usize,
+    ) -> Result<&'a mut [u8], TxPadError> {
+        let () = Self::VALID_BLOCK_SIZE;
+        raw::pad_min(rng, buf, pos, min_len, N)
+    }
+
+    /// Unpad given `data` padded by [`pad_min`](TxPaddingConst::pad_min)
+    pub fn unpad_min(data: &[u8]) -> Result<&[u8], UnpadError> {
+        let () = Self::VALID_BLOCK_SIZE;
+        raw::unpad_length_prefixed(data, N).map_err(|_| UnpadError)
+    }
+}
+
+impl<const N: usize, R> TxPaddingConst<N, R>
+where
+    R: RngCore + CryptoRng + Default,
+{
+    /// Pads message with length `pos` in the provided buffer to at least `min_len` bytes
+    pub fn pad_min(buf: &mut [u8], pos: usize, min_len: usize) -> Result<&mut [u8], TxPadError> {
+        Self::pad_min_with_rng(&mut R::default(), buf, pos, min_len)
+    }
+}
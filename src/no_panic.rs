@@ -0,0 +1,46 @@
+//! Link-time verification that functions cannot panic
+//!
+//! With the `no-panic` feature in an optimized build, the bodies wrapped in `no_panic!` hold a
+//! guard whose destructor calls a function which does not exist. The destructor only runs, and
+//! the call is only kept by the optimizer, if the body can unwind, so linking any binary which
+//! uses such a body fails unless every panic in it has been optimized out. Debug builds keep
+//! their panicking paths, so there the macro expands to the body alone.
+//!
+//! The bounds checks are only elided once the block size is known, so the functions of `raw`
+//! reachable from a wrapped body are `#[inline(always)]`. `TxPaddingDyn` takes the block size at
+//! runtime and is not covered.
+
+/// Wrap a function body so that linking fails if it can panic
+macro_rules! no_panic {
+    ($($body:tt)*) => {{
+        #[cfg(all(feature = "no-panic", not(debug_assertions)))]
+        let guard = $crate::no_panic::Guard;
+        let ret = $crate::no_panic::call(move || { $($body)* });
+        #[cfg(all(feature = "no-panic", not(debug_assertions)))]
+        core::mem::forget(guard);
+        ret
+    }};
+}
+
+/// Run `f`, which lets the macro take the body as a closure consuming its captures
+#[inline(always)]
+pub(crate) fn call<T>(f: impl FnOnce() -> T) -> T {
+    f()
+}
+
+#[cfg(all(feature = "no-panic", not(debug_assertions)))]
+pub(crate) struct Guard;
+
+#[cfg(all(feature = "no-panic", not(debug_assertions)))]
+impl Drop for Guard {
+    #[inline(always)]
+    fn drop(&mut self) {
+        extern "C" {
+            #[link_name = "\n\nERROR[tx-padding]: detected a possible panic in a function wrapped \
+                           in no_panic!\n\n"]
+            fn trigger() -> !;
+        }
+        // SAFETY: never called, since linking fails if the call survives optimization
+        unsafe { trigger() }
+    }
+}
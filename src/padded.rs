@@ -0,0 +1,78 @@
+//! Structured view of a validated padded message
+
+use core::convert::TryFrom;
+use core::marker::PhantomData;
+
+use crate::{raw, BlockSize, UnpadError};
+
+/// A padded message validated like `Padding::unpad`, giving access to its parts
+///
+/// The padded message is the [`header`](Padded::header) byte, followed by the
+/// [`random_prefix`](Padded::random_prefix), the [`message`](Padded::message) and
+/// [`zero_tail_len`](Padded::zero_tail_len) zeros.
+///
+/// ```
+/// # #[cfg(any(feature = "os-rng", feature = "thread-rng"))]
+/// # {
+/// use core::convert::TryFrom;
+/// use tx_padding::{Padded, TxPadding, Padding};
+/// use tx_padding::consts::{U8};
+///
+/// let mut buffer = [0; 16];
+/// buffer[..4].copy_from_slice(b"test");
+/// let padded_msg = TxPadding::<U8>::pad(&mut buffer, 4, 8).unwrap();
+/// let padded = Padded::<U8>::try_from(&padded_msg[..]).unwrap();
+/// assert_eq!(padded.random_prefix().len(), 4);
+/// assert_eq!(padded.message(), b"test");
+/// assert_eq!(padded.zero_tail_len(), 7);
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(transparent)]
+pub struct Padded<'a, N> {
+    data: &'a [u8],
+    _marker: PhantomData<N>,
+}
+
+impl<'a, N: BlockSize> Padded<'a, N> {
+    /// The header byte storing the number of random bytes
+    pub fn header(&self) -> u8 {
+        self.data[0]
+    }
+
+    /// The random bytes following the header
+    pub fn random_prefix(&self) -> &'a [u8] {
+        &self.data[1..self.msg_start()]
+    }
+
+    /// The message
+    pub fn message(&self) -> &'a [u8] {
+        &self.data[self.msg_start()..self.data.len() - self.zero_tail_len()]
+    }
+
+    /// Number of zeros following the message
+    pub fn zero_tail_len(&self) -> usize {
+        N::USIZE - 1
+    }
+
+    /// The whole padded message
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.data
+    }
+
+    fn msg_start(&self) -> usize {
+        1 + (self.header() & ((N::USIZE - 1) as u8)) as usize + 2
+    }
+}
+
+impl<'a, N: BlockSize> TryFrom<&'a [u8]> for Padded<'a, N> {
+    type Error = UnpadError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, UnpadError> {
+        raw::unpad_range(data, N::USIZE).map_err(|_| UnpadError)?;
+        Ok(Padded {
+            data,
+            _marker: PhantomData,
+        })
+    }
+}
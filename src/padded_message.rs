@@ -0,0 +1,143 @@
+//! Owned padded message with serde support
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+use core::fmt;
+use core::marker::PhantomData;
+
+use serde::de::{self, Deserialize, Deserializer, SeqAccess, Visitor};
+use serde::ser::{Serialize, Serializer};
+
+use crate::{raw, BlockSize, CryptoRng, RngCore, TxPadError, UnpadError};
+
+/// An owned padded message, validated like `Padding::unpad`
+///
+/// It serializes as a hex string in human-readable formats, e.g. JSON or TOML, and as bytes
+/// otherwise. Deserializing rejects malformed padding, so a padded message embedded in a
+/// configuration or capture file is checked when the file is loaded.
+///
+/// ```
+/// use tx_padding::PaddedMessage;
+/// use tx_padding::consts::{U8};
+///
+/// let padded = PaddedMessage::<U8>::pad_with_rng(&mut rand::rngs::OsRng, b"test").unwrap();
+/// let json = serde_json::to_string(&padded).unwrap();
+/// assert_eq!(json.len(), 2 + 2 * 16);
+/// let padded: PaddedMessage<U8> = serde_json::from_str(&json).unwrap();
+/// assert_eq!(padded.message(), b"test");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct PaddedMessage<N> {
+    data: Vec<u8>,
+    _marker: PhantomData<N>,
+}
+
+impl<N: BlockSize> PaddedMessage<N> {
+    /// Pad `msg`, drawing the random prefix from `rng`
+    pub fn pad_with_rng(
+        rng: &mut (impl RngCore + CryptoRng),
+        msg: &[u8],
+    ) -> Result<Self, TxPadError> {
+        let len = raw::checked_padded_len(N::USIZE, msg.len()).ok_or(TxPadError::MessageTooLong)?;
+        let mut data = alloc::vec![0; len];
+        data[..msg.len()].copy_from_slice(msg);
+        raw::pad(rng, &mut data, msg.len(), N::USIZE)?;
+        Ok(PaddedMessage {
+            data,
+            _marker: PhantomData,
+        })
+    }
+
+    /// The message
+    pub fn message(&self) -> &[u8] {
+        let range = raw::unpad_range(&self.data, N::USIZE).expect("validated on construction");
+        &self.data[range]
+    }
+
+    /// The whole padded message
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Consume the padded message, returning the underlying vector
+    pub fn into_vec(self) -> Vec<u8> {
+        self.data
+    }
+}
+
+impl<N: BlockSize> TryFrom<Vec<u8>> for PaddedMessage<N> {
+    type Error = UnpadError;
+
+    fn try_from(data: Vec<u8>) -> Result<Self, UnpadError> {
+        raw::unpad_range(&data, N::USIZE).map_err(|_| UnpadError)?;
+        Ok(PaddedMessage {
+            data,
+            _marker: PhantomData,
+        })
+    }
+}
+
+impl<N> AsRef<[u8]> for PaddedMessage<N> {
+    fn as_ref(&self) -> &[u8] {
+        &self.data
+    }
+}
+
+impl<N> From<PaddedMessage<N>> for Vec<u8> {
+    fn from(padded: PaddedMessage<N>) -> Vec<u8> {
+        padded.data
+    }
+}
+
+impl<N> Serialize for PaddedMessage<N> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.serialize_str(&hex::encode(&self.data))
+        } else {
+            serializer.serialize_bytes(&self.data)
+        }
+    }
+}
+
+impl<'de, N: BlockSize> Deserialize<'de> for PaddedMessage<N> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let data = if deserializer.is_human_readable() {
+            hex::decode(String::deserialize(deserializer)?).map_err(de::Error::custom)?
+        } else {
+            deserializer.deserialize_byte_buf(BytesVisitor)?
+        };
+        raw::unpad_range(&data, N::USIZE).map_err(de::Error::custom)?;
+        Ok(PaddedMessage {
+            data,
+            _marker: PhantomData,
+        })
+    }
+}
+
+struct BytesVisitor;
+
+impl<'de> Visitor<'de> for BytesVisitor {
+    type Value = Vec<u8>;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a padded message")
+    }
+
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Vec<u8>, E> {
+        Ok(v.to_vec())
+    }
+
+    fn visit_byte_buf<E: de::Error>(self, v: Vec<u8>) -> Result<Vec<u8>, E> {
+        Ok(v)
+    }
+
+    // Formats without a native byte string, e.g. encoding bytes as a sequence of integers
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Vec<u8>, A::Error> {
+        let mut data = Vec::with_capacity(seq.size_hint().unwrap_or(0).min(4096));
+        while let Some(b) = seq.next_element()? {
+            data.push(b);
+        }
+        Ok(data)
+    }
+}
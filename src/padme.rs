@@ -0,0 +1,106 @@
+use core::convert::Infallible;
+use core::marker::PhantomData;
+
+use crate::raw::{self, Filler};
+use crate::{BlockSize, CryptoRng, DefaultRng, PadError, Padding, RngCore, TxPadError, UnpadError};
+
+/// Length a message of length `len` is padded to by Padmé
+///
+/// The length is rounded up so that only the `floor(log2(floor(log2(len)))) + 1` most
+/// significant bits may be non-zero, which leaks `O(log log len)` bits of the length with at
+/// most 12% overhead.
+const fn padme_len(len: usize) -> usize {
+    if len < 2 {
+        return len;
+    }
+    let e = usize::BITS - 1 - len.leading_zeros();
+    let s = u32::BITS - e.leading_zeros();
+    let mask = (1 << (e - s)) - 1;
+    (len + mask) & !mask
+}
+
+/// Padmé length-hiding padding layered on top of `TxPadding`
+///
+/// Padmé, from "Reducing Metadata Leakage from Encrypted Files and Communication with PURBs",
+/// rounds lengths up to a small set of sizes which grows logarithmically. The message is
+/// prefixed with its length as a 64-bit big-endian integer and followed by random filler, and
+/// the result is padded with `TxPadding<N>` so that the overall length is the Padmé length of
+/// the plain `TxPadding` output rounded up to a multiple of `N`.
+///
+/// ```
+/// # #[cfg(any(feature = "os-rng", feature = "thread-rng"))]
+/// # {
+/// use tx_padding::{Padme, Padding};
+/// use tx_padding::consts::{U16};
+///
+/// let mut buffer = [0; 1200];
+/// buffer[..1000].copy_from_slice(&[0x5a; 1000]);
+/// let padded_msg = Padme::<U16>::pad(&mut buffer, 1000, 16).unwrap();
+/// assert_eq!(padded_msg.len(), 1088);
+/// assert_eq!(Padme::<U16>::unpad(&padded_msg).unwrap(), &[0x5a; 1000][..]);
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub enum Padme<N, R = DefaultRng> {
+    _Phantom(Infallible, PhantomData<fn() -> (N, R)>),
+}
+
+impl<N: BlockSize, R> Padme<N, R> {
+    /// Length of the padded output for a message of length `msg_len`
+    ///
+    /// This is also the minimal length of the buffer passed to the padding methods.
+    pub const fn padded_len(msg_len: usize) -> usize {
+        let base = raw::padded_len(N::USIZE, raw::LEN_FIELD + msg_len);
+        (padme_len(base) + N::USIZE - 1) / N::USIZE * N::USIZE
+    }
+
+    /// Pads message with length `pos` in the provided buffer, drawing the random bytes from `rng`
+    pub fn pad_with_rng<'a>(
+        rng: &mut (impl RngCore + CryptoRng),
+        buf: &'a mut [u8],
+        pos: usize,
+    ) -> Result<&'a mut [u8], TxPadError> {
+        if pos > isize::MAX as usize - raw::LEN_FIELD {
+            Err(TxPadError::MessageTooLong)?
+        }
+        raw::pad_length_prefixed(
+            rng,
+            buf,
+            pos,
+            Self::padded_len(pos),
+            N::USIZE,
+            Filler::Random,
+        )
+    }
+
+    /// Unpad given `data`, returning the message
+    ///
+    /// `Padding::unpad` delegates here, but this does not require a default RNG.
+    pub fn unpad(data: &[u8]) -> Result<&[u8], UnpadError> {
+        raw::unpad_length_prefixed(data, N::USIZE).map_err(|_| UnpadError)
+    }
+}
+
+impl<N, R> Padding for Padme<N, R>
+where
+    N: BlockSize,
+    R: RngCore + CryptoRng + Default,
+{
+    fn pad_block(_block: &mut [u8], _pos: usize) -> Result<(), PadError> {
+        Err(PadError)
+    }
+
+    fn unpad(data: &[u8]) -> Result<&[u8], UnpadError> {
+        Self::unpad(data)
+    }
+
+    fn pad(buf: &mut [u8], pos: usize, block_size: usize) -> Result<&mut [u8], PadError> {
+        if block_size != N::USIZE {
+            Err(TxPadError::BlockSizeMismatch {
+                expected: N::USIZE,
+                got: block_size,
+            })?
+        }
+        Ok(Self::pad_with_rng(&mut R::default(), buf, pos)?)
+    }
+}
@@ -0,0 +1,53 @@
+//! Padding and unpadding many messages in parallel with `rayon`
+
+use std::vec::Vec;
+
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
+
+use crate::batch::PooledRng;
+use crate::{raw, BlockSize, CryptoRng, RngCore, TxPadError, TxPadding, UnpadError};
+
+impl<N, R> TxPadding<N, R>
+where
+    N: BlockSize,
+    R: RngCore + CryptoRng + Default,
+{
+    /// Pad every message of `frames` into the buffer paired with it, in parallel
+    ///
+    /// Each worker draws its random prefixes from its own instance of `R`, requesting them in
+    /// chunks like [`pad_batch`](TxPadding::pad_batch). On error, an arbitrary subset of the
+    /// frames has been padded.
+    ///
+    /// ```
+    /// use tx_padding::TxPadding;
+    /// use tx_padding::consts::{U8};
+    ///
+    /// let msgs = [&b"ping"[..], b"pong"];
+    /// let mut out = [[0; 16]; 2];
+    /// let frames: Vec<_> = msgs.iter().copied().zip(out.iter_mut().map(|o| &mut o[..])).collect();
+    /// TxPadding::<U8>::par_pad_batch(frames).unwrap();
+    /// let unpadded = TxPadding::<U8>::par_unpad_batch(out.iter().map(|o| &o[..]).collect::<Vec<_>>());
+    /// assert_eq!(unpadded.unwrap(), msgs);
+    /// ```
+    pub fn par_pad_batch<'m, 'o>(
+        frames: impl IntoParallelIterator<Item = (&'m [u8], &'o mut [u8])>,
+    ) -> Result<(), TxPadError> {
+        frames.into_par_iter().try_for_each_init(
+            || PooledRng::new(R::default()),
+            |rng, (msg, out)| raw::pad_from(rng, msg, out, N::USIZE).map(|_| ()),
+        )
+    }
+}
+
+impl<N: BlockSize, R> TxPadding<N, R> {
+    /// Unpad every padded message of `data` in parallel, keeping their order
+    ///
+    /// Fails if any of them is malformed.
+    pub fn par_unpad_batch<'a>(
+        data: impl IntoParallelIterator<Item = &'a [u8]>,
+    ) -> Result<Vec<&'a [u8]>, UnpadError> {
+        data.into_par_iter()
+            .map(|d| raw::unpad(d, N::USIZE).map_err(|_| UnpadError))
+            .collect()
+    }
+}
@@ -0,0 +1,152 @@
+//! Generators of messages and padded frames, for property testing protocols embedding them
+//!
+//! With the `arbitrary` feature, [`SizedMessage`] and [`PaddedFrame`] implement
+//! `arbitrary::Arbitrary`, e.g. for fuzz targets. With the `proptest` feature, they implement
+//! `proptest::arbitrary::Arbitrary`, so `any::<PaddedFrame<U16>>()` is a strategy for padded
+//! frames.
+
+use alloc::vec;
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+use core::marker::PhantomData;
+
+use crate::{
+    raw, BlockSize, CryptoRng, InsecureRng, Padded, ReplayRng, RngCore, TxPadError, TxPadding,
+};
+
+/// A message along with a block size supported by the scheme
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SizedMessage {
+    /// A power of 2 between 2 and 256
+    pub block_size: usize,
+    /// The message
+    pub msg: Vec<u8>,
+}
+
+/// An owned padded message, along with the message it holds
+///
+/// ```
+/// use tx_padding::consts::U8;
+/// use tx_padding::PaddedFrame;
+///
+/// let frame = PaddedFrame::<U8>::new(b"test").unwrap();
+/// assert_eq!(frame.as_bytes().len(), 16);
+/// assert_eq!(frame.padded().message(), b"test");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PaddedFrame<N> {
+    msg: Vec<u8>,
+    bytes: Vec<u8>,
+    _marker: PhantomData<N>,
+}
+
+impl<N: BlockSize> PaddedFrame<N> {
+    /// Pad `msg`, drawing the random prefix from `rng`
+    pub fn new_with_rng(
+        rng: &mut (impl RngCore + CryptoRng),
+        msg: &[u8],
+    ) -> Result<Self, TxPadError> {
+        let padded_len =
+            raw::checked_padded_len(N::USIZE, msg.len()).ok_or(TxPadError::MessageTooLong)?;
+        let mut bytes = vec![0; padded_len];
+        bytes[..msg.len()].copy_from_slice(msg);
+        TxPadding::<N>::pad_with_rng(rng, &mut bytes, msg.len())?;
+        Ok(PaddedFrame {
+            msg: msg.to_vec(),
+            bytes,
+            _marker: PhantomData,
+        })
+    }
+
+    /// Pad `msg`, with `random` as the random prefix
+    ///
+    /// `random` must hold at least `N + 1` bytes, of which only the required ones are used.
+    fn with_random(random: &[u8], msg: &[u8]) -> Result<Self, TxPadError> {
+        let pad_len = raw::pad_len(N::USIZE, msg.len());
+        let random = random.get(..pad_len).ok_or(TxPadError::RngFailure)?;
+        Self::new_with_rng(&mut InsecureRng(ReplayRng::new(random)), msg)
+    }
+
+    /// The message
+    pub fn message(&self) -> &[u8] {
+        &self.msg
+    }
+
+    /// The padded message
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// Structured view of the padded message
+    pub fn padded(&self) -> Padded<'_, N> {
+        Padded::try_from(&self.bytes[..]).unwrap()
+    }
+}
+
+#[cfg(any(
+    feature = "os-rng",
+    feature = "thread-rng",
+    feature = "custom-rng",
+    feature = "entropy-source"
+))]
+impl<N: BlockSize> PaddedFrame<N> {
+    /// Pad `msg`
+    pub fn new(msg: &[u8]) -> Result<Self, TxPadError> {
+        Self::new_with_rng(&mut crate::DefaultRng::default(), msg)
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for SizedMessage {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(SizedMessage {
+            block_size: 1 << u.int_in_range(1..=8)?,
+            msg: u.arbitrary()?,
+        })
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+impl<'a, N: BlockSize> arbitrary::Arbitrary<'a> for PaddedFrame<N> {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        let msg: Vec<u8> = u.arbitrary()?;
+        let random = (0..=N::USIZE)
+            .map(|_| u.arbitrary())
+            .collect::<arbitrary::Result<Vec<u8>>>()?;
+        Self::with_random(&random, &msg).map_err(|_| arbitrary::Error::IncorrectFormat)
+    }
+}
+
+#[cfg(feature = "proptest")]
+impl proptest::arbitrary::Arbitrary for SizedMessage {
+    type Parameters = ();
+    type Strategy = proptest::strategy::BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        use proptest::prelude::*;
+
+        (1..=8u32, proptest::collection::vec(any::<u8>(), 0..1024))
+            .prop_map(|(shift, msg)| SizedMessage {
+                block_size: 1 << shift,
+                msg,
+            })
+            .boxed()
+    }
+}
+
+#[cfg(feature = "proptest")]
+impl<N: BlockSize + core::fmt::Debug + 'static> proptest::arbitrary::Arbitrary for PaddedFrame<N> {
+    type Parameters = ();
+    type Strategy = proptest::strategy::BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        use proptest::prelude::*;
+
+        (
+            proptest::collection::vec(any::<u8>(), N::USIZE + 1),
+            proptest::collection::vec(any::<u8>(), 0..1024),
+        )
+            .prop_map(|(random, msg)| Self::with_random(&random, &msg).unwrap())
+            .boxed()
+    }
+}
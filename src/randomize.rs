@@ -0,0 +1,97 @@
+use core::convert::Infallible;
+use core::marker::PhantomData;
+
+use crate::{BlockSize, CryptoRng, DefaultRng, PadError, Padding, RngCore, TxPadError, UnpadError};
+
+/// Padding prepending a block of random bytes to the output of `P`
+///
+/// The message is padded with `P` as usual, so the padding itself stays wire-compatible with
+/// `P`, e.g. PKCS#7, and the result is preceded by one block of `N` random bytes. Like the random
+/// prefix of `TxPadding`, it makes the encryption of the same message differ every time even
+/// under a deterministic mode. Unpadding skips the first block and unpads the rest with `P`.
+///
+/// ```
+/// # #[cfg(any(feature = "os-rng", feature = "thread-rng"))]
+/// # {
+/// use tx_padding::{Randomize, Padding};
+/// use tx_padding::consts::{U8};
+/// use block_padding::Pkcs7;
+///
+/// let mut buffer = [0; 24];
+/// buffer[..4].copy_from_slice(b"test");
+/// let padded_msg = Randomize::<Pkcs7, U8>::pad(&mut buffer, 4, 8).unwrap();
+/// assert_eq!(padded_msg.len(), 16);
+/// assert_eq!(&padded_msg[8..], b"test\x04\x04\x04\x04");
+/// assert_eq!(Randomize::<Pkcs7, U8>::unpad(padded_msg).unwrap(), b"test");
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub enum Randomize<P, N, R = DefaultRng> {
+    _Phantom(Infallible, PhantomData<(P, N)>, PhantomData<fn() -> R>),
+}
+
+impl<P: Padding, N: BlockSize, R> Randomize<P, N, R> {
+    /// Pads message with length `pos` in the provided buffer, drawing the random block from `rng`
+    ///
+    /// The message is moved one block further before being padded with `P`, so the buffer must
+    /// hold a block more than `P` needs.
+    pub fn pad_with_rng<'a>(
+        rng: &mut (impl RngCore + CryptoRng),
+        buf: &'a mut [u8],
+        pos: usize,
+    ) -> Result<&'a mut [u8], PadError> {
+        let block_size = N::USIZE;
+        if pos > buf.len() {
+            Err(TxPadError::PositionOutOfBounds {
+                pos,
+                len: buf.len(),
+            })?
+        }
+        if buf.len() - pos < block_size {
+            Err(TxPadError::BufferTooSmall {
+                required: pos + block_size,
+                got: buf.len(),
+            })?
+        }
+        buf.copy_within(..pos, block_size);
+        let padded_len = P::pad(&mut buf[block_size..], pos, block_size)?.len();
+        rng.try_fill_bytes(&mut buf[..block_size])
+            .map_err(|_| TxPadError::RngFailure)?;
+        Ok(&mut buf[..block_size + padded_len])
+    }
+
+    /// Unpad given `data`, returning the message
+    ///
+    /// `Padding::unpad` delegates here, but this does not require a default RNG.
+    pub fn unpad(data: &[u8]) -> Result<&[u8], UnpadError> {
+        if data.len() < N::USIZE {
+            Err(UnpadError)?
+        }
+        P::unpad(&data[N::USIZE..])
+    }
+}
+
+impl<P, N, R> Padding for Randomize<P, N, R>
+where
+    P: Padding,
+    N: BlockSize,
+    R: RngCore + CryptoRng + Default,
+{
+    fn pad_block(_block: &mut [u8], _pos: usize) -> Result<(), PadError> {
+        Err(PadError)
+    }
+
+    fn unpad(data: &[u8]) -> Result<&[u8], UnpadError> {
+        Self::unpad(data)
+    }
+
+    fn pad(buf: &mut [u8], pos: usize, block_size: usize) -> Result<&mut [u8], PadError> {
+        if block_size != N::USIZE {
+            Err(TxPadError::BlockSizeMismatch {
+                expected: N::USIZE,
+                got: block_size,
+            })?
+        }
+        Self::pad_with_rng(&mut R::default(), buf, pos)
+    }
+}
@@ -0,0 +1,704 @@
+//! The padding scheme implemented over a runtime block size
+//!
+//! Every padding type of the crate delegates to these functions after validating its block size,
+//! so `block_size` is always assumed to be a power of 2 between 2 and 256 inclusive here.
+
+use core::convert::TryInto;
+#[cfg(feature = "uninit")]
+use core::iter;
+#[cfg(feature = "uninit")]
+use core::mem::MaybeUninit;
+use core::ops::Range;
+
+use crate::error::UnpadErrorKind;
+use crate::{trace, RngCore, TxPadError};
+
+/// Largest prefix of a padded message: the header and at most `block_size + 1` random bytes
+pub(crate) const MAX_PREFIX_LEN: usize = 1 + 256 + 1;
+
+/// Length of the padded output for a message of length `msg_len`
+pub(crate) const fn padded_len(block_size: usize, msg_len: usize) -> usize {
+    block_size * ((msg_len + 1) / block_size + 2)
+}
+
+/// Length of the padded output for a message of length `msg_len`, or `None` if it overflows
+pub(crate) const fn checked_padded_len(block_size: usize, msg_len: usize) -> Option<usize> {
+    // (msg_len + 1) / block_size without overflowing on usize::MAX
+    let blocks = msg_len / block_size + (msg_len % block_size + 1) / block_size;
+    match blocks.checked_add(2) {
+        Some(blocks) => blocks.checked_mul(block_size),
+        None => None,
+    }
+}
+
+/// Length of the padded output for a message of length `msg_len`, failing with `MessageTooLong`
+/// if the message is longer than `max_msg_len`
+///
+/// The length is summed from the parts of the padded message rather than rounded, which lets the
+/// optimizer see that each of them lies within it.
+#[inline(always)]
+fn required_len(block_size: usize, msg_len: usize) -> Result<usize, TxPadError> {
+    if msg_len > max_msg_len(block_size) {
+        Err(TxPadError::MessageTooLong)?
+    }
+    Ok(1 + pad_len(block_size, msg_len) + (block_size - 1) + msg_len)
+}
+
+/// Longest message whose padded output fits in a slice, i.e. is at most `isize::MAX` bytes
+pub(crate) const fn max_msg_len(block_size: usize) -> usize {
+    isize::MAX as usize - max_overhead(block_size)
+}
+
+/// Smallest number of bytes the padding adds to a message of any length
+pub(crate) const fn min_overhead(block_size: usize) -> usize {
+    block_size + 2
+}
+
+/// Largest number of bytes the padding adds to a message of any length
+pub(crate) const fn max_overhead(block_size: usize) -> usize {
+    2 * block_size + 1
+}
+
+/// Number of random bytes prepended to a message of length `msg_len`
+#[inline(always)]
+pub(crate) fn pad_len(block_size: usize, msg_len: usize) -> usize {
+    // (-msg_len - 2) mod block_size, computed without overflowing
+    (block_size - (msg_len % block_size + 2) % block_size) % block_size + 2
+}
+
+/// Write the header byte followed by random bytes into `prefix`
+///
+/// `prefix` must be `1 + pad_len` bytes long.
+#[inline(always)]
+pub(crate) fn fill_prefix(
+    rng: &mut impl RngCore,
+    prefix: &mut [u8],
+    block_size: usize,
+) -> Result<(), TxPadError> {
+    let pad_len = prefix.len() - 1;
+    rng.try_fill_bytes(&mut prefix[1..])
+        .map_err(|_| TxPadError::RngFailure)?;
+    prefix[0] = marker(block_size, pad_len);
+    Ok(())
+}
+
+/// Write random bytes followed by the trailer byte into `suffix`, mirroring `fill_prefix`
+///
+/// `suffix` must be `pad_len + 1` bytes long.
+pub(crate) fn fill_suffix(
+    rng: &mut impl RngCore,
+    suffix: &mut [u8],
+    block_size: usize,
+) -> Result<(), TxPadError> {
+    let pad_len = suffix.len() - 1;
+    rng.try_fill_bytes(&mut suffix[..pad_len])
+        .map_err(|_| TxPadError::RngFailure)?;
+    suffix[pad_len] = marker(block_size, pad_len);
+    Ok(())
+}
+
+/// The header or trailer byte declaring `pad_len` random bytes
+#[inline(always)]
+fn marker(block_size: usize, pad_len: usize) -> u8 {
+    !((block_size - 1) as u8) | (pad_len - 2) as u8
+}
+
+/// Number of random bytes declared by a header or trailer byte, checking that a padded message
+/// of length `len` is long enough for them
+#[inline(always)]
+pub(crate) fn declared_pad_len(
+    len: usize,
+    marker: u8,
+    block_size: usize,
+) -> Result<usize, UnpadErrorKind> {
+    let pad_len = (marker & ((block_size - 1) as u8)) as usize + 2;
+    if len < pad_len + block_size {
+        Err(UnpadErrorKind::TooShort)?
+    }
+    Ok(pad_len)
+}
+
+/// Check that the run of padding zeros at `zeros` in `data` is all `fill`
+///
+/// The run is compared 8 bytes at a time, and only scanned byte by byte to locate the offending
+/// byte once it is known to be malformed.
+#[inline(always)]
+pub(crate) fn check_zeros(
+    data: &[u8],
+    zeros: Range<usize>,
+    fill: u8,
+) -> Result<(), UnpadErrorKind> {
+    let run = &data[zeros.clone()];
+    let pattern = u64::from_ne_bytes([fill; 8]);
+    let mut words = run.chunks_exact(8);
+    let diff = words.by_ref().fold(0, |diff, word| {
+        diff | (u64::from_ne_bytes(word.try_into().unwrap()) ^ pattern)
+    });
+    if diff == 0 && words.remainder().iter().all(|&v| v == fill) {
+        return Ok(());
+    }
+
+    let i = run.iter().position(|&v| v != fill).unwrap_or(0);
+    Err(UnpadErrorKind::NonZeroTail {
+        index: zeros.start + i,
+    })
+}
+
+/// Replace the upper bits of `header`, which do not store the number of random bytes, with
+/// random ones
+#[inline(always)]
+pub(crate) fn randomize_header(
+    rng: &mut impl RngCore,
+    header: &mut u8,
+    block_size: usize,
+) -> Result<(), TxPadError> {
+    let mut random = [0];
+    rng.try_fill_bytes(&mut random)
+        .map_err(|_| TxPadError::RngFailure)?;
+    let pad_zero = (block_size - 1) as u8;
+    *header = (random[0] & !pad_zero) | (*header & pad_zero);
+    Ok(())
+}
+
+/// Largest scheme version which fits in the header
+pub(crate) const MAX_VERSION: u8 = 3;
+
+/// Store `version` in the 2 bits of `header` right above the number of random bytes
+///
+/// `block_size` must be at most 64 so these bits are available.
+pub(crate) fn set_version(header: &mut u8, version: u8, block_size: usize) {
+    let shift = block_size.trailing_zeros();
+    *header = (*header & !(MAX_VERSION << shift)) | (version << shift);
+}
+
+/// The scheme version stored in `header` by `set_version`
+pub(crate) fn version(header: u8, block_size: usize) -> u8 {
+    (header >> block_size.trailing_zeros()) & MAX_VERSION
+}
+
+/// Like `unpad`, but also require the header to store the scheme `version`
+pub(crate) fn unpad_versioned(
+    data: &[u8],
+    block_size: usize,
+    version: u8,
+) -> Result<&[u8], UnpadErrorKind> {
+    let msg = unpad(data, block_size)?;
+    let got = self::version(data[0], block_size);
+    if got != version {
+        Err(UnpadErrorKind::VersionMismatch {
+            expected: version,
+            got,
+        })?
+    }
+    Ok(msg)
+}
+
+/// Check that the message with length `pos` lies within a buffer of length `buf_len`
+#[inline(always)]
+pub(crate) fn check_pos(buf_len: usize, pos: usize) -> Result<(), TxPadError> {
+    if pos > buf_len {
+        Err(TxPadError::PositionOutOfBounds { pos, len: buf_len })?
+    }
+    Ok(())
+}
+
+/// Length of the padded output for a message of length `msg_len`, checking that `buf_len` is
+/// large enough
+#[inline(always)]
+fn fitting_len(buf_len: usize, msg_len: usize, block_size: usize) -> Result<usize, TxPadError> {
+    let be = required_len(block_size, msg_len)?;
+    if buf_len < be {
+        Err(TxPadError::BufferTooSmall {
+            required: be,
+            got: buf_len,
+        })?
+    }
+    Ok(be)
+}
+
+/// Offset of the message in a padded buffer, checking that `buf_len` is large enough
+#[inline(always)]
+pub(crate) fn reserve(
+    buf_len: usize,
+    msg_len: usize,
+    block_size: usize,
+) -> Result<usize, TxPadError> {
+    fitting_len(buf_len, msg_len, block_size)?;
+    Ok(1 + pad_len(block_size, msg_len))
+}
+
+/// Fill the prefix and the trailing zeros around a message already placed at its offset
+#[inline(always)]
+pub(crate) fn finish<'a>(
+    rng: &mut impl RngCore,
+    buf: &'a mut [u8],
+    msg_len: usize,
+    block_size: usize,
+) -> Result<&'a mut [u8], TxPadError> {
+    let be = fitting_len(buf.len(), msg_len, block_size)?;
+    let offset = 1 + pad_len(block_size, msg_len);
+    let pad_zero = block_size - 1;
+    fill_prefix(rng, &mut buf[..offset], block_size)?;
+
+    buf[be - pad_zero..be].fill(0);
+
+    Ok(&mut buf[..be])
+}
+
+#[inline(always)]
+pub(crate) fn pad<'a>(
+    rng: &mut impl RngCore,
+    buf: &'a mut [u8],
+    pos: usize,
+    block_size: usize,
+) -> Result<&'a mut [u8], TxPadError> {
+    trace::padded(block_size, pos, pad_untraced(rng, buf, pos, block_size))
+}
+
+#[inline(always)]
+fn pad_untraced<'a>(
+    rng: &mut impl RngCore,
+    buf: &'a mut [u8],
+    pos: usize,
+    block_size: usize,
+) -> Result<&'a mut [u8], TxPadError> {
+    check_pos(buf.len(), pos)?;
+    let be = fitting_len(buf.len(), pos, block_size)?;
+    buf[..be].copy_within(..pos, 1 + pad_len(block_size, pos));
+    finish(rng, buf, pos, block_size)
+}
+
+/// Like `pad`, but without writing the trailing zeros, which `buf` must already hold
+#[inline(always)]
+pub(crate) fn pad_assume_zeroed<'a>(
+    rng: &mut impl RngCore,
+    buf: &'a mut [u8],
+    pos: usize,
+    block_size: usize,
+) -> Result<&'a mut [u8], TxPadError> {
+    trace::padded(
+        block_size,
+        pos,
+        pad_assume_zeroed_untraced(rng, buf, pos, block_size),
+    )
+}
+
+#[inline(always)]
+fn pad_assume_zeroed_untraced<'a>(
+    rng: &mut impl RngCore,
+    buf: &'a mut [u8],
+    pos: usize,
+    block_size: usize,
+) -> Result<&'a mut [u8], TxPadError> {
+    check_pos(buf.len(), pos)?;
+    let be = fitting_len(buf.len(), pos, block_size)?;
+    debug_assert!(buf[pos..be].iter().all(|&v| v == 0));
+    let offset = 1 + pad_len(block_size, pos);
+    buf[..be].copy_within(..pos, offset);
+    fill_prefix(rng, &mut buf[..offset], block_size)?;
+    Ok(&mut buf[..be])
+}
+
+/// Pad `msg` into the separate buffer `out`, leaving `msg` untouched
+#[inline(always)]
+pub(crate) fn pad_from<'a>(
+    rng: &mut impl RngCore,
+    msg: &[u8],
+    out: &'a mut [u8],
+    block_size: usize,
+) -> Result<&'a mut [u8], TxPadError> {
+    trace::padded(
+        block_size,
+        msg.len(),
+        pad_from_untraced(rng, msg, out, block_size),
+    )
+}
+
+#[inline(always)]
+fn pad_from_untraced<'a>(
+    rng: &mut impl RngCore,
+    msg: &[u8],
+    out: &'a mut [u8],
+    block_size: usize,
+) -> Result<&'a mut [u8], TxPadError> {
+    let pos = msg.len();
+    let be = required_len(block_size, pos)?;
+    if out.len() < be {
+        Err(TxPadError::BufferTooSmall {
+            required: be,
+            got: out.len(),
+        })?
+    }
+
+    let pad_zero = block_size - 1;
+    let pad_len = pad_len(block_size, pos);
+    fill_prefix(rng, &mut out[..1 + pad_len], block_size)?;
+    out[1 + pad_len..1 + pad_len + pos].copy_from_slice(msg);
+    out[be - pad_zero..be].fill(0);
+
+    Ok(&mut out[..be])
+}
+
+/// Pad `msg` into the possibly uninitialized buffer `out`, initializing the padded part of it
+#[cfg(feature = "uninit")]
+#[inline(always)]
+pub(crate) fn pad_uninit<'a>(
+    rng: &mut impl RngCore,
+    msg: &[u8],
+    out: &'a mut [MaybeUninit<u8>],
+    block_size: usize,
+) -> Result<&'a mut [u8], TxPadError> {
+    trace::padded(
+        block_size,
+        msg.len(),
+        pad_uninit_untraced(rng, msg, out, block_size),
+    )
+}
+
+#[cfg(feature = "uninit")]
+#[inline(always)]
+fn pad_uninit_untraced<'a>(
+    rng: &mut impl RngCore,
+    msg: &[u8],
+    out: &'a mut [MaybeUninit<u8>],
+    block_size: usize,
+) -> Result<&'a mut [u8], TxPadError> {
+    let pos = msg.len();
+    let be = required_len(block_size, pos)?;
+    if out.len() < be {
+        Err(TxPadError::BufferTooSmall {
+            required: be,
+            got: out.len(),
+        })?
+    }
+
+    let pad_zero = block_size - 1;
+    let prefix_len = 1 + pad_len(block_size, pos);
+    let mut prefix = [0; MAX_PREFIX_LEN];
+    fill_prefix(rng, &mut prefix[..prefix_len], block_size)?;
+    let padded_msg = prefix[..prefix_len]
+        .iter()
+        .chain(msg)
+        .chain(iter::repeat(&0).take(pad_zero));
+    for (slot, &v) in out[..be].iter_mut().zip(padded_msg) {
+        *slot = MaybeUninit::new(v);
+    }
+
+    // SAFETY: every byte of `out[..be]` has been initialized above, and `MaybeUninit<u8>` has the
+    // same layout as `u8`
+    Ok(unsafe { &mut *(&mut out[..be] as *mut [MaybeUninit<u8>] as *mut [u8]) })
+}
+
+#[inline(always)]
+pub(crate) fn unpad(data: &[u8], block_size: usize) -> Result<&[u8], UnpadErrorKind> {
+    unpad_range(data, block_size).map(|range| &data[range])
+}
+
+/// Like `unpad`, but also require the length of `data` to be a multiple of `block_size`
+#[inline(always)]
+pub(crate) fn unpad_strict(data: &[u8], block_size: usize) -> Result<&[u8], UnpadErrorKind> {
+    if data.len() % block_size != 0 {
+        Err(UnpadErrorKind::LengthNotBlockAligned)?
+    }
+    unpad(data, block_size)
+}
+
+/// Whether `data` is a well-formed padded message, without reporting it to `trace`
+#[inline(always)]
+pub(crate) fn is_valid(data: &[u8], block_size: usize) -> bool {
+    unpad_filled_range_untraced(data, block_size, 0).is_ok()
+}
+
+/// Like `unpad`, but only check the header against the length of `data`, not the trailing zeros
+#[inline(always)]
+pub(crate) fn unpad_trusted(data: &[u8], block_size: usize) -> Result<&[u8], UnpadErrorKind> {
+    let header = match data.first() {
+        Some(&header) => header,
+        None => Err(UnpadErrorKind::EmptyInput)?,
+    };
+    let pad_len = declared_pad_len(data.len(), header, block_size)?;
+    Ok(&data[1 + pad_len..data.len() - (block_size - 1)])
+}
+
+/// Position of the message in the padded `data`
+#[inline(always)]
+pub(crate) fn unpad_range(data: &[u8], block_size: usize) -> Result<Range<usize>, UnpadErrorKind> {
+    unpad_filled_range(data, block_size, 0)
+}
+
+/// Move the message in the padded `buf` to its front, returning its length
+///
+/// `buf` is left untouched on failure.
+#[cfg(any(feature = "alloc", feature = "heapless", feature = "bytes"))]
+#[inline(always)]
+pub(crate) fn unpad_to_front(buf: &mut [u8], block_size: usize) -> Result<usize, UnpadErrorKind> {
+    let range = unpad_range(buf, block_size)?;
+    let len = range.len();
+    buf.copy_within(range, 0);
+    Ok(len)
+}
+
+/// Length of the message in a padded message of length `total_len` starting with `first_block`
+#[inline(always)]
+pub(crate) fn peek_msg_len(
+    first_block: &[u8],
+    total_len: usize,
+    block_size: usize,
+) -> Result<usize, UnpadErrorKind> {
+    let header = match first_block.first() {
+        Some(&header) => header,
+        None => Err(UnpadErrorKind::EmptyInput)?,
+    };
+    let pad_len = declared_pad_len(total_len, header, block_size)?;
+    Ok(total_len - pad_len - block_size)
+}
+
+/// Position of the message in `data` whose trailing zeros are replaced by `fill`
+#[inline(always)]
+pub(crate) fn unpad_filled_range(
+    data: &[u8],
+    block_size: usize,
+    fill: u8,
+) -> Result<Range<usize>, UnpadErrorKind> {
+    trace::unpadded(
+        block_size,
+        data.len(),
+        unpad_filled_range_untraced(data, block_size, fill),
+    )
+}
+
+#[inline(always)]
+fn unpad_filled_range_untraced(
+    data: &[u8],
+    block_size: usize,
+    fill: u8,
+) -> Result<Range<usize>, UnpadErrorKind> {
+    if data.is_empty() {
+        Err(UnpadErrorKind::EmptyInput)?
+    }
+    let l = data.len();
+    let pad_zero = block_size - 1;
+    let pad_len = declared_pad_len(data.len(), data[0], block_size)?;
+    check_zeros(data, l - pad_zero..l, fill)?;
+
+    Ok(1 + pad_len..l - pad_zero)
+}
+
+/// Size of the length field preceding the message in `pad_length_prefixed`
+pub(crate) const LEN_FIELD: usize = 8;
+
+/// What follows the message in `pad_length_prefixed`
+pub(crate) enum Filler {
+    Random,
+    /// Zeros, which extend the trailing zeros of the padding
+    Zeros,
+}
+
+/// Pad the message with length `pos` to exactly `padded_len` bytes, which must be a multiple of
+/// `block_size` no less than `padded_len(block_size, LEN_FIELD + pos)`
+///
+/// The message is prefixed with its length as a 64-bit big-endian integer and followed by
+/// `filler`, and the result is padded as usual.
+pub(crate) fn pad_length_prefixed<'a>(
+    rng: &mut impl RngCore,
+    buf: &'a mut [u8],
+    pos: usize,
+    padded_len: usize,
+    block_size: usize,
+    filler: Filler,
+) -> Result<&'a mut [u8], TxPadError> {
+    check_pos(buf.len(), pos)?;
+    if buf.len() < padded_len {
+        Err(TxPadError::BufferTooSmall {
+            required: padded_len,
+            got: buf.len(),
+        })?
+    }
+
+    let filler_len = padded_len - self::padded_len(block_size, LEN_FIELD + pos);
+    let payload_len = LEN_FIELD + pos + filler_len;
+    buf.copy_within(..pos, LEN_FIELD);
+    buf[..LEN_FIELD].copy_from_slice(&(pos as u64).to_be_bytes());
+    let filler_bytes = &mut buf[LEN_FIELD + pos..payload_len];
+    match filler {
+        Filler::Random => rng
+            .try_fill_bytes(filler_bytes)
+            .map_err(|_| TxPadError::RngFailure)?,
+        Filler::Zeros => {
+            filler_bytes.fill(0);
+        }
+    }
+    pad(rng, buf, payload_len, block_size)
+}
+
+/// Unpad `data` padded by `pad_length_prefixed`
+pub(crate) fn unpad_length_prefixed(
+    data: &[u8],
+    block_size: usize,
+) -> Result<&[u8], UnpadErrorKind> {
+    let payload = unpad(data, block_size)?;
+    if payload.len() < LEN_FIELD {
+        Err(UnpadErrorKind::TooShort)?
+    }
+    let (len, rest) = payload.split_at(LEN_FIELD);
+    let len = u64::from_be_bytes(len.try_into().unwrap());
+    if len > rest.len() as u64 {
+        Err(UnpadErrorKind::TooShort)?
+    }
+    Ok(&rest[..len as usize])
+}
+
+/// Length of the output of `pad_min` for a message of length `msg_len`
+pub(crate) const fn padded_min_len(block_size: usize, msg_len: usize, min_len: usize) -> usize {
+    let len = padded_len(block_size, LEN_FIELD + msg_len);
+    let min_len = (min_len + block_size - 1) / block_size * block_size;
+    if len < min_len {
+        min_len
+    } else {
+        len
+    }
+}
+
+/// Pad the message with length `pos` to at least `min_len` bytes, prefixed with its length
+pub(crate) fn pad_min<'a>(
+    rng: &mut impl RngCore,
+    buf: &'a mut [u8],
+    pos: usize,
+    min_len: usize,
+    block_size: usize,
+) -> Result<&'a mut [u8], TxPadError> {
+    if pos > isize::MAX as usize - LEN_FIELD || min_len > isize::MAX as usize {
+        Err(TxPadError::MessageTooLong)?
+    }
+    let padded_len = padded_min_len(block_size, pos, min_len);
+    pad_length_prefixed(rng, buf, pos, padded_len, block_size, Filler::Zeros)
+}
+
+/// Length of the output of `pad_aligned` for a message of length `msg_len`, or `None` if
+/// `blocks` is zero or the length overflows
+pub(crate) const fn padded_aligned_len(
+    block_size: usize,
+    msg_len: usize,
+    blocks: usize,
+) -> Option<usize> {
+    let alignment = match blocks.checked_mul(block_size) {
+        Some(alignment) if alignment > 0 => alignment,
+        _ => return None,
+    };
+    let max_msg_len = match (isize::MAX as usize - LEN_FIELD).checked_sub(alignment) {
+        Some(max_msg_len) => max_msg_len,
+        None => return None,
+    };
+    if msg_len > max_msg_len {
+        return None;
+    }
+    let len = padded_len(block_size, LEN_FIELD + msg_len);
+    Some((len + alignment - 1) / alignment * alignment)
+}
+
+/// Pad the message with length `pos` to a multiple of `blocks` blocks, prefixed with its length
+pub(crate) fn pad_aligned<'a>(
+    rng: &mut impl RngCore,
+    buf: &'a mut [u8],
+    pos: usize,
+    blocks: usize,
+    block_size: usize,
+) -> Result<&'a mut [u8], TxPadError> {
+    if blocks == 0 {
+        Err(TxPadError::InvalidBlockSize { got: 0 })?
+    }
+    let padded_len =
+        padded_aligned_len(block_size, pos, blocks).ok_or(TxPadError::MessageTooLong)?;
+    pad_length_prefixed(rng, buf, pos, padded_len, block_size, Filler::Zeros)
+}
+
+/// Unpad the padded message of `blocks` blocks at the front of `data`, returning the message and
+/// the length of the padded message
+pub(crate) fn unpad_prefix(
+    data: &[u8],
+    blocks: usize,
+    block_size: usize,
+) -> Result<(&[u8], usize), UnpadErrorKind> {
+    let padded_len = match blocks.checked_mul(block_size) {
+        Some(padded_len) if padded_len <= data.len() => padded_len,
+        _ => Err(UnpadErrorKind::TooShort)?,
+    };
+    Ok((unpad(&data[..padded_len], block_size)?, padded_len))
+}
+
+/// Pad like `pad`, but with `fill` in place of the trailing zeros
+#[inline(always)]
+pub(crate) fn pad_filled<'a>(
+    rng: &mut impl RngCore,
+    buf: &'a mut [u8],
+    pos: usize,
+    block_size: usize,
+    fill: u8,
+) -> Result<&'a mut [u8], TxPadError> {
+    let padded_msg = pad(rng, buf, pos, block_size)?;
+    let l = padded_msg.len();
+    padded_msg[l - (block_size - 1)..].fill(fill);
+    Ok(padded_msg)
+}
+
+/// Pad like `pad`, but with random bytes in place of the trailing zeros
+pub(crate) fn pad_random_tail<'a>(
+    rng: &mut impl RngCore,
+    buf: &'a mut [u8],
+    pos: usize,
+    block_size: usize,
+) -> Result<&'a mut [u8], TxPadError> {
+    let padded_msg = pad(rng, buf, pos, block_size)?;
+    let l = padded_msg.len();
+    rng.try_fill_bytes(&mut padded_msg[l - (block_size - 1)..])
+        .map_err(|_| TxPadError::RngFailure)?;
+    Ok(padded_msg)
+}
+
+/// Position of the message in `data` padded by `pad_random_tail`
+pub(crate) fn unpad_random_tail_range(
+    data: &[u8],
+    block_size: usize,
+) -> Result<Range<usize>, UnpadErrorKind> {
+    if data.is_empty() {
+        Err(UnpadErrorKind::EmptyInput)?
+    }
+    let pad_len = declared_pad_len(data.len(), data[0], block_size)?;
+
+    Ok(1 + pad_len..data.len() - (block_size - 1))
+}
+
+/// Pad in the tail marker layout: zeros, the message, random bytes and the trailer byte
+pub(crate) fn pad_tail<'a>(
+    rng: &mut impl RngCore,
+    buf: &'a mut [u8],
+    pos: usize,
+    block_size: usize,
+) -> Result<&'a mut [u8], TxPadError> {
+    check_pos(buf.len(), pos)?;
+    reserve(buf.len(), pos, block_size)?;
+    let be = padded_len(block_size, pos);
+    let pad_zero = block_size - 1;
+    buf.copy_within(..pos, pad_zero);
+    fill_suffix(rng, &mut buf[pad_zero + pos..be], block_size)?;
+    buf[..pad_zero].fill(0);
+
+    Ok(&mut buf[..be])
+}
+
+/// Position of the message in `data` padded in the tail marker layout
+pub(crate) fn unpad_tail_range(
+    data: &[u8],
+    block_size: usize,
+) -> Result<Range<usize>, UnpadErrorKind> {
+    let l = data.len();
+    let trailer = match data.last() {
+        Some(&trailer) => trailer,
+        None => Err(UnpadErrorKind::EmptyInput)?,
+    };
+    let pad_zero = block_size - 1;
+    let pad_len = declared_pad_len(data.len(), trailer, block_size)?;
+    check_zeros(data, 0..pad_zero, 0)?;
+
+    Ok(pad_zero..l - 1 - pad_len)
+}
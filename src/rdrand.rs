@@ -0,0 +1,197 @@
+//! RNG drawing from the RDRAND and RDSEED instructions of x86 processors
+
+#[cfg(target_arch = "x86")]
+use core::arch::x86 as arch;
+#[cfg(target_arch = "x86_64")]
+use core::arch::x86_64 as arch;
+use core::sync::atomic::{AtomicU8, Ordering};
+
+#[cfg(feature = "os-rng")]
+use rand::rngs::OsRng;
+use rand::{CryptoRng, Error, RngCore};
+
+/// Word produced by a single RDRAND or RDSEED instruction
+#[cfg(target_arch = "x86_64")]
+type Word = u64;
+#[cfg(target_arch = "x86")]
+type Word = u32;
+
+/// Number of attempts before giving up on an instruction, as recommended by Intel for RDRAND
+const RETRIES: usize = 10;
+
+/// The instructions have not been probed yet
+const UNKNOWN: u8 = 0;
+/// Flag set once the instructions have been probed
+const PROBED: u8 = 1;
+/// Flag set if RDRAND is available and passed the self-test
+const HAS_RDRAND: u8 = 2;
+/// Flag set if RDSEED is available
+const HAS_RDSEED: u8 = 4;
+
+/// The probed instructions, or `UNKNOWN`
+static FEATURES: AtomicU8 = AtomicU8::new(UNKNOWN);
+
+/// The instructions available on this processor
+fn features() -> u8 {
+    let features = FEATURES.load(Ordering::Relaxed);
+    if features != UNKNOWN {
+        return features;
+    }
+    let features = probe();
+    FEATURES.store(features, Ordering::Relaxed);
+    features
+}
+
+/// Query CPUID for the instructions and test RDRAND
+fn probe() -> u8 {
+    // SAFETY: CPUID is available on every processor Rust supports for these architectures. The
+    // intrinsics are safe functions on recent compilers only.
+    #[allow(unused_unsafe)]
+    let (leaf1, leaf7) = unsafe {
+        let max_leaf = arch::__get_cpuid_max(0).0;
+        let leaf7 = if max_leaf >= 7 {
+            arch::__cpuid_count(7, 0).ebx
+        } else {
+            0
+        };
+        (arch::__cpuid(1).ecx, leaf7)
+    };
+    let mut features = PROBED;
+    // Some AMD processors report success while always returning ones after a suspend, so RDRAND
+    // is only trusted once it has produced something else
+    // SAFETY: the instruction is only executed if CPUID reports it
+    if leaf1 & (1 << 30) != 0 && (0..RETRIES).any(|_| unsafe { rdrand() } != Some(!0)) {
+        features |= HAS_RDRAND;
+    }
+    if leaf7 & (1 << 18) != 0 {
+        features |= HAS_RDSEED;
+    }
+    features
+}
+
+/// Execute RDRAND once, returning `None` if no random word was ready
+#[target_feature(enable = "rdrand")]
+unsafe fn rdrand() -> Option<Word> {
+    let mut word = 0;
+    #[cfg(target_arch = "x86_64")]
+    let ok = arch::_rdrand64_step(&mut word);
+    #[cfg(target_arch = "x86")]
+    let ok = arch::_rdrand32_step(&mut word);
+    if ok == 1 {
+        Some(word)
+    } else {
+        None
+    }
+}
+
+/// Execute RDSEED once, returning `None` if no random word was ready
+#[target_feature(enable = "rdseed")]
+unsafe fn rdseed() -> Option<Word> {
+    let mut word = 0;
+    #[cfg(target_arch = "x86_64")]
+    let ok = arch::_rdseed64_step(&mut word);
+    #[cfg(target_arch = "x86")]
+    let ok = arch::_rdseed32_step(&mut word);
+    if ok == 1 {
+        Some(word)
+    } else {
+        None
+    }
+}
+
+/// Draw a random word, trying RDRAND then RDSEED up to `RETRIES` times each
+fn random_word(features: u8) -> Option<Word> {
+    if features & HAS_RDRAND != 0 {
+        // SAFETY: RDRAND is available per `features`
+        if let Some(word) = (0..RETRIES).find_map(|_| unsafe { rdrand() }) {
+            return Some(word);
+        }
+    }
+    if features & HAS_RDSEED != 0 {
+        // SAFETY: RDSEED is available per `features`
+        if let Some(word) = (0..RETRIES).find_map(|_| unsafe { rdseed() }) {
+            return Some(word);
+        }
+    }
+    None
+}
+
+/// Fill `dest` from the instructions, returning `false` if they are unavailable or exhausted
+fn fill(dest: &mut [u8]) -> bool {
+    let features = features();
+    if features & (HAS_RDRAND | HAS_RDSEED) == 0 {
+        return false;
+    }
+    for chunk in dest.chunks_mut(core::mem::size_of::<Word>()) {
+        match random_word(features) {
+            Some(word) => chunk.copy_from_slice(&word.to_ne_bytes()[..chunk.len()]),
+            None => return false,
+        }
+    }
+    true
+}
+
+/// The RNG drawing from the RDRAND instruction, for x86 and x86-64 processors
+///
+/// Filling the random prefix takes a few instructions instead of a system call per padded
+/// message. Each word is retried on RDRAND a few times, then on RDSEED, and if neither produces
+/// it, e.g. on a processor without them, the whole request is served by `OsRng`. Without the
+/// `os-rng` feature, generating random bytes fails instead.
+///
+/// Enabling the `rdrand` feature does not change [`DefaultRng`](crate::DefaultRng): this type
+/// must be named at the use sites.
+///
+/// ```
+/// use tx_padding::{RdRandRng, TxPadding, Padding};
+/// use tx_padding::consts::{U8};
+///
+/// let mut buffer = [0; 16];
+/// buffer[..4].copy_from_slice(b"test");
+/// let padded_msg = TxPadding::<U8, RdRandRng>::pad(&mut buffer, 4, 8).unwrap();
+/// assert_eq!(TxPadding::<U8>::unpad(&padded_msg).unwrap(), b"test");
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RdRandRng;
+
+impl RdRandRng {
+    /// Whether this processor has a working RDRAND or RDSEED instruction
+    ///
+    /// If not, every request falls back to `OsRng`.
+    pub fn is_available() -> bool {
+        features() & (HAS_RDRAND | HAS_RDSEED) != 0
+    }
+}
+
+impl RngCore for RdRandRng {
+    fn next_u32(&mut self) -> u32 {
+        let mut bytes = [0; 4];
+        self.fill_bytes(&mut bytes);
+        u32::from_le_bytes(bytes)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let mut bytes = [0; 8];
+        self.fill_bytes(&mut bytes);
+        u64::from_le_bytes(bytes)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        if let Err(e) = self.try_fill_bytes(dest) {
+            panic!("{}", e)
+        }
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        if fill(dest) {
+            return Ok(());
+        }
+        #[cfg(feature = "os-rng")]
+        return OsRng.try_fill_bytes(dest);
+        #[cfg(not(feature = "os-rng"))]
+        Err(Error::from(
+            core::num::NonZeroU32::new(Error::CUSTOM_START).unwrap(),
+        ))
+    }
+}
+
+impl CryptoRng for RdRandRng {}
@@ -0,0 +1,168 @@
+//! Padding schemes looked up by an identifier byte
+
+use core::convert::TryFrom;
+use core::fmt;
+use core::marker::PhantomData;
+
+use crate::{
+    BlockSize, CryptoRng, DynPadding, Padme, RngCore, TxPadError, TxPadding, TxRandomTailPadding,
+    UnpadError,
+};
+
+/// Number of schemes with an identifier
+const SCHEME_COUNT: usize = 4;
+
+/// Identifier byte of a padding scheme, e.g. as negotiated by the peers of a protocol
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(u8)]
+pub enum SchemeId {
+    /// [`TxPadding`]: random prefix and trailing zeros
+    TxZeroTail = 0,
+    /// [`TxRandomTailPadding`]: random prefix and random tail
+    TxRandomTail = 1,
+    /// [`Padme`]: Padmé lengths on top of `TxPadding`
+    Padme = 2,
+    /// [`BucketPadding`](crate::BucketPadding): bucketed lengths on top of `TxPadding`
+    Bucketed = 3,
+}
+
+impl TryFrom<u8> for SchemeId {
+    type Error = TxPadError;
+
+    fn try_from(id: u8) -> Result<Self, TxPadError> {
+        match id {
+            0 => Ok(SchemeId::TxZeroTail),
+            1 => Ok(SchemeId::TxRandomTail),
+            2 => Ok(SchemeId::Padme),
+            3 => Ok(SchemeId::Bucketed),
+            _ => Err(TxPadError::UnknownScheme { id }),
+        }
+    }
+}
+
+impl From<SchemeId> for u8 {
+    fn from(id: SchemeId) -> u8 {
+        id as u8
+    }
+}
+
+/// Implementations of the padding schemes, keyed by their [`SchemeId`]
+///
+/// Protocols supporting several schemes can register the implementation of each, with its block
+/// size and parameters, once and then dispatch on the identifier byte negotiated with the peer.
+///
+/// ```
+/// # #[cfg(any(feature = "os-rng", feature = "thread-rng"))]
+/// # {
+/// use tx_padding::{BucketPadding, SchemeId, SchemeRegistry};
+/// use tx_padding::consts::{U16};
+///
+/// let buckets = BucketPadding::<U16>::new(&[256, 1024]);
+/// let mut registry = SchemeRegistry::with_defaults::<U16>();
+/// registry.register(SchemeId::Bucketed, &buckets);
+///
+/// let id = u8::from(SchemeId::Bucketed); // e.g. negotiated with the peer
+/// let mut buffer = [0; 256];
+/// buffer[..4].copy_from_slice(b"test");
+/// let padded_msg = registry.pad(id, &mut buffer, 4).unwrap();
+/// assert_eq!(padded_msg.len(), 256);
+/// assert_eq!(registry.unpad_auto(id, padded_msg).unwrap(), b"test");
+/// assert!(registry.unpad_auto(42, padded_msg).is_err());
+/// # }
+/// ```
+#[derive(Clone, Copy, Default)]
+pub struct SchemeRegistry<'a> {
+    schemes: [Option<&'a dyn DynPadding>; SCHEME_COUNT],
+}
+
+impl<'a> SchemeRegistry<'a> {
+    /// An empty registry
+    pub const fn new() -> Self {
+        SchemeRegistry {
+            schemes: [None; SCHEME_COUNT],
+        }
+    }
+
+    /// Register `scheme` under `id`, returning the scheme it replaces
+    pub fn register(
+        &mut self,
+        id: SchemeId,
+        scheme: &'a dyn DynPadding,
+    ) -> Option<&'a dyn DynPadding> {
+        self.schemes[id as usize].replace(scheme)
+    }
+
+    /// The scheme registered under the identifier byte `id`
+    pub fn get(&self, id: u8) -> Option<&'a dyn DynPadding> {
+        let id = SchemeId::try_from(id).ok()?;
+        self.schemes[id as usize]
+    }
+
+    /// Pads message with length `pos` in the provided buffer with the scheme registered under
+    /// `id`
+    pub fn pad<'b>(
+        &self,
+        id: u8,
+        buf: &'b mut [u8],
+        pos: usize,
+    ) -> Result<&'b mut [u8], TxPadError> {
+        self.get(id)
+            .ok_or(TxPadError::UnknownScheme { id })?
+            .pad(buf, pos)
+    }
+
+    /// Unpad given `data` with the scheme registered under `id`
+    ///
+    /// An unknown or unregistered `id` is reported as `UnpadError` like malformed padding, so
+    /// a peer cannot tell the two apart.
+    pub fn unpad_auto<'b>(&self, id: u8, data: &'b [u8]) -> Result<&'b [u8], UnpadError> {
+        self.get(id).ok_or(UnpadError)?.unpad(data)
+    }
+}
+
+impl SchemeRegistry<'static> {
+    /// A registry with [`TxPadding`], [`TxRandomTailPadding`] and [`Padme`] of block size `N`
+    /// drawing from the default RNG
+    ///
+    /// [`BucketPadding`](crate::BucketPadding) depends on its table of buckets, so it has to be
+    /// registered separately.
+    #[cfg(any(
+        feature = "os-rng",
+        feature = "thread-rng",
+        feature = "custom-rng",
+        feature = "entropy-source"
+    ))]
+    pub fn with_defaults<N: BlockSize>() -> Self {
+        Self::with_defaults_rng::<N, crate::DefaultRng>()
+    }
+
+    /// A registry with [`TxPadding`], [`TxRandomTailPadding`] and [`Padme`] of block size `N`
+    /// drawing from `R`
+    pub fn with_defaults_rng<N, R>() -> Self
+    where
+        N: BlockSize,
+        R: RngCore + CryptoRng + Default + 'static,
+    {
+        let mut registry = Self::new();
+        registry.register(SchemeId::TxZeroTail, TxPadding::<N, R>::as_dyn());
+        registry.register(
+            SchemeId::TxRandomTail,
+            &PhantomData::<TxRandomTailPadding<N, R>>,
+        );
+        registry.register(SchemeId::Padme, &PhantomData::<Padme<N, R>>);
+        registry
+    }
+}
+
+impl fmt::Debug for SchemeRegistry<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map()
+            .entries(
+                self.schemes
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(id, scheme)| Some((id, scheme.as_ref()?.block_size()))),
+            )
+            .finish()
+    }
+}
@@ -0,0 +1,118 @@
+//! Recording the random bytes of a padded message, to reproduce it exactly
+
+use core::num::NonZeroU32;
+
+use rand::Error;
+
+use crate::{raw, BlockSize, CryptoRng, RngCore, TxPadError, TxPadding};
+
+impl<N: BlockSize, R> TxPadding<N, R> {
+    /// Pads message with length `pos` in the provided buffer like
+    /// [`pad_with_rng`](TxPadding::pad_with_rng), and copies the random bytes drawn from `rng`
+    /// into `record`
+    ///
+    /// Returns the padded message along with the number of bytes recorded, which is at most
+    /// `N + 1`. Padding the same message with a [`ReplayRng`] over the recorded bytes reproduces
+    /// the padded message exactly.
+    pub fn pad_recorded_with_rng<'a>(
+        rng: &mut (impl RngCore + CryptoRng),
+        buf: &'a mut [u8],
+        pos: usize,
+        record: &mut [u8],
+    ) -> Result<(&'a mut [u8], usize), TxPadError> {
+        let pad_len = raw::pad_len(N::USIZE, pos);
+        if record.len() < pad_len {
+            Err(TxPadError::BufferTooSmall {
+                required: pad_len,
+                got: record.len(),
+            })?
+        }
+        let padded_msg = raw::pad(rng, buf, pos, N::USIZE)?;
+        record[..pad_len].copy_from_slice(&padded_msg[1..1 + pad_len]);
+        Ok((padded_msg, pad_len))
+    }
+}
+
+impl<N, R> TxPadding<N, R>
+where
+    N: BlockSize,
+    R: RngCore + CryptoRng + Default,
+{
+    /// Pads message with length `pos` in the provided buffer, and copies the random bytes into
+    /// `record`
+    ///
+    /// ```
+    /// use tx_padding::{InsecureRng, ReplayRng, TxPadding};
+    /// use tx_padding::consts::{U8};
+    ///
+    /// let mut buffer = [0; 16];
+    /// buffer[..4].copy_from_slice(b"test");
+    /// let mut record = [0; 9];
+    /// let (padded_msg, n) = TxPadding::<U8>::pad_recorded(&mut buffer, 4, &mut record).unwrap();
+    ///
+    /// let mut replayed = [0; 16];
+    /// replayed[..4].copy_from_slice(b"test");
+    /// let mut rng = InsecureRng(ReplayRng::new(&record[..n]));
+    /// let replayed_msg = TxPadding::<U8>::pad_with_rng(&mut rng, &mut replayed, 4).unwrap();
+    /// assert_eq!(replayed_msg, padded_msg);
+    /// ```
+    pub fn pad_recorded<'a>(
+        buf: &'a mut [u8],
+        pos: usize,
+        record: &mut [u8],
+    ) -> Result<(&'a mut [u8], usize), TxPadError> {
+        Self::pad_recorded_with_rng(&mut R::default(), buf, pos, record)
+    }
+}
+
+/// RNG handing out previously recorded bytes, to reproduce a padded message in a test
+///
+/// Generating random bytes fails once the recorded bytes are exhausted. It does not implement
+/// `CryptoRng`, as replayed bytes are no longer unpredictable, so it has to be wrapped in
+/// [`InsecureRng`](crate::InsecureRng).
+#[derive(Clone, Debug)]
+pub struct ReplayRng<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> ReplayRng<'a> {
+    /// Create a generator replaying `bytes`
+    pub fn new(bytes: &'a [u8]) -> Self {
+        ReplayRng { bytes }
+    }
+
+    /// The bytes not replayed yet
+    pub fn remaining(&self) -> &'a [u8] {
+        self.bytes
+    }
+}
+
+impl RngCore for ReplayRng<'_> {
+    fn next_u32(&mut self) -> u32 {
+        let mut bytes = [0; 4];
+        self.fill_bytes(&mut bytes);
+        u32::from_le_bytes(bytes)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let mut bytes = [0; 8];
+        self.fill_bytes(&mut bytes);
+        u64::from_le_bytes(bytes)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        if let Err(e) = self.try_fill_bytes(dest) {
+            panic!("{}", e)
+        }
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        if self.bytes.len() < dest.len() {
+            Err(Error::from(NonZeroU32::new(Error::CUSTOM_START).unwrap()))?
+        }
+        let (head, rest) = self.bytes.split_at(dest.len());
+        dest.copy_from_slice(head);
+        self.bytes = rest;
+        Ok(())
+    }
+}
@@ -0,0 +1,110 @@
+//! Padded messages which are scrubbed from memory once no longer needed
+
+use alloc::vec::Vec;
+use core::fmt;
+use core::marker::PhantomData;
+use core::ops::Range;
+
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+use crate::{raw, BlockSize, CryptoRng, RngCore, TxPadError, TxPadding};
+
+/// An owned padded message which is zeroized when dropped
+///
+/// Created by [`TxPadding::pad_zeroizing`]. Neither the message nor the random prefix is exposed
+/// through `Debug`.
+///
+/// ```
+/// use tx_padding::{TxPadding, Padding};
+/// use tx_padding::consts::{U8};
+///
+/// let padded_msg = TxPadding::<U8>::pad_zeroizing(b"secret").unwrap();
+/// assert_eq!(padded_msg.msg(), b"secret");
+/// assert_eq!(TxPadding::<U8>::unpad(padded_msg.as_bytes()).unwrap(), b"secret");
+/// ```
+pub struct SecretPadded<N> {
+    buf: Vec<u8>,
+    msg: Range<usize>,
+    _marker: PhantomData<N>,
+}
+
+impl<N: BlockSize> SecretPadded<N> {
+    /// The padded message
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.buf
+    }
+
+    /// The message without the padding
+    pub fn msg(&self) -> &[u8] {
+        &self.buf[self.msg.clone()]
+    }
+
+    /// Length of the padded message
+    pub fn len(&self) -> usize {
+        self.buf.len()
+    }
+
+    /// Whether the padded message has been zeroized
+    pub fn is_empty(&self) -> bool {
+        self.buf.is_empty()
+    }
+}
+
+impl<N> AsRef<[u8]> for SecretPadded<N> {
+    fn as_ref(&self) -> &[u8] {
+        &self.buf
+    }
+}
+
+impl<N> fmt::Debug for SecretPadded<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SecretPadded").finish_non_exhaustive()
+    }
+}
+
+/// Zeroizing leaves an empty padded message
+impl<N> Zeroize for SecretPadded<N> {
+    fn zeroize(&mut self) {
+        self.buf.zeroize();
+        self.msg = 0..0;
+    }
+}
+
+impl<N> Drop for SecretPadded<N> {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl<N> ZeroizeOnDrop for SecretPadded<N> {}
+
+impl<N: BlockSize, R> TxPadding<N, R> {
+    /// Pad `msg` into a [`SecretPadded`], drawing the random prefix from `rng`
+    ///
+    /// The padded message is written straight into the zeroizing buffer, which is scrubbed
+    /// before the error is returned if padding fails.
+    pub fn pad_zeroizing_with_rng(
+        rng: &mut (impl RngCore + CryptoRng),
+        msg: &[u8],
+    ) -> Result<SecretPadded<N>, TxPadError> {
+        let offset = 1 + raw::pad_len(N::USIZE, msg.len());
+        let mut padded_msg = SecretPadded {
+            buf: alloc::vec![0; raw::padded_len(N::USIZE, msg.len())],
+            msg: offset..offset + msg.len(),
+            _marker: PhantomData,
+        };
+        raw::pad_from(rng, msg, &mut padded_msg.buf, N::USIZE)?;
+        Ok(padded_msg)
+    }
+}
+
+impl<N, R> TxPadding<N, R>
+where
+    N: BlockSize,
+    R: RngCore + CryptoRng + Default,
+{
+    /// Pad `msg` into a [`SecretPadded`]
+    pub fn pad_zeroizing(msg: &[u8]) -> Result<SecretPadded<N>, TxPadError> {
+        Self::pad_zeroizing_with_rng(&mut R::default(), msg)
+    }
+}
@@ -0,0 +1,37 @@
+//! Padding with the random prefix derived from a seed
+
+use rand::SeedableRng;
+use rand_chacha::ChaCha20Rng;
+
+use crate::{BlockSize, TxPadError, TxPadding};
+
+impl<N: BlockSize, R> TxPadding<N, R> {
+    /// Pads message with length `pos` in the provided buffer like
+    /// [`pad_with_rng`](TxPadding::pad_with_rng), with the random prefix taken from the start of
+    /// the ChaCha20 keystream for key `seed` and the 64-bit `nonce`
+    ///
+    /// The same `seed`, `nonce` and message always produce the same output, e.g. for convergent
+    /// storage or for generating test vectors in other languages. The keystream starts at block
+    /// counter 0 and is laid out as in the original ChaCha20 with a 64-bit nonce. A seed must
+    /// never be reused with the same nonce on different messages unless the lengths may leak.
+    ///
+    /// ```
+    /// use tx_padding::{TxPadding, Padding};
+    /// use tx_padding::consts::{U8};
+    ///
+    /// let mut buffer = [0; 16];
+    /// buffer[..4].copy_from_slice(b"test");
+    /// let padded_msg = TxPadding::<U8>::pad_seeded([0; 32], 0, &mut buffer, 4).unwrap();
+    /// assert_eq!(&padded_msg[..], b"\xfa\x76\xb8\xe0\xadtest\x00\x00\x00\x00\x00\x00\x00");
+    /// ```
+    pub fn pad_seeded(
+        seed: [u8; 32],
+        nonce: u64,
+        buf: &mut [u8],
+        pos: usize,
+    ) -> Result<&mut [u8], TxPadError> {
+        let mut rng = ChaCha20Rng::from_seed(seed);
+        rng.set_stream(nonce);
+        Self::pad_with_rng(&mut rng, buf, pos)
+    }
+}
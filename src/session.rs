@@ -0,0 +1,174 @@
+//! Sans-io driver exchanging length-delimited padded messages over any transport
+
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+use core::marker::PhantomData;
+
+use crate::frames::FRAME_HEADER_LEN as HEADER_LEN;
+use crate::{raw, BlockSize, CryptoRng, DefaultRng, RngCore, TxPadError, UnpadError};
+
+/// What a [`Session`] has for its transport
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SessionEvent<'a> {
+    /// Padded bytes to be written to the transport
+    Transmit(&'a [u8]),
+    /// A message unpadded from the received bytes
+    Message(&'a [u8]),
+    /// At least this many more bytes must be received before the next message is complete
+    NeedMoreInput(usize),
+}
+
+/// A state machine padding outgoing messages and unpadding incoming ones, without doing any I/O
+///
+/// The frames are the same as those of `TxPaddingCodec`: the length of the padded message as a
+/// big-endian `u32`, followed by the padded message. Messages are queued with
+/// [`send`](Session::send) and bytes received from the transport are handed over with
+/// [`feed`](Session::feed). [`poll`](Session::poll) then reports the padded bytes to write
+/// first, then every complete message received, and finally how many more bytes are needed.
+/// Blocking and async transports alike just loop over these calls.
+///
+/// The bytes of an event are dropped from the session by the next call to any of these methods.
+///
+/// ```
+/// use tx_padding::{Session, SessionEvent};
+/// use tx_padding::consts::{U16};
+///
+/// let mut alice = Session::<U16>::new();
+/// let mut bob = Session::<U16>::new();
+/// alice.send(b"hello world").unwrap();
+/// while let Ok(SessionEvent::Transmit(bytes)) = alice.poll() {
+///     bob.feed(bytes);
+/// }
+/// assert_eq!(bob.poll().unwrap(), SessionEvent::Message(b"hello world"));
+/// assert_eq!(bob.poll().unwrap(), SessionEvent::NeedMoreInput(4));
+/// ```
+#[derive(Clone, Debug)]
+pub struct Session<N, R = DefaultRng> {
+    outgoing: Vec<u8>,
+    incoming: Vec<u8>,
+    /// Whether `outgoing` was handed out by the last event
+    transmitted: bool,
+    /// Length of the frame at the front of `incoming` handed out by the last event
+    consumed: usize,
+    max_frame_len: usize,
+    _marker: PhantomData<fn() -> (N, R)>,
+}
+
+impl<N: BlockSize, R> Session<N, R> {
+    /// Create a session exchanging padded messages up to 8 MiB
+    pub fn new() -> Self {
+        Self::with_max_frame_len(8 * 1024 * 1024)
+    }
+
+    /// Create a session exchanging padded messages up to `max_frame_len` bytes
+    pub fn with_max_frame_len(max_frame_len: usize) -> Self {
+        Session {
+            outgoing: Vec::new(),
+            incoming: Vec::new(),
+            transmitted: false,
+            consumed: 0,
+            max_frame_len: max_frame_len.min(u32::MAX as usize),
+            _marker: PhantomData,
+        }
+    }
+
+    /// The largest padded message this session sends or accepts
+    pub fn max_frame_len(&self) -> usize {
+        self.max_frame_len
+    }
+
+    /// Queue `msg` to be padded and transmitted, drawing the random prefix from `rng`
+    ///
+    /// Fails with `MessageTooLong` if the padded message exceeds the maximum frame length. Nothing
+    /// is queued on failure.
+    pub fn send_with_rng(
+        &mut self,
+        rng: &mut (impl RngCore + CryptoRng),
+        msg: &[u8],
+    ) -> Result<(), TxPadError> {
+        self.reclaim();
+        let padded_len = raw::checked_padded_len(N::USIZE, msg.len())
+            .filter(|&len| len <= self.max_frame_len)
+            .ok_or(TxPadError::MessageTooLong)?;
+        let header_start = self.outgoing.len();
+        let start = header_start + HEADER_LEN;
+        self.outgoing
+            .extend_from_slice(&(padded_len as u32).to_be_bytes());
+        self.outgoing.resize(start + padded_len, 0);
+        if let Err(e) = raw::pad_from(rng, msg, &mut self.outgoing[start..], N::USIZE) {
+            self.outgoing.truncate(header_start);
+            Err(e)?
+        }
+        Ok(())
+    }
+
+    /// Hand over `input` received from the transport
+    pub fn feed(&mut self, input: &[u8]) {
+        self.reclaim();
+        self.incoming.extend_from_slice(input);
+    }
+
+    /// The next event of the session
+    ///
+    /// Fails if the next frame received is malformed, which is then skipped. A frame whose length
+    /// is zero, not a multiple of the block size or beyond the maximum frame length leaves no way
+    /// to find the next one, so every byte received so far is discarded.
+    pub fn poll(&mut self) -> Result<SessionEvent<'_>, UnpadError> {
+        self.reclaim();
+        if !self.outgoing.is_empty() {
+            self.transmitted = true;
+            return Ok(SessionEvent::Transmit(&self.outgoing));
+        }
+        if self.incoming.len() < HEADER_LEN {
+            return Ok(SessionEvent::NeedMoreInput(
+                HEADER_LEN - self.incoming.len(),
+            ));
+        }
+        let mut header = [0; HEADER_LEN];
+        header.copy_from_slice(&self.incoming[..HEADER_LEN]);
+        let padded_len = match usize::try_from(u32::from_be_bytes(header)) {
+            Ok(len) if len != 0 && len % N::USIZE == 0 && len <= self.max_frame_len => len,
+            _ => {
+                self.incoming.clear();
+                Err(UnpadError)?
+            }
+        };
+        let frame_len = HEADER_LEN + padded_len;
+        if self.incoming.len() < frame_len {
+            return Ok(SessionEvent::NeedMoreInput(frame_len - self.incoming.len()));
+        }
+        self.consumed = frame_len;
+        raw::unpad(&self.incoming[HEADER_LEN..frame_len], N::USIZE)
+            .map(SessionEvent::Message)
+            .map_err(|_| UnpadError)
+    }
+
+    /// Drop the bytes handed out by the last event
+    fn reclaim(&mut self) {
+        if self.transmitted {
+            self.outgoing.clear();
+            self.transmitted = false;
+        }
+        if self.consumed > 0 {
+            self.incoming.drain(..self.consumed);
+            self.consumed = 0;
+        }
+    }
+}
+
+impl<N, R> Session<N, R>
+where
+    N: BlockSize,
+    R: RngCore + CryptoRng + Default,
+{
+    /// Queue `msg` to be padded and transmitted
+    pub fn send(&mut self, msg: &[u8]) -> Result<(), TxPadError> {
+        self.send_with_rng(&mut R::default(), msg)
+    }
+}
+
+impl<N: BlockSize, R> Default for Session<N, R> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,258 @@
+//! Incremental padding and unpadding of messages arriving in chunks
+
+use core::marker::PhantomData;
+
+use crate::{raw, BlockSize, CryptoRng, DefaultRng, RngCore, TxPadError, UnpadError};
+
+/// Largest number of bytes the streaming types hold back: a block plus the longest prefix
+pub(crate) const MAX_PENDING: usize = 256 + 2;
+
+/// Incremental padder for messages which are not available in one piece
+///
+/// The size of the random prefix depends on the length of the message, so the length must be
+/// announced up front. Message bytes are then fed with [`update`](Padder::update), which
+/// outputs every complete block of the padded message, and [`finalize`](Padder::finalize)
+/// outputs the remaining bytes along with the trailing zeros.
+///
+/// ```
+/// # #[cfg(any(feature = "os-rng", feature = "thread-rng"))]
+/// # {
+/// use tx_padding::{Padder, TxPadding, Padding};
+/// use tx_padding::consts::{U8};
+///
+/// let mut padder = Padder::<U8>::new(11).unwrap();
+/// let mut padded_msg = [0; 24];
+/// let mut n = padder.update(b"hello ", &mut padded_msg).unwrap();
+/// n += padder.update(b"world", &mut padded_msg[n..]).unwrap();
+/// n += padder.finalize(&mut padded_msg[n..]).unwrap();
+/// assert_eq!(n, 24);
+/// assert_eq!(TxPadding::<U8>::unpad(&padded_msg).unwrap(), b"hello world");
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct Padder<N, R = DefaultRng> {
+    pending: [u8; MAX_PENDING],
+    pending_len: usize,
+    msg_len: usize,
+    consumed: usize,
+    _marker: PhantomData<fn() -> (N, R)>,
+}
+
+impl<N: BlockSize, R> Padder<N, R> {
+    /// Start padding a message of `msg_len` bytes, drawing the random prefix from `rng`
+    pub fn with_rng(
+        rng: &mut (impl RngCore + CryptoRng),
+        msg_len: usize,
+    ) -> Result<Self, TxPadError> {
+        if msg_len > raw::max_msg_len(N::USIZE) {
+            Err(TxPadError::MessageTooLong)?
+        }
+        let mut pending = [0; MAX_PENDING];
+        let prefix_len = 1 + raw::pad_len(N::USIZE, msg_len);
+        raw::fill_prefix(rng, &mut pending[..prefix_len], N::USIZE)?;
+        Ok(Padder {
+            pending,
+            pending_len: prefix_len,
+            msg_len,
+            consumed: 0,
+            _marker: PhantomData,
+        })
+    }
+
+    /// Total length of the padded output
+    pub fn padded_len(&self) -> usize {
+        raw::padded_len(N::USIZE, self.msg_len)
+    }
+
+    /// Number of bytes `update` outputs when fed `input_len` more bytes
+    pub fn output_len(&self, input_len: usize) -> usize {
+        (self.pending_len + input_len) / N::USIZE * N::USIZE
+    }
+
+    /// Number of bytes `finalize` outputs
+    pub fn final_len(&self) -> usize {
+        self.pending_len + N::USIZE - 1
+    }
+
+    /// Feed the next chunk of the message, writing complete padded blocks into `out`
+    ///
+    /// `out` must hold at least [`output_len(input.len())`](Padder::output_len) bytes. Returns
+    /// the number of bytes written.
+    pub fn update(&mut self, mut input: &[u8], out: &mut [u8]) -> Result<usize, TxPadError> {
+        let expected = self.msg_len - self.consumed;
+        if input.len() > expected {
+            Err(TxPadError::MessageLengthMismatch {
+                expected: self.msg_len,
+                got: self.consumed + input.len(),
+            })?
+        }
+        let required = self.output_len(input.len());
+        if out.len() < required {
+            Err(TxPadError::BufferTooSmall {
+                required,
+                got: out.len(),
+            })?
+        }
+        self.consumed += input.len();
+
+        let block_size = N::USIZE;
+        let mut written = 0;
+        while !input.is_empty() || self.pending_len >= block_size {
+            if self.pending_len >= block_size {
+                let n = self.pending_len / block_size * block_size;
+                out[written..written + n].copy_from_slice(&self.pending[..n]);
+                self.pending.copy_within(n..self.pending_len, 0);
+                self.pending_len -= n;
+                written += n;
+            } else {
+                let n = (block_size - self.pending_len).min(input.len());
+                self.pending[self.pending_len..self.pending_len + n].copy_from_slice(&input[..n]);
+                self.pending_len += n;
+                input = &input[n..];
+            }
+        }
+
+        Ok(written)
+    }
+
+    /// Finish the padded message, writing the remaining bytes into `out`
+    ///
+    /// `out` must hold at least [`final_len()`](Padder::final_len) bytes. Returns the number of
+    /// bytes written.
+    pub fn finalize(self, out: &mut [u8]) -> Result<usize, TxPadError> {
+        if self.consumed != self.msg_len {
+            Err(TxPadError::MessageLengthMismatch {
+                expected: self.msg_len,
+                got: self.consumed,
+            })?
+        }
+        let required = self.final_len();
+        if out.len() < required {
+            Err(TxPadError::BufferTooSmall {
+                required,
+                got: out.len(),
+            })?
+        }
+
+        out[..self.pending_len].copy_from_slice(&self.pending[..self.pending_len]);
+        out[self.pending_len..required].fill(0);
+
+        Ok(required)
+    }
+}
+
+impl<N, R> Padder<N, R>
+where
+    N: BlockSize,
+    R: RngCore + CryptoRng + Default,
+{
+    /// Start padding a message of `msg_len` bytes
+    pub fn new(msg_len: usize) -> Result<Self, TxPadError> {
+        Self::with_rng(&mut R::default(), msg_len)
+    }
+}
+
+/// Incremental unpadder for padded messages which are not available in one piece
+///
+/// Message bytes are output by [`update`](Unpadder::update) as soon as they cannot be part of
+/// the trailing zeros, i.e. everything except the last `N - 1` bytes seen so far. The trailing
+/// zeros and the overall length are only validated by [`finish`](Unpadder::finish), so the
+/// output must not be trusted before it succeeds.
+///
+/// ```
+/// # #[cfg(any(feature = "os-rng", feature = "thread-rng"))]
+/// # {
+/// use tx_padding::{Unpadder, TxPadding, Padding};
+/// use tx_padding::consts::{U8};
+///
+/// let mut buffer = [0; 24];
+/// buffer[..11].copy_from_slice(b"hello world");
+/// let padded_msg = TxPadding::<U8>::pad(&mut buffer, 11, 8).unwrap();
+/// let mut unpadder = Unpadder::<U8>::new();
+/// let mut msg = [0; 24];
+/// let mut n = 0;
+/// for chunk in padded_msg.chunks(5) {
+///     n += unpadder.update(chunk, &mut msg[n..]).unwrap();
+/// }
+/// unpadder.finish().unwrap();
+/// assert_eq!(&msg[..n], b"hello world");
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct Unpadder<N> {
+    started: bool,
+    skip: usize,
+    held: [u8; MAX_PENDING],
+    held_len: usize,
+    _marker: PhantomData<N>,
+}
+
+impl<N: BlockSize> Unpadder<N> {
+    /// Start unpadding a padded message
+    pub fn new() -> Self {
+        Unpadder {
+            started: false,
+            skip: 0,
+            held: [0; MAX_PENDING],
+            held_len: 0,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Feed the next chunk of the padded message, writing message bytes into `out`
+    ///
+    /// `out` must be at least as long as `input`, otherwise `UnpadError` is returned. Returns
+    /// the number of bytes written.
+    pub fn update(&mut self, mut input: &[u8], out: &mut [u8]) -> Result<usize, UnpadError> {
+        if out.len() < input.len() {
+            Err(UnpadError)?
+        }
+        let pad_zero = N::USIZE - 1;
+
+        if !self.started {
+            match input.split_first() {
+                Some((&header, rest)) => {
+                    self.started = true;
+                    self.skip = (header & (pad_zero as u8)) as usize + 2;
+                    input = rest;
+                }
+                None => return Ok(0),
+            }
+        }
+        let n = self.skip.min(input.len());
+        self.skip -= n;
+        input = &input[n..];
+
+        let emit = (self.held_len + input.len()).saturating_sub(pad_zero);
+        let from_held = emit.min(self.held_len);
+        let from_input = emit - from_held;
+        out[..from_held].copy_from_slice(&self.held[..from_held]);
+        out[from_held..emit].copy_from_slice(&input[..from_input]);
+
+        self.held.copy_within(from_held..self.held_len, 0);
+        self.held_len -= from_held;
+        let rest = &input[from_input..];
+        self.held[self.held_len..self.held_len + rest.len()].copy_from_slice(rest);
+        self.held_len += rest.len();
+
+        Ok(emit)
+    }
+
+    /// Validate the end of the padded message
+    pub fn finish(self) -> Result<(), UnpadError> {
+        let pad_zero = N::USIZE - 1;
+        if !self.started || self.skip != 0 || self.held_len != pad_zero {
+            Err(UnpadError)?
+        }
+        if self.held[..self.held_len].iter().any(|&v| v != 0) {
+            Err(UnpadError)?
+        }
+        Ok(())
+    }
+}
+
+impl<N: BlockSize> Default for Unpadder<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
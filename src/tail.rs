@@ -0,0 +1,89 @@
+use core::convert::Infallible;
+use core::marker::PhantomData;
+
+#[cfg(feature = "diagnostics")]
+use crate::UnpadErrorKind;
+use crate::{
+    raw, BlockSize, CryptoRng, DefaultRng, PadError, Padding, RngCore, TxPadError, UnpadError,
+};
+
+/// The sibling of `TxPadding` which stores the number of random bytes in the last byte
+///
+/// The padded message is laid out in reverse: `N - 1` zeros, the message, the random bytes and
+/// finally the trailer byte declaring their number. A consumer reading the padded message front
+/// to back can thus skip the zeros right away and learns where the message ends with the last
+/// byte. The padded length and the validation are the same as for `TxPadding`, but the two
+/// layouts are not interchangeable.
+///
+/// ```
+/// # #[cfg(any(feature = "os-rng", feature = "thread-rng"))]
+/// # {
+/// use tx_padding::{TxTailPadding, Padding};
+/// use tx_padding::consts::{U8};
+///
+/// let mut buffer = [0xff; 16];
+/// buffer[..4].copy_from_slice(b"test");
+/// let padded_msg = TxTailPadding::<U8>::pad(&mut buffer, 4, 8).unwrap();
+/// assert_eq!(&padded_msg[..11], b"\x00\x00\x00\x00\x00\x00\x00test");
+/// assert_eq!((padded_msg[15] & 0x7) + 2, 4);
+/// assert_eq!(TxTailPadding::<U8>::unpad(&padded_msg).unwrap(), b"test");
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub enum TxTailPadding<N, R = DefaultRng> {
+    _Phantom(Infallible, PhantomData<fn() -> (N, R)>),
+}
+
+impl<N: BlockSize, R> TxTailPadding<N, R> {
+    /// Length of the padded output for a message of length `msg_len`
+    pub const fn padded_len(msg_len: usize) -> usize {
+        raw::padded_len(N::USIZE, msg_len)
+    }
+
+    /// Pads message with length `pos` in the provided buffer, drawing the random bytes from `rng`
+    pub fn pad_with_rng<'a>(
+        rng: &mut (impl RngCore + CryptoRng),
+        buf: &'a mut [u8],
+        pos: usize,
+    ) -> Result<&'a mut [u8], TxPadError> {
+        raw::pad_tail(rng, buf, pos, N::USIZE)
+    }
+
+    /// Unpad given `data`, returning the message
+    ///
+    /// `Padding::unpad` delegates here, but this does not require a default RNG.
+    pub fn unpad(data: &[u8]) -> Result<&[u8], UnpadError> {
+        let range = raw::unpad_tail_range(data, N::USIZE).map_err(|_| UnpadError)?;
+        Ok(&data[range])
+    }
+
+    /// Unpad given `data`, reporting why it is malformed on failure
+    #[cfg(feature = "diagnostics")]
+    pub fn unpad_verbose(data: &[u8]) -> Result<&[u8], UnpadErrorKind> {
+        raw::unpad_tail_range(data, N::USIZE).map(|range| &data[range])
+    }
+}
+
+impl<N, R> Padding for TxTailPadding<N, R>
+where
+    N: BlockSize,
+    R: RngCore + CryptoRng + Default,
+{
+    fn pad_block(_block: &mut [u8], _pos: usize) -> Result<(), PadError> {
+        Err(PadError)
+    }
+
+    fn unpad(data: &[u8]) -> Result<&[u8], UnpadError> {
+        Self::unpad(data)
+    }
+
+    fn pad(buf: &mut [u8], pos: usize, block_size: usize) -> Result<&mut [u8], PadError> {
+        if block_size != N::USIZE {
+            Err(TxPadError::BlockSizeMismatch {
+                expected: N::USIZE,
+                got: block_size,
+            })?
+        }
+        Ok(Self::pad_with_rng(&mut R::default(), buf, pos)?)
+    }
+}
@@ -0,0 +1,53 @@
+//! Trace-level events emitted on padding and unpadding under the `tracing` feature
+//!
+//! Without the feature these are identities the compiler removes entirely.
+
+use core::ops::Range;
+
+use crate::error::UnpadErrorKind;
+use crate::TxPadError;
+
+/// Report the outcome of padding a message of length `msg_len`
+#[inline(always)]
+pub(crate) fn padded(
+    block_size: usize,
+    msg_len: usize,
+    result: Result<&mut [u8], TxPadError>,
+) -> Result<&mut [u8], TxPadError> {
+    #[cfg(feature = "tracing")]
+    match &result {
+        Ok(padded_msg) => tracing::trace!(
+            block_size,
+            msg_len,
+            overhead = padded_msg.len() - msg_len,
+            "padded message"
+        ),
+        Err(e) => tracing::trace!(block_size, msg_len, error = %e, "padding failed"),
+    }
+    #[cfg(not(feature = "tracing"))]
+    let _ = (block_size, msg_len);
+    result
+}
+
+/// Report the outcome of unpadding a padded message of length `padded_len`
+#[inline(always)]
+pub(crate) fn unpadded(
+    block_size: usize,
+    padded_len: usize,
+    result: Result<Range<usize>, UnpadErrorKind>,
+) -> Result<Range<usize>, UnpadErrorKind> {
+    #[cfg(feature = "tracing")]
+    match &result {
+        Ok(range) => tracing::trace!(
+            block_size,
+            padded_len,
+            msg_len = range.len(),
+            overhead = padded_len - range.len(),
+            "unpadded message"
+        ),
+        Err(e) => tracing::trace!(block_size, padded_len, error = %e, "unpadding failed"),
+    }
+    #[cfg(not(feature = "tracing"))]
+    let _ = (block_size, padded_len);
+    result
+}
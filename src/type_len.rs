@@ -0,0 +1,27 @@
+//! Padded lengths computed at the type level
+
+use typenum::consts::U2;
+use typenum::operator_aliases::{Add1, Prod, Quot, Sum};
+
+/// Length of the padded output for a message of length `M` with block size `N`, computed at the
+/// type level
+///
+/// This is `N * ((M + 1) / N + 2)`, the same as
+/// [`TxPadding::padded_len`](crate::TxPadding::padded_len), so buffers for messages of a fixed
+/// size can be sized by the compiler.
+///
+/// ```
+/// # #[cfg(any(feature = "os-rng", feature = "thread-rng"))]
+/// # {
+/// use tx_padding::{PaddedLen, TxPadding, Padding};
+/// use tx_padding::consts::{U100, U16};
+/// use typenum::Unsigned;
+///
+/// let mut buffer = [0; PaddedLen::<U100, U16>::USIZE];
+/// assert_eq!(buffer.len(), 128);
+/// buffer[..100].copy_from_slice(&[0x5a; 100]);
+/// let padded_msg = TxPadding::<U16>::pad(&mut buffer, 100, 16).unwrap();
+/// assert_eq!(padded_msg.len(), PaddedLen::<U100, U16>::USIZE);
+/// # }
+/// ```
+pub type PaddedLen<M, N> = Prod<N, Sum<Quot<Add1<M>, N>, U2>>;
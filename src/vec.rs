@@ -0,0 +1,221 @@
+use alloc::vec::Vec;
+use core::fmt;
+use core::marker::PhantomData;
+use core::ops::Deref;
+
+use crate::{
+    raw, BlockSize, CryptoRng, DefaultRng, RngCore, TxPadError, TxPadding, TxPaddingConst,
+    UnpadError,
+};
+
+/// An owned buffer of padded messages, growing as messages are pushed
+///
+/// Each pushed message is padded right after the previous one, so the buffer never has to be
+/// sized by hand. It derefs to the padded bytes.
+///
+/// ```
+/// use tx_padding::{PaddedVec, TxPadding, Padding};
+/// use tx_padding::consts::{U8};
+///
+/// let mut padded = PaddedVec::<U8>::new();
+/// padded.push_message(b"hello").unwrap();
+/// padded.push_message(b"world").unwrap();
+/// assert_eq!(padded.len(), 32);
+/// assert_eq!(TxPadding::<U8>::unpad(&padded[..16]).unwrap(), b"hello");
+/// assert_eq!(TxPadding::<U8>::unpad(&padded[16..]).unwrap(), b"world");
+/// ```
+pub struct PaddedVec<N, R = DefaultRng> {
+    buf: Vec<u8>,
+    _marker: PhantomData<fn() -> (N, R)>,
+}
+
+impl<N: BlockSize, R> PaddedVec<N, R> {
+    /// Create an empty buffer
+    pub fn new() -> Self {
+        Self::with_capacity(0)
+    }
+
+    /// Create an empty buffer with room for `capacity` padded bytes
+    pub fn with_capacity(capacity: usize) -> Self {
+        PaddedVec {
+            buf: Vec::with_capacity(capacity),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Pad `msg` at the end of the buffer, drawing the random prefix from `rng`
+    ///
+    /// Returns the newly padded message. The buffer is left unchanged on failure.
+    pub fn push_message_with_rng(
+        &mut self,
+        rng: &mut (impl RngCore + CryptoRng),
+        msg: &[u8],
+    ) -> Result<&mut [u8], TxPadError> {
+        let start = self.buf.len();
+        let end = raw::checked_padded_len(N::USIZE, msg.len())
+            .and_then(|len| len.checked_add(start))
+            .ok_or(TxPadError::MessageTooLong)?;
+        self.buf.resize(end, 0);
+        if let Err(e) = raw::pad_from(rng, msg, &mut self.buf[start..], N::USIZE) {
+            self.buf.truncate(start);
+            Err(e)?
+        }
+        Ok(&mut self.buf[start..])
+    }
+
+    /// Remove every padded message, keeping the allocation
+    pub fn clear(&mut self) {
+        self.buf.clear();
+    }
+
+    /// The padded bytes
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.buf
+    }
+
+    /// Unwrap the buffer holding the padded bytes
+    pub fn into_vec(self) -> Vec<u8> {
+        self.buf
+    }
+}
+
+impl<N, R> PaddedVec<N, R>
+where
+    N: BlockSize,
+    R: RngCore + CryptoRng + Default,
+{
+    /// Pad `msg` at the end of the buffer
+    pub fn push_message(&mut self, msg: &[u8]) -> Result<&mut [u8], TxPadError> {
+        self.push_message_with_rng(&mut R::default(), msg)
+    }
+}
+
+impl<N: BlockSize, R> Default for PaddedVec<N, R> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Implemented by hand, as deriving would require the marker types `N` and `R` to implement the
+// traits as well, which most RNGs do not
+
+impl<N, R> Clone for PaddedVec<N, R> {
+    fn clone(&self) -> Self {
+        PaddedVec {
+            buf: self.buf.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<N, R> PartialEq for PaddedVec<N, R> {
+    fn eq(&self, other: &Self) -> bool {
+        self.buf == other.buf
+    }
+}
+
+impl<N, R> Eq for PaddedVec<N, R> {}
+
+impl<N, R> fmt::Debug for PaddedVec<N, R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PaddedVec").field("buf", &self.buf).finish()
+    }
+}
+
+impl<N, R> Deref for PaddedVec<N, R> {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        &self.buf
+    }
+}
+
+impl<N, R> AsRef<[u8]> for PaddedVec<N, R> {
+    fn as_ref(&self) -> &[u8] {
+        &self.buf
+    }
+}
+
+impl<N, R> From<PaddedVec<N, R>> for Vec<u8> {
+    fn from(padded: PaddedVec<N, R>) -> Vec<u8> {
+        padded.buf
+    }
+}
+
+impl<N: BlockSize, R> TxPadding<N, R> {
+    /// Unpad `data` into a newly allocated vector
+    pub fn unpad_to_vec(data: &[u8]) -> Result<Vec<u8>, UnpadError> {
+        Self::unpad(data).map(<[u8]>::to_vec)
+    }
+
+    /// Unpad `buf` in place, moving the message to its front and truncating it to the message
+    ///
+    /// The allocation is kept, so `buf` can be reused without copying the message out. `buf` is
+    /// left untouched on failure.
+    ///
+    /// ```
+    /// use tx_padding::TxPadding;
+    /// use tx_padding::consts::{U8};
+    ///
+    /// let mut buf = TxPadding::<U8>::pad_to_vec(b"test").unwrap();
+    /// TxPadding::<U8>::unpad_truncate_vec(&mut buf).unwrap();
+    /// assert_eq!(buf, b"test");
+    /// assert!(buf.capacity() >= 16);
+    /// ```
+    pub fn unpad_truncate_vec(buf: &mut Vec<u8>) -> Result<(), UnpadError> {
+        let len = raw::unpad_to_front(buf, N::USIZE).map_err(|_| UnpadError)?;
+        buf.truncate(len);
+        Ok(())
+    }
+}
+
+impl<N, R> TxPadding<N, R>
+where
+    N: BlockSize,
+    R: RngCore + CryptoRng + Default,
+{
+    /// Pad `msg` into a newly allocated vector of the right size
+    ///
+    /// ```
+    /// use tx_padding::TxPadding;
+    /// use tx_padding::consts::{U8};
+    ///
+    /// let padded_msg = TxPadding::<U8>::pad_to_vec(b"test").unwrap();
+    /// assert_eq!(padded_msg.len(), 16);
+    /// assert_eq!(TxPadding::<U8>::unpad_to_vec(&padded_msg).unwrap(), b"test");
+    /// ```
+    pub fn pad_to_vec(msg: &[u8]) -> Result<Vec<u8>, TxPadError> {
+        let mut buf = alloc::vec![0; Self::padded_len(msg.len())];
+        buf[..msg.len()].copy_from_slice(msg);
+        Self::pad_with_rng(&mut R::default(), &mut buf, msg.len())?;
+        Ok(buf)
+    }
+}
+
+impl<const N: usize, R> TxPaddingConst<N, R> {
+    /// Unpad `data` into a newly allocated vector
+    pub fn unpad_to_vec(data: &[u8]) -> Result<Vec<u8>, UnpadError> {
+        Self::unpad(data).map(<[u8]>::to_vec)
+    }
+
+    /// Unpad `buf` in place, moving the message to its front and truncating it to the message
+    pub fn unpad_truncate_vec(buf: &mut Vec<u8>) -> Result<(), UnpadError> {
+        let () = Self::VALID_BLOCK_SIZE;
+        let len = raw::unpad_to_front(buf, N).map_err(|_| UnpadError)?;
+        buf.truncate(len);
+        Ok(())
+    }
+}
+
+impl<const N: usize, R> TxPaddingConst<N, R>
+where
+    R: RngCore + CryptoRng + Default,
+{
+    /// Pad `msg` into a newly allocated vector of the right size
+    pub fn pad_to_vec(msg: &[u8]) -> Result<Vec<u8>, TxPadError> {
+        let mut buf = alloc::vec![0; Self::padded_len(msg.len())];
+        buf[..msg.len()].copy_from_slice(msg);
+        Self::pad_with_rng(&mut R::default(), &mut buf, msg.len())?;
+        Ok(buf)
+    }
+}
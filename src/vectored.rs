@@ -0,0 +1,119 @@
+//! Padding into separate parts for vectored I/O
+
+use crate::raw::{self, MAX_PREFIX_LEN};
+use crate::{BlockSize, CryptoRng, RngCore, TxPadError, TxPadding};
+
+/// Enough zeros for the tail of any supported block size
+static ZEROS: [u8; 255] = [0; 255];
+
+/// A padded message split into its parts without copying the message
+///
+/// The padded message is the concatenation of [`prefix`](PaddedParts::prefix), i.e. the
+/// [`header`](PaddedParts::header) followed by the [`random`](PaddedParts::random) bytes, the
+/// [`msg`](PaddedParts::msg) itself and [`zero_tail_len`](PaddedParts::zero_tail_len) zeros.
+/// With the `std` feature, [`io_slices`](PaddedParts::io_slices) lays it out for
+/// `Write::write_vectored`.
+///
+/// ```
+/// # #[cfg(any(feature = "os-rng", feature = "thread-rng"))]
+/// # {
+/// use tx_padding::{TxPadding, Padding};
+/// use tx_padding::consts::{U8};
+///
+/// let parts = TxPadding::<U8>::pad_vectored(b"test").unwrap();
+/// let mut padded_msg = Vec::new();
+/// padded_msg.extend_from_slice(parts.prefix());
+/// padded_msg.extend_from_slice(parts.msg());
+/// padded_msg.extend_from_slice(parts.zero_tail());
+/// assert_eq!(padded_msg.len(), parts.len());
+/// assert_eq!(TxPadding::<U8>::unpad(&padded_msg).unwrap(), b"test");
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct PaddedParts<'a> {
+    prefix: [u8; MAX_PREFIX_LEN],
+    prefix_len: usize,
+    msg: &'a [u8],
+    zero_tail_len: usize,
+}
+
+impl<'a> PaddedParts<'a> {
+    /// The header byte storing the number of random bytes
+    pub fn header(&self) -> [u8; 1] {
+        [self.prefix[0]]
+    }
+
+    /// The random bytes following the header
+    pub fn random(&self) -> &[u8] {
+        &self.prefix[1..self.prefix_len]
+    }
+
+    /// The header followed by the random bytes
+    pub fn prefix(&self) -> &[u8] {
+        &self.prefix[..self.prefix_len]
+    }
+
+    /// The message
+    pub fn msg(&self) -> &'a [u8] {
+        self.msg
+    }
+
+    /// Number of zeros following the message
+    pub fn zero_tail_len(&self) -> usize {
+        self.zero_tail_len
+    }
+
+    /// The zeros following the message
+    pub fn zero_tail(&self) -> &'static [u8] {
+        &ZEROS[..self.zero_tail_len]
+    }
+
+    /// Total length of the padded message
+    pub fn len(&self) -> usize {
+        self.prefix_len + self.msg.len() + self.zero_tail_len
+    }
+
+    /// Always `false`, a padded message is never empty
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+
+    /// The parts of the padded message as slices for `Write::write_vectored`
+    #[cfg(feature = "std")]
+    pub fn io_slices(&self) -> [std::io::IoSlice<'_>; 3] {
+        [
+            std::io::IoSlice::new(self.prefix()),
+            std::io::IoSlice::new(self.msg),
+            std::io::IoSlice::new(self.zero_tail()),
+        ]
+    }
+}
+
+impl<N: BlockSize, R> TxPadding<N, R> {
+    /// Pad `msg` without copying it, drawing the random prefix from `rng`
+    pub fn pad_vectored_with_rng<'a>(
+        rng: &mut (impl RngCore + CryptoRng),
+        msg: &'a [u8],
+    ) -> Result<PaddedParts<'a>, TxPadError> {
+        let mut prefix = [0; MAX_PREFIX_LEN];
+        let prefix_len = 1 + raw::pad_len(N::USIZE, msg.len());
+        raw::fill_prefix(rng, &mut prefix[..prefix_len], N::USIZE)?;
+        Ok(PaddedParts {
+            prefix,
+            prefix_len,
+            msg,
+            zero_tail_len: N::USIZE - 1,
+        })
+    }
+}
+
+impl<N, R> TxPadding<N, R>
+where
+    N: BlockSize,
+    R: RngCore + CryptoRng + Default,
+{
+    /// Pad `msg` without copying it
+    pub fn pad_vectored(msg: &[u8]) -> Result<PaddedParts<'_>, TxPadError> {
+        Self::pad_vectored_with_rng(&mut R::default(), msg)
+    }
+}
@@ -0,0 +1,236 @@
+//! Test vectors in JSON, for validating other implementations of the scheme against this one
+//!
+//! A vector lists the block size, the random bytes of the prefix, the message and the padded
+//! message, all byte strings in hex. Valid vectors must pad to `padded` when the random bytes
+//! are drawn in order and unpad back to `msg`. Invalid vectors carry no random bytes nor
+//! message, and `padded` must be rejected when unpadding.
+//!
+//! ```json
+//! {
+//!   "algorithm": "tx-padding",
+//!   "vectors": [
+//!     {
+//!       "id": 1,
+//!       "comment": "message of length 0",
+//!       "block_size": 2,
+//!       "random": "000d",
+//!       "msg": "",
+//!       "padded": "fe000d00",
+//!       "result": "valid"
+//!     }
+//!   ]
+//! }
+//! ```
+
+use std::string::{String, ToString};
+use std::vec::Vec;
+use std::{format, vec};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{InsecureRng, ReplayRng, TxPaddingDyn};
+
+/// Whether a vector must be accepted or rejected
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Expected {
+    /// Padding reproduces `padded`, and unpadding it recovers `msg`
+    Valid,
+    /// Unpadding `padded` fails
+    Invalid,
+}
+
+/// A single test vector
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TestVector {
+    /// Number of the vector within its file
+    pub id: usize,
+    /// What the vector exercises
+    pub comment: String,
+    /// Block size the message is padded to
+    pub block_size: usize,
+    /// Random bytes of the prefix, in the order they are drawn
+    #[serde(with = "hex_bytes")]
+    pub random: Vec<u8>,
+    /// The message
+    #[serde(with = "hex_bytes")]
+    pub msg: Vec<u8>,
+    /// The padded message
+    #[serde(with = "hex_bytes")]
+    pub padded: Vec<u8>,
+    /// Whether the vector must be accepted or rejected
+    pub result: Expected,
+}
+
+/// A file of test vectors
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TestVectors {
+    /// Always `tx-padding`
+    pub algorithm: String,
+    /// The vectors
+    pub vectors: Vec<TestVector>,
+}
+
+impl TestVector {
+    /// Create a valid vector by padding `msg` with the prefix made of `random`
+    ///
+    /// Fails if the number of random bytes is not the one required for `msg` and `block_size`.
+    pub fn new(
+        id: usize,
+        comment: &str,
+        block_size: usize,
+        random: &[u8],
+        msg: &[u8],
+    ) -> Result<Self, String> {
+        let padded_len = <TxPaddingDyn>::padded_len(msg.len(), block_size)
+            .ok_or_else(|| format!("invalid block size {}", block_size))?;
+        let mut padded = vec![0; padded_len];
+        padded[..msg.len()].copy_from_slice(msg);
+        let mut rng = InsecureRng(ReplayRng::new(random));
+        <TxPaddingDyn>::pad_with_rng(&mut rng, &mut padded, msg.len(), block_size)
+            .map_err(|e| e.to_string())?;
+        if !rng.0.remaining().is_empty() {
+            Err("too many random bytes")?
+        }
+        Ok(TestVector {
+            id,
+            comment: comment.to_string(),
+            block_size,
+            random: random.to_vec(),
+            msg: msg.to_vec(),
+            padded,
+            result: Expected::Valid,
+        })
+    }
+
+    /// Create an invalid vector, whose `padded` must be rejected
+    pub fn invalid(id: usize, comment: &str, block_size: usize, padded: &[u8]) -> Self {
+        TestVector {
+            id,
+            comment: comment.to_string(),
+            block_size,
+            random: Vec::new(),
+            msg: Vec::new(),
+            padded: padded.to_vec(),
+            result: Expected::Invalid,
+        }
+    }
+
+    /// Check this implementation against the vector
+    pub fn check(&self) -> Result<(), String> {
+        let unpadded = <TxPaddingDyn>::unpad(&self.padded, self.block_size);
+        match self.result {
+            Expected::Valid => {
+                let expected = Self::new(
+                    self.id,
+                    &self.comment,
+                    self.block_size,
+                    &self.random,
+                    &self.msg,
+                )?;
+                if expected.padded != self.padded {
+                    Err(format!("vector {}: padded message mismatch", self.id))?
+                }
+                if unpadded.ok() != Some(&self.msg[..]) {
+                    Err(format!("vector {}: unpadding failed", self.id))?
+                }
+            }
+            Expected::Invalid => {
+                if unpadded.is_ok() {
+                    Err(format!("vector {}: invalid padding accepted", self.id))?
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl TestVectors {
+    /// The reference set of vectors, covering every block size
+    pub fn generate() -> Self {
+        let mut vectors = Vec::new();
+        let mut id = 0;
+        let mut next_id = || {
+            id += 1;
+            id
+        };
+        for shift in 1..=8 {
+            let block_size = 1 << shift;
+            let mut msg_lens = vec![0, 1, block_size - 2, block_size - 1, block_size];
+            msg_lens.push(3 * block_size + 5);
+            msg_lens.sort_unstable();
+            msg_lens.dedup();
+            for msg_len in msg_lens {
+                let msg: Vec<u8> = (0..msg_len).map(|i| (i * 7 + 3) as u8).collect();
+                let pad_len =
+                    <TxPaddingDyn>::padded_len(msg_len, block_size).unwrap() - msg_len - block_size;
+                let random: Vec<u8> = (0..pad_len).map(|i| (i * 13 + msg_len) as u8).collect();
+                let comment = format!("message of length {}", msg_len);
+                let vector = TestVector::new(next_id(), &comment, block_size, &random, &msg);
+                vectors.push(vector.unwrap());
+            }
+
+            let valid = vectors.last().unwrap().padded.clone();
+            vectors.push(TestVector::invalid(
+                next_id(),
+                "empty input",
+                block_size,
+                &[],
+            ));
+            let mut tail = valid.clone();
+            if block_size > 2 {
+                *tail.last_mut().unwrap() = 1;
+                vectors.push(TestVector::invalid(
+                    next_id(),
+                    "non-zero tail",
+                    block_size,
+                    &tail,
+                ));
+            }
+            let truncated = &valid[..block_size / 2];
+            vectors.push(TestVector::invalid(
+                next_id(),
+                "truncated",
+                block_size,
+                truncated,
+            ));
+        }
+        TestVectors {
+            algorithm: "tx-padding".to_string(),
+            vectors,
+        }
+    }
+
+    /// Serialize the vectors as pretty-printed JSON
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap()
+    }
+
+    /// Parse vectors from JSON
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    /// Check this implementation against every vector
+    pub fn check(&self) -> Result<(), String> {
+        self.vectors.iter().try_for_each(TestVector::check)
+    }
+}
+
+/// Serialization of byte strings as hex
+mod hex_bytes {
+    use std::string::String;
+    use std::vec::Vec;
+
+    use serde::{de, Deserialize, Deserializer, Serializer};
+
+    pub(super) fn serialize<S: Serializer>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&hex::encode(bytes))
+    }
+
+    pub(super) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<u8>, D::Error> {
+        hex::decode(String::deserialize(deserializer)?).map_err(de::Error::custom)
+    }
+}
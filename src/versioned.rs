@@ -0,0 +1,78 @@
+//! Padding with a scheme version stored in the header
+
+use typenum::consts::U64;
+use typenum::marker_traits::NonZero;
+use typenum::operator_aliases::LeEq;
+use typenum::type_operators::IsLessOrEqual;
+
+#[cfg(feature = "diagnostics")]
+use crate::UnpadErrorKind;
+use crate::{raw, BlockSize, CryptoRng, RngCore, TxPadError, TxPadding, UnpadError};
+
+/// The versioned methods need 2 spare bits in the header, which exist for block sizes up to 64
+impl<N, R> TxPadding<N, R>
+where
+    N: BlockSize + IsLessOrEqual<U64>,
+    LeEq<N, U64>: NonZero,
+{
+    /// Pads message with length `pos` in the provided buffer and stores the scheme `version` in
+    /// the header, drawing the random prefix from `rng`
+    ///
+    /// `version` must be at most 3. It occupies the 2 bits of the first byte right above the
+    /// number of random bytes, which are otherwise set to ones, so a downstream protocol can
+    /// tell revisions of its padding format apart.
+    ///
+    /// ```
+    /// use tx_padding::{InsecureRng, Padding, TxPadding};
+    /// use tx_padding::consts::{U8};
+    /// use rand::rngs::mock::StepRng;
+    ///
+    /// let mut rng = InsecureRng(StepRng::new(0, 0));
+    /// let mut buffer = [0; 16];
+    /// buffer[..4].copy_from_slice(b"test");
+    /// let padded_msg =
+    ///     TxPadding::<U8>::pad_versioned_with_rng(&mut rng, &mut buffer, 4, 1).unwrap();
+    /// assert_eq!(padded_msg[0], 0xea);
+    /// assert_eq!(TxPadding::<U8>::unpad_versioned(padded_msg, 1).unwrap(), b"test");
+    /// assert!(TxPadding::<U8>::unpad_versioned(padded_msg, 0).is_err());
+    /// assert_eq!(TxPadding::<U8>::unpad(padded_msg).unwrap(), b"test");
+    /// ```
+    pub fn pad_versioned_with_rng<'a>(
+        rng: &mut (impl RngCore + CryptoRng),
+        buf: &'a mut [u8],
+        pos: usize,
+        version: u8,
+    ) -> Result<&'a mut [u8], TxPadError> {
+        if version > raw::MAX_VERSION {
+            Err(TxPadError::InvalidVersion { got: version })?
+        }
+        let padded_msg = raw::pad(rng, buf, pos, N::USIZE)?;
+        raw::set_version(&mut padded_msg[0], version, N::USIZE);
+        Ok(padded_msg)
+    }
+
+    /// Unpad given `data`, additionally requiring the header to store the scheme `version`
+    pub fn unpad_versioned(data: &[u8], version: u8) -> Result<&[u8], UnpadError> {
+        raw::unpad_versioned(data, N::USIZE, version).map_err(|_| UnpadError)
+    }
+
+    /// Unpad given `data` like [`unpad_versioned`](TxPadding::unpad_versioned), reporting why it
+    /// is malformed on failure
+    #[cfg(feature = "diagnostics")]
+    pub fn unpad_versioned_verbose(data: &[u8], version: u8) -> Result<&[u8], UnpadErrorKind> {
+        raw::unpad_versioned(data, N::USIZE, version)
+    }
+}
+
+impl<N, R> TxPadding<N, R>
+where
+    N: BlockSize + IsLessOrEqual<U64>,
+    LeEq<N, U64>: NonZero,
+    R: RngCore + CryptoRng + Default,
+{
+    /// Pads message with length `pos` in the provided buffer and stores the scheme `version` in
+    /// the header
+    pub fn pad_versioned(buf: &mut [u8], pos: usize, version: u8) -> Result<&mut [u8], TxPadError> {
+        Self::pad_versioned_with_rng(&mut R::default(), buf, pos, version)
+    }
+}
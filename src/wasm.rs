@@ -0,0 +1,36 @@
+//! Bindings for JavaScript, padding with the runtime block size of `TxPaddingDyn`
+//!
+//! Built with `wasm-bindgen`, the functions of this module are exported to JavaScript taking and
+//! returning `Uint8Array`s, with errors thrown as `Error`s. The `wasm-js` feature also enables
+//! the `crypto.getRandomValues` backend of the OS generator on `wasm32-unknown-unknown`.
+
+use alloc::string::ToString;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use wasm_bindgen::prelude::*;
+
+use crate::TxPaddingDyn;
+
+/// Largest number of bytes padding adds to a message
+const MAX_OVERHEAD: usize = 1 + 257 + 255;
+
+/// Pad `msg` to a multiple of `block_size`, which must be a power of 2 between 2 and 256
+#[wasm_bindgen]
+pub fn pad(msg: &[u8], block_size: usize) -> Result<Vec<u8>, JsError> {
+    let mut buf = vec![0; msg.len() + MAX_OVERHEAD];
+    buf[..msg.len()].copy_from_slice(msg);
+    let len = <TxPaddingDyn>::pad(&mut buf, msg.len(), block_size)
+        .map_err(|e| JsError::new(&e.to_string()))?
+        .len();
+    buf.truncate(len);
+    Ok(buf)
+}
+
+/// Unpad `data` padded to a multiple of `block_size`
+#[wasm_bindgen]
+pub fn unpad(data: &[u8], block_size: usize) -> Result<Vec<u8>, JsError> {
+    <TxPaddingDyn>::unpad(data, block_size)
+        .map(<[u8]>::to_vec)
+        .map_err(|_| JsError::new("invalid padding"))
+}
@@ -0,0 +1,140 @@
+use core::convert::Infallible;
+use core::marker::PhantomData;
+use core::ops::Range;
+
+use crate::error::UnpadErrorKind;
+use crate::{
+    raw, CryptoRng, DefaultRng, PadError, Padding, RngCore, TxPadError, UnpadError, WideBlockSize,
+};
+
+/// Length of the header storing the number of random bytes
+const HEADER_LEN: usize = 2;
+
+/// `TxPadding` with a two-byte header, supporting block sizes up to 65536
+///
+/// The number of random bytes minus 2 is stored in the lower `log(N)` bits of the big-endian
+/// 16-bit header and the rest of its bits are ones. Apart from the header, the layout is the same
+/// as `TxPadding`: random bytes, the message and `N - 1` zeros. The padded output is
+/// `N * ((msg_len + 2) / N + 2)` bytes long.
+///
+/// ```
+/// # #[cfg(any(feature = "os-rng", feature = "thread-rng"))]
+/// # {
+/// use tx_padding::{TxPaddingWide, Padding};
+/// use tx_padding::consts::{U4096};
+///
+/// let mut buffer = [0; 8192];
+/// buffer[..4].copy_from_slice(b"test");
+/// let padded_msg = TxPaddingWide::<U4096>::pad(&mut buffer, 4, 4096).unwrap();
+/// assert_eq!(padded_msg.len(), 8192);
+/// assert_eq!(TxPaddingWide::<U4096>::unpad(padded_msg).unwrap(), b"test");
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub enum TxPaddingWide<N, R = DefaultRng> {
+    _Phantom(Infallible, PhantomData<fn() -> (N, R)>),
+}
+
+impl<N: WideBlockSize, R> TxPaddingWide<N, R> {
+    /// Length of the padded output for a message of length `msg_len`
+    ///
+    /// This is also the minimal length of the buffer passed to the padding methods.
+    pub const fn padded_len(msg_len: usize) -> usize {
+        N::USIZE * ((msg_len + HEADER_LEN) / N::USIZE + 2)
+    }
+
+    /// Length of the padded output for a message of length `msg_len`, or `None` if it overflows
+    fn checked_padded_len(msg_len: usize) -> Option<usize> {
+        // (msg_len + HEADER_LEN) / N without overflowing on usize::MAX
+        let blocks = msg_len / N::USIZE + (msg_len % N::USIZE + HEADER_LEN) / N::USIZE;
+        blocks.checked_add(2)?.checked_mul(N::USIZE)
+    }
+
+    /// Pads message with length `pos` in the provided buffer, drawing the random prefix from `rng`
+    pub fn pad_with_rng<'a>(
+        rng: &mut (impl RngCore + CryptoRng),
+        buf: &'a mut [u8],
+        pos: usize,
+    ) -> Result<&'a mut [u8], TxPadError> {
+        raw::check_pos(buf.len(), pos)?;
+        let be = match Self::checked_padded_len(pos) {
+            Some(be) if be <= isize::MAX as usize => be,
+            _ => Err(TxPadError::MessageTooLong)?,
+        };
+        if buf.len() < be {
+            Err(TxPadError::BufferTooSmall {
+                required: be,
+                got: buf.len(),
+            })?
+        }
+
+        let pad_zero = N::USIZE - 1;
+        // (-pos - 3) mod N, computed without overflowing
+        let pad_len = (N::USIZE - (pos % N::USIZE + HEADER_LEN + 1) % N::USIZE) % N::USIZE + 2;
+        let offset = HEADER_LEN + pad_len;
+        buf.copy_within(..pos, offset);
+        rng.try_fill_bytes(&mut buf[HEADER_LEN..offset])
+            .map_err(|_| TxPadError::RngFailure)?;
+        let header = !(pad_zero as u16) | (pad_len - 2) as u16;
+        buf[..HEADER_LEN].copy_from_slice(&header.to_be_bytes());
+        buf[be - pad_zero..be].fill(0);
+
+        Ok(&mut buf[..be])
+    }
+
+    /// Unpad given `data`, returning the message
+    ///
+    /// `Padding::unpad` delegates here, but this does not require a default RNG.
+    pub fn unpad(data: &[u8]) -> Result<&[u8], UnpadError> {
+        let range = Self::unpad_range(data).map_err(|_| UnpadError)?;
+        Ok(&data[range])
+    }
+
+    /// Unpad given `data`, reporting why it is malformed on failure
+    #[cfg(feature = "diagnostics")]
+    pub fn unpad_verbose(data: &[u8]) -> Result<&[u8], UnpadErrorKind> {
+        Self::unpad_range(data).map(|range| &data[range])
+    }
+
+    /// Position of the message in the padded `data`
+    fn unpad_range(data: &[u8]) -> Result<Range<usize>, UnpadErrorKind> {
+        let l = data.len();
+        let header = match data {
+            [] => Err(UnpadErrorKind::EmptyInput)?,
+            [_] => Err(UnpadErrorKind::TooShort)?,
+            [a, b, ..] => u16::from_be_bytes([*a, *b]),
+        };
+        let pad_zero = N::USIZE - 1;
+        let pad_len = (header & pad_zero as u16) as usize + 2;
+        if l < HEADER_LEN + pad_len + pad_zero {
+            Err(UnpadErrorKind::TooShort)?
+        }
+        raw::check_zeros(data, l - pad_zero..l, 0)?;
+
+        Ok(HEADER_LEN + pad_len..l - pad_zero)
+    }
+}
+
+impl<N, R> Padding for TxPaddingWide<N, R>
+where
+    N: WideBlockSize,
+    R: RngCore + CryptoRng + Default,
+{
+    fn pad_block(_block: &mut [u8], _pos: usize) -> Result<(), PadError> {
+        Err(PadError)
+    }
+
+    fn unpad(data: &[u8]) -> Result<&[u8], UnpadError> {
+        Self::unpad(data)
+    }
+
+    fn pad(buf: &mut [u8], pos: usize, block_size: usize) -> Result<&mut [u8], PadError> {
+        if block_size != N::USIZE {
+            Err(TxPadError::BlockSizeMismatch {
+                expected: N::USIZE,
+                got: block_size,
+            })?
+        }
+        Ok(Self::pad_with_rng(&mut R::default(), buf, pos)?)
+    }
+}
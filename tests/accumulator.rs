@@ -0,0 +1,79 @@
+//! Test collecting padded frames byte by byte
+#![no_std]
+
+use tx_padding::consts;
+#[cfg(any(feature = "os-rng", feature = "thread-rng"))]
+use tx_padding::TxPadding;
+use tx_padding::{FrameAccumulator, TxPadError};
+
+#[cfg(any(feature = "os-rng", feature = "thread-rng"))]
+#[test]
+fn accumulate_frames() {
+    let msg = [0x5a; 20];
+    let mut acc = FrameAccumulator::<consts::U8, 40>::new(4).unwrap();
+    assert_eq!(acc.frame_len(), 32);
+    for n in 15..=msg.len() {
+        let mut padded_msg = [0; 32];
+        TxPadding::<consts::U8>::pad_into(&msg[..n], &mut padded_msg).unwrap();
+        for (i, &byte) in padded_msg.iter().enumerate() {
+            assert!(acc.take_message().is_none());
+            assert_eq!(acc.push(byte), i == 31);
+            assert_eq!(acc.len(), i + 1);
+        }
+        assert!(acc.is_complete());
+        assert_eq!(acc.take_message().unwrap().unwrap(), &msg[..n]);
+        assert!(acc.is_empty());
+        assert!(acc.take_message().is_none());
+    }
+}
+
+#[cfg(any(feature = "os-rng", feature = "thread-rng"))]
+#[test]
+fn drop_bytes_past_complete_frame() {
+    let mut padded_msg = [0; 16];
+    TxPadding::<consts::U8>::pad_into(b"test", &mut padded_msg).unwrap();
+    let mut acc = FrameAccumulator::<consts::U8, 16>::new(2).unwrap();
+    for &byte in &padded_msg {
+        acc.push(byte);
+    }
+    assert!(acc.push(0xff));
+    assert_eq!(acc.len(), 16);
+    assert_eq!(acc.take_message().unwrap().unwrap(), b"test");
+}
+
+#[test]
+fn discard_malformed_frame() {
+    let mut acc = FrameAccumulator::<consts::U8, 16>::new(2).unwrap();
+    for _ in 0..15 {
+        acc.push(0xff);
+    }
+    acc.clear();
+    assert!(acc.is_empty());
+    for _ in 0..16 {
+        acc.push(0xff);
+    }
+    assert!(acc.take_message().unwrap().is_err());
+    assert!(acc.is_empty());
+}
+
+#[test]
+fn reject_unfitting_frame_len() {
+    assert_eq!(
+        FrameAccumulator::<consts::U8, 16>::new(3).unwrap_err(),
+        TxPadError::BufferTooSmall {
+            required: 24,
+            got: 16
+        }
+    );
+    assert_eq!(
+        FrameAccumulator::<consts::U8, 16>::new(1).unwrap_err(),
+        TxPadError::BufferTooSmall {
+            required: 16,
+            got: 8
+        }
+    );
+    assert_eq!(
+        FrameAccumulator::<consts::U8, 16>::new(usize::MAX).unwrap_err(),
+        TxPadError::MessageTooLong
+    );
+}
@@ -0,0 +1,60 @@
+//! Test padding to a multiple of several blocks
+#![no_std]
+
+use rand::rngs::mock::StepRng;
+
+use tx_padding::consts;
+use tx_padding::{InsecureRng, TxPadError, TxPadding};
+
+#[test]
+fn pad_aligned_round_trip() {
+    let msg = [0x5a; 600];
+    for &blocks in &[1, 3, 32] {
+        for n in 0..600 {
+            let mut buf = [0xff; 2048];
+            buf[..n].copy_from_slice(&msg[..n]);
+            let padded_msg = TxPadding::<consts::U16>::pad_aligned_with_rng(
+                &mut InsecureRng(StepRng::new(1, 1)),
+                &mut buf,
+                n,
+                blocks,
+            )
+            .unwrap();
+            let l = padded_msg.len();
+            assert_eq!(
+                Some(l),
+                TxPadding::<consts::U16>::padded_aligned_len(n, blocks)
+            );
+            assert_eq!(l % (16 * blocks), 0);
+            assert!(l < TxPadding::<consts::U16>::padded_len(8 + n) + 16 * blocks);
+            assert!(padded_msg[l - 15..].iter().all(|&v| v == 0));
+            assert_eq!(
+                TxPadding::<consts::U16>::unpad_aligned(padded_msg).unwrap(),
+                &msg[..n]
+            );
+        }
+    }
+}
+
+#[test]
+fn reject_zero_blocks() {
+    let mut buf = [0; 64];
+    assert_eq!(TxPadding::<consts::U16>::padded_aligned_len(3, 0), None);
+    assert_eq!(
+        TxPadding::<consts::U16>::pad_aligned_with_rng(
+            &mut InsecureRng(StepRng::new(1, 1)),
+            &mut buf,
+            3,
+            0
+        ),
+        Err(TxPadError::InvalidBlockSize { got: 0 })
+    );
+    assert_eq!(
+        TxPadding::<consts::U16>::padded_aligned_len(3, usize::MAX),
+        None
+    );
+    assert_eq!(
+        TxPadding::<consts::U16>::padded_aligned_len(3, isize::MAX as usize / 16 + 1),
+        None
+    );
+}
@@ -0,0 +1,70 @@
+//! Test the variant supporting block sizes which are not powers of 2
+#![no_std]
+
+use rand::rngs::mock::StepRng;
+
+use tx_padding::consts;
+use tx_padding::{InsecureRng, TxPadding, TxPaddingAnySize};
+
+macro_rules! test_any_size_round_trip {
+    ($name:ident, $n:ty, $block_size:expr) => {
+        #[test]
+        fn $name() {
+            let msg = [0x5a; 300];
+            let mut buf = [0xff; 1024];
+            for n in 0..300 {
+                buf[..n].copy_from_slice(&msg[..n]);
+                let padded_msg = TxPaddingAnySize::<$n>::pad_with_rng(
+                    &mut InsecureRng(StepRng::new(1, 1)),
+                    &mut buf,
+                    n,
+                )
+                .unwrap();
+                let l = padded_msg.len();
+                assert_eq!(l, TxPaddingAnySize::<$n>::padded_len(n));
+                assert_eq!(l % $block_size, 0);
+                assert!((padded_msg[0] as usize) < $block_size);
+                assert!(padded_msg[l - ($block_size - 1)..].iter().all(|&v| v == 0));
+                assert_eq!(
+                    TxPaddingAnySize::<$n>::unpad(padded_msg).unwrap(),
+                    &msg[..n]
+                );
+            }
+        }
+    };
+}
+
+test_any_size_round_trip!(any_size_round_trip_u3, consts::U3, 3);
+test_any_size_round_trip!(any_size_round_trip_u24, consts::U24, 24);
+test_any_size_round_trip!(any_size_round_trip_u48, consts::U48, 48);
+test_any_size_round_trip!(any_size_round_trip_u255, consts::U255, 255);
+test_any_size_round_trip!(any_size_round_trip_u256, consts::U256, 256);
+
+#[test]
+fn agree_with_power_of_two_body() {
+    for n in 0..16 {
+        let mut a = [0x5a; 64];
+        let mut b = [0x5a; 64];
+        let padded_a =
+            TxPadding::<consts::U16>::pad_with_rng(&mut InsecureRng(StepRng::new(1, 1)), &mut a, n)
+                .unwrap();
+        let padded_b = TxPaddingAnySize::<consts::U16>::pad_with_rng(
+            &mut InsecureRng(StepRng::new(1, 1)),
+            &mut b,
+            n,
+        )
+        .unwrap();
+        assert_eq!(padded_a[1..], padded_b[1..]);
+        assert_eq!(padded_a[0] & 0xf, padded_b[0]);
+    }
+}
+
+#[cfg(any(feature = "os-rng", feature = "thread-rng"))]
+#[test]
+fn reject_invalid_header() {
+    let mut data = [0; 72];
+    data[0] = 23;
+    assert!(TxPaddingAnySize::<consts::U24>::unpad(&data).is_ok());
+    data[0] = 24;
+    assert!(TxPaddingAnySize::<consts::U24>::unpad(&data).is_err());
+}
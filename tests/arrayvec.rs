@@ -0,0 +1,38 @@
+//! Test padding into and unpadding from fixed-capacity `arrayvec` vectors
+#![cfg(feature = "arrayvec")]
+#![no_std]
+
+use tx_padding::consts;
+use tx_padding::{TxPadError, TxPadding, TxPaddingConst};
+
+#[test]
+fn pad_arrayvec_sizes() {
+    let msg = [0x5a; 40];
+    for n in 0..msg.len() {
+        let padded_msg = TxPadding::<consts::U8>::pad_arrayvec::<64>(&msg[..n]).unwrap();
+        assert_eq!(padded_msg.len(), TxPadding::<consts::U8>::padded_len(n));
+        assert_eq!(
+            TxPadding::<consts::U8>::unpad_arrayvec::<40>(&padded_msg).unwrap(),
+            msg[..n]
+        );
+        let padded_msg = TxPaddingConst::<8>::pad_arrayvec::<64>(&msg[..n]).unwrap();
+        assert_eq!(
+            TxPaddingConst::<8>::unpad_arrayvec::<40>(&padded_msg).unwrap(),
+            msg[..n]
+        );
+    }
+}
+
+#[test]
+fn reject_insufficient_capacity() {
+    assert_eq!(
+        TxPadding::<consts::U8>::pad_arrayvec::<15>(b"test"),
+        Err(TxPadError::BufferTooSmall {
+            required: 16,
+            got: 15
+        })
+    );
+    let padded_msg = TxPadding::<consts::U8>::pad_arrayvec::<16>(b"test").unwrap();
+    assert!(TxPadding::<consts::U8>::unpad_arrayvec::<3>(&padded_msg).is_err());
+    assert!(TxPadding::<consts::U8>::unpad_arrayvec::<3>(&[0xf8, 0, 0]).is_err());
+}
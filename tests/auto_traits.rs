@@ -0,0 +1,81 @@
+//! Test that the RNG type parameter does not affect the auto traits of the padding types
+#![no_std]
+
+use core::marker::PhantomData;
+
+use rand::rngs::mock::StepRng;
+use rand::{CryptoRng, Error, RngCore};
+
+use tx_padding::consts::U16;
+use tx_padding::{Padder, TxPadding, TxPaddingConst, TxPaddingDyn, Unpadder};
+
+/// A generator which is neither `Send` nor `Sync`, like `ThreadRng`
+struct LocalRng(StepRng, PhantomData<*const u8>);
+
+impl Default for LocalRng {
+    fn default() -> Self {
+        LocalRng(StepRng::new(0, 1), PhantomData)
+    }
+}
+
+impl RngCore for LocalRng {
+    fn next_u32(&mut self) -> u32 {
+        self.0.next_u32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0.next_u64()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.0.fill_bytes(dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.0.try_fill_bytes(dest)
+    }
+}
+
+impl CryptoRng for LocalRng {}
+
+fn assert_send<T: Send>() {}
+
+fn assert_sync<T: Sync>() {}
+
+#[test]
+fn padding_types_are_send_and_sync() {
+    assert_send::<TxPadding<U16, LocalRng>>();
+    assert_sync::<TxPadding<U16, LocalRng>>();
+    assert_send::<TxPaddingConst<16, LocalRng>>();
+    assert_sync::<TxPaddingConst<16, LocalRng>>();
+    assert_send::<TxPaddingDyn<LocalRng>>();
+    assert_sync::<TxPaddingDyn<LocalRng>>();
+}
+
+#[test]
+fn stream_types_are_send_and_sync() {
+    assert_send::<Padder<U16, LocalRng>>();
+    assert_sync::<Padder<U16, LocalRng>>();
+    assert_send::<Unpadder<U16>>();
+    assert_sync::<Unpadder<U16>>();
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn owned_types_are_send_and_sync() {
+    use tx_padding::{PaddedVec, Session};
+
+    assert_send::<Session<U16, LocalRng>>();
+    assert_sync::<Session<U16, LocalRng>>();
+    assert_send::<PaddedVec<U16, LocalRng>>();
+    assert_sync::<PaddedVec<U16, LocalRng>>();
+}
+
+#[cfg(feature = "tokio")]
+#[test]
+fn codec_is_send_and_sync() {
+    use tx_padding::TxPaddingCodec;
+
+    assert_send::<TxPaddingCodec<U16, LocalRng>>();
+    assert_sync::<TxPaddingCodec<U16, LocalRng>>();
+}
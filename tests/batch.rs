@@ -0,0 +1,84 @@
+//! Test padding many messages at once
+#![no_std]
+
+use rand::rngs::mock::StepRng;
+use rand::{CryptoRng, Error, RngCore};
+
+use tx_padding::consts;
+use tx_padding::{InsecureRng, TxPadError, TxPadding};
+
+/// RNG counting how many times it is asked for random bytes
+struct CountingRng {
+    inner: StepRng,
+    calls: usize,
+}
+
+impl RngCore for CountingRng {
+    fn next_u32(&mut self) -> u32 {
+        self.calls += 1;
+        self.inner.next_u32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.calls += 1;
+        self.inner.next_u64()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.calls += 1;
+        self.inner.fill_bytes(dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.calls += 1;
+        self.inner.try_fill_bytes(dest)
+    }
+}
+
+impl CryptoRng for CountingRng {}
+
+#[test]
+fn pad_batch_round_trip() {
+    let msg = [0x5a; 64];
+    let mut out = [[0xff; 96]; 64];
+    let mut rng = CountingRng {
+        inner: StepRng::new(1, 1),
+        calls: 0,
+    };
+    TxPadding::<consts::U16>::pad_batch_with_rng(
+        &mut rng,
+        out.iter_mut()
+            .enumerate()
+            .map(|(n, o)| (&msg[..n], &mut o[..])),
+    )
+    .unwrap();
+    assert!(rng.calls < 64);
+    for (n, o) in out.iter().enumerate() {
+        let padded_msg = &o[..TxPadding::<consts::U16>::padded_len(n)];
+        assert_eq!(
+            TxPadding::<consts::U16>::unpad(padded_msg).unwrap(),
+            &msg[..n]
+        );
+    }
+}
+
+#[test]
+fn pad_batch_stops_at_first_error() {
+    let (mut a, mut b, mut c) = ([0; 16], [0; 8], [0; 16]);
+    assert_eq!(
+        TxPadding::<consts::U8>::pad_batch_with_rng(
+            &mut InsecureRng(StepRng::new(1, 1)),
+            [
+                (&b"ping"[..], &mut a[..]),
+                (b"pong", &mut b),
+                (b"pang", &mut c),
+            ]
+        ),
+        Err(TxPadError::BufferTooSmall {
+            required: 16,
+            got: 8
+        })
+    );
+    assert_eq!(TxPadding::<consts::U8>::unpad(&a).unwrap(), b"ping");
+    assert_eq!(c, [0; 16]);
+}
@@ -0,0 +1,84 @@
+//! Test interoperability with the block cipher modes of `block-modes`
+#![cfg(feature = "os-rng")]
+
+use aes_07::Aes128;
+use block_modes::block_padding::NoPadding;
+use block_modes::{BlockMode, Cbc};
+
+use tx_padding::consts::{U16, U8};
+use tx_padding::TxPadding;
+
+type Aes128Cbc = Cbc<Aes128, TxPadding<U16>>;
+
+const KEY: [u8; 16] = [0x42; 16];
+const IV: [u8; 16] = [0x24; 16];
+
+fn cipher() -> Aes128Cbc {
+    Aes128Cbc::new_from_slices(&KEY, &IV).unwrap()
+}
+
+#[test]
+fn encrypt_decrypt_round_trip() {
+    let msg = [0x5a; 100];
+    for n in 0..100 {
+        let mut buf = [0; TxPadding::<U16>::padded_len(100)];
+        buf[..n].copy_from_slice(&msg[..n]);
+        let l = cipher().encrypt(&mut buf, n).unwrap().len();
+        assert!(l <= TxPadding::<U16>::padded_len(n));
+        assert_eq!(l % 16, 0);
+        assert_eq!(cipher().decrypt(&mut buf[..l]).unwrap(), &msg[..n]);
+    }
+}
+
+#[test]
+fn decrypt_vec_truncates_to_prefix() {
+    let mut buf = [0; TxPadding::<U16>::padded_len(5)];
+    buf[..5].copy_from_slice(b"hello");
+    let ciphertext = cipher().encrypt(&mut buf, 5).unwrap().to_vec();
+    let decrypted = cipher().decrypt_vec(&ciphertext).unwrap();
+    assert_eq!(decrypted.len(), 5);
+    assert_eq!(decrypted[0] & 0xf0, 0xf0);
+    assert_ne!(decrypted, b"hello");
+}
+
+#[test]
+fn decrypts_to_tx_padding() {
+    let mut buf = [0; TxPadding::<U16>::padded_len(3)];
+    buf[..3].copy_from_slice(b"abc");
+    let l = cipher().encrypt(&mut buf, 3).unwrap().len();
+    let padded_msg = Cbc::<Aes128, NoPadding>::new_from_slices(&KEY, &IV)
+        .unwrap()
+        .decrypt(&mut buf[..l])
+        .unwrap();
+    assert_eq!(TxPadding::<U16>::unpad(padded_msg).unwrap(), b"abc");
+}
+
+#[test]
+fn reject_small_buffer() {
+    let mut buf = [0; 16];
+    buf[..3].copy_from_slice(b"abc");
+    assert!(cipher().encrypt(&mut buf, 3).is_err());
+}
+
+#[test]
+fn reject_incompatible_block_size() {
+    let mut buf = [0; 64];
+    let cipher = Cbc::<Aes128, TxPadding<U8>>::new_from_slices(&KEY, &IV).unwrap();
+    assert!(cipher.encrypt(&mut buf, 3).is_err());
+}
+
+#[test]
+fn reject_corrupted_ciphertext() {
+    let mut buf = [0; TxPadding::<U16>::padded_len(3)];
+    buf[..3].copy_from_slice(b"abc");
+    let l = cipher().encrypt(&mut buf, 3).unwrap().len();
+    buf[l - 1] ^= 1;
+    assert!(cipher().decrypt(&mut buf[..l]).is_err());
+    assert!(cipher().decrypt(&mut buf[..l - 1]).is_err());
+}
+
+#[test]
+#[should_panic(expected = "enough space for padding is allocated")]
+fn encrypt_vec_reserves_too_little() {
+    cipher().encrypt_vec(b"hello");
+}
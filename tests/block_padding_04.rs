@@ -0,0 +1,46 @@
+//! Test the `block-padding` 0.4 interoperability helpers
+#![cfg(feature = "block-padding-04")]
+#![no_std]
+
+use block_padding_04::array::Array;
+use block_padding_04::{NoPadding, Padding as _};
+use rand::rngs::mock::StepRng;
+
+use tx_padding::consts;
+use tx_padding::{InsecureRng, TxPadding};
+
+#[test]
+fn pad_array_blocks_matches_pad() {
+    for n in 0..20 {
+        let mut a = [0x5a; 40];
+        let mut b = [0x5a; 40];
+        let padded_msg =
+            TxPadding::<consts::U8>::pad_with_rng(&mut InsecureRng(StepRng::new(1, 1)), &mut a, n)
+                .unwrap();
+        let blocks = TxPadding::<consts::U8>::pad_array_blocks_with_rng(
+            &mut InsecureRng(StepRng::new(1, 1)),
+            &mut b,
+            n,
+        )
+        .unwrap();
+        assert_eq!(blocks.len() * 8, padded_msg.len());
+        for (block, chunk) in blocks.iter().zip(padded_msg.chunks(8)) {
+            assert_eq!(block.as_slice(), chunk);
+        }
+        assert_eq!(NoPadding::unpad_blocks(blocks).unwrap(), &padded_msg[..]);
+        assert_eq!(
+            TxPadding::<consts::U8>::unpad_array_blocks(blocks).unwrap(),
+            TxPadding::<consts::U8>::unpad(padded_msg).unwrap()
+        );
+    }
+}
+
+#[test]
+fn unpad_array_blocks_rejects_illformed() {
+    let blocks = [
+        Array([0xF8, 0, 0, 0, 0, 0, 0, 0]),
+        Array([0, 1, 0, 0, 0, 0, 0, 0]),
+    ];
+    assert!(TxPadding::<consts::U8>::unpad_array_blocks(&blocks).is_err());
+    assert!(TxPadding::<consts::U8>::unpad_array_blocks(&[]).is_err());
+}
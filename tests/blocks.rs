@@ -0,0 +1,51 @@
+//! Test the `block-padding` 0.3 interoperability helpers
+#![cfg(feature = "block-padding-03")]
+#![allow(deprecated)]
+#![no_std]
+
+use block_padding_03::generic_array::GenericArray;
+use block_padding_03::{NoPadding, Padding as _};
+use rand::rngs::mock::StepRng;
+
+use tx_padding::consts;
+use tx_padding::{InsecureRng, TxPadding};
+
+#[test]
+fn pad_blocks_matches_pad() {
+    for n in 0..20 {
+        let mut a = [0x5a; 40];
+        let mut b = [0x5a; 40];
+        let padded_msg =
+            TxPadding::<consts::U8>::pad_with_rng(&mut InsecureRng(StepRng::new(1, 1)), &mut a, n)
+                .unwrap();
+        let blocks = TxPadding::<consts::U8>::pad_blocks_with_rng(
+            &mut InsecureRng(StepRng::new(1, 1)),
+            &mut b,
+            n,
+        )
+        .unwrap();
+        let mut copies = [GenericArray::default(); 5];
+        let mut count = 0;
+        for (block, copy) in blocks.zip(copies.iter_mut()) {
+            *copy = *block;
+            count += 1;
+        }
+        let blocks = &copies[..count];
+        assert_eq!(blocks.len() * 8, padded_msg.len());
+        assert_eq!(NoPadding::unpad_blocks(blocks).unwrap(), &padded_msg[..]);
+        assert_eq!(
+            TxPadding::<consts::U8>::unpad_blocks(blocks).unwrap(),
+            TxPadding::<consts::U8>::unpad(padded_msg).unwrap()
+        );
+    }
+}
+
+#[test]
+fn unpad_blocks_rejects_illformed() {
+    let blocks = [
+        GenericArray::clone_from_slice(&[0xF8, 0, 0, 0, 0, 0, 0, 0]),
+        GenericArray::clone_from_slice(&[0, 1, 0, 0, 0, 0, 0, 0]),
+    ];
+    assert!(TxPadding::<consts::U8>::unpad_blocks(&blocks).is_err());
+    assert!(TxPadding::<consts::U8>::unpad_blocks(&[]).is_err());
+}
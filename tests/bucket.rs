@@ -0,0 +1,54 @@
+//! Test the bucketed padding
+#![no_std]
+
+use rand::rngs::mock::StepRng;
+
+use tx_padding::consts;
+use tx_padding::{BucketPadding, InsecureRng, TxPadError, TxPadding};
+
+const BUCKETS: &[usize] = &[4096, 256, 1024];
+
+#[test]
+fn round_up_to_bucket() {
+    let padding = BucketPadding::<consts::U16>::new(BUCKETS);
+    let msg = [0x5a; 4096];
+    let mut buf = [0; 4096];
+    for n in 0..4096 {
+        let expected = match TxPadding::<consts::U16>::padded_len(8 + n) {
+            l if l <= 256 => Some(256),
+            l if l <= 1024 => Some(1024),
+            l if l <= 4096 => Some(4096),
+            _ => None,
+        };
+        assert_eq!(padding.padded_len(n), expected);
+        buf[..n].copy_from_slice(&msg[..n]);
+        match expected {
+            Some(l) => {
+                let padded_msg = padding
+                    .pad_with_rng(&mut InsecureRng(StepRng::new(1, 1)), &mut buf, n)
+                    .unwrap();
+                assert_eq!(padded_msg.len(), l);
+                assert_eq!(padding.unpad(padded_msg).unwrap(), &msg[..n]);
+            }
+            None => assert_eq!(
+                padding.pad_with_rng(&mut InsecureRng(StepRng::new(1, 1)), &mut buf, n),
+                Err(TxPadError::MessageTooLong)
+            ),
+        }
+    }
+}
+
+#[test]
+fn unaligned_buckets_round_up() {
+    let padding = BucketPadding::<consts::U16>::new(&[100]);
+    assert_eq!(padding.padded_len(3), Some(112));
+}
+
+#[cfg(any(feature = "os-rng", feature = "thread-rng"))]
+#[test]
+fn empty_table() {
+    let padding = BucketPadding::<consts::U16>::new(&[]);
+    let mut buf = [0; 64];
+    assert_eq!(padding.padded_len(0), None);
+    assert_eq!(padding.pad(&mut buf, 0), Err(TxPadError::MessageTooLong));
+}
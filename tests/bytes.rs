@@ -0,0 +1,50 @@
+//! Test padding into and unpadding from `bytes` buffers
+#![cfg(feature = "bytes")]
+
+use bytes::{BufMut, BytesMut};
+
+use tx_padding::consts;
+use tx_padding::TxPadding;
+
+#[test]
+fn bytes_round_trip() {
+    let msg = [0x5a; 40];
+    let mut buf = BytesMut::new();
+    buf.put_slice(b"header");
+    for n in 0..msg.len() {
+        TxPadding::<consts::U8>::pad_bytes(&msg[..n], &mut buf).unwrap();
+    }
+    let mut src = buf.freeze().split_off(6);
+    for n in 0..msg.len() {
+        let frame = src.split_to(TxPadding::<consts::U8>::padded_len(n));
+        let unpadded = TxPadding::<consts::U8>::unpad_bytes(&frame).unwrap();
+        assert_eq!(unpadded, &msg[..n]);
+        assert_eq!(unpadded.as_ptr(), frame[frame.len() - 7 - n..].as_ptr());
+    }
+    assert!(src.is_empty());
+}
+
+#[test]
+fn unpad_bytes_rejects_illformed() {
+    assert!(TxPadding::<consts::U8>::unpad_bytes(&bytes::Bytes::new()).is_err());
+    assert!(
+        TxPadding::<consts::U8>::unpad_bytes(&bytes::Bytes::from_static(&[0xf8, 0, 0])).is_err()
+    );
+}
+
+#[test]
+fn unpad_truncate_bytes() {
+    let msg = [0x5a; 40];
+    let mut buf = BytesMut::new();
+    for n in 0..msg.len() {
+        buf.clear();
+        TxPadding::<consts::U8>::pad_bytes(&msg[..n], &mut buf).unwrap();
+        let ptr = buf.as_ptr();
+        TxPadding::<consts::U8>::unpad_truncate_bytes(&mut buf).unwrap();
+        assert_eq!(&buf[..], &msg[..n]);
+        assert_eq!(buf.as_ptr(), ptr);
+    }
+    let mut buf = BytesMut::from(&[0xf8, 0, 0][..]);
+    assert!(TxPadding::<consts::U8>::unpad_truncate_bytes(&mut buf).is_err());
+    assert_eq!(&buf[..], [0xf8, 0, 0]);
+}
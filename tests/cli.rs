@@ -0,0 +1,69 @@
+//! Test the txpad command line tool
+#![cfg(feature = "cli")]
+
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+
+use tx_padding::consts;
+use tx_padding::TxPadding;
+
+fn txpad(args: &[&str], input: &[u8]) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_txpad"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    // The tool may reject its arguments and exit before reading its input
+    let _ = child.stdin.take().unwrap().write_all(input);
+    child.wait_with_output().unwrap()
+}
+
+#[test]
+fn cli_pad_raw() {
+    let output = txpad(&["--block-size", "8", "pad"], b"hello world");
+    assert!(output.status.success());
+    assert_eq!(output.stdout.len(), 24);
+    assert_eq!(
+        TxPadding::<consts::U8>::unpad(&output.stdout).unwrap(),
+        b"hello world"
+    );
+}
+
+#[test]
+fn cli_unpad_hex() {
+    let output = txpad(
+        &["--format", "hex", "-b", "8", "unpad"],
+        b"fa 01020304 74657374 00000000000000\n",
+    );
+    assert!(output.status.success());
+    assert_eq!(output.stdout, b"74657374\n");
+}
+
+#[test]
+fn cli_base64_round_trip() {
+    let padded = txpad(&["--format", "base64", "pad"], b"aGVsbG8=\n");
+    assert!(padded.status.success());
+    let output = txpad(&["--format", "base64", "unpad"], &padded.stdout);
+    assert!(output.status.success());
+    assert_eq!(output.stdout, b"aGVsbG8=\n");
+}
+
+#[test]
+fn cli_len() {
+    let output = txpad(&["-b", "16", "len"], b"hello");
+    assert!(output.status.success());
+    assert_eq!(output.stdout, b"32\n");
+}
+
+#[test]
+fn cli_errors() {
+    let output = txpad(&["--block-size", "12", "pad"], b"hello");
+    assert!(!output.status.success());
+    assert!(output.stderr.starts_with(b"txpad: invalid block size 12"));
+
+    let output = txpad(&["unpad"], &[0xff; 16]);
+    assert!(!output.status.success());
+    assert!(output.stderr.starts_with(b"txpad: invalid padding"));
+}
@@ -0,0 +1,81 @@
+//! Test the `tokio_util` codec
+#![cfg(feature = "tokio")]
+
+mod common;
+
+use bytes::{BufMut, Bytes, BytesMut};
+use tokio_util::codec::{Decoder, Encoder};
+
+use tx_padding::consts;
+use tx_padding::TxPaddingCodec;
+
+use common::FailingRng;
+
+#[test]
+fn codec_round_trip_partial_frames() {
+    let mut codec = TxPaddingCodec::<consts::U16>::new();
+    let mut encoded = BytesMut::new();
+    let messages: [&[u8]; 4] = [b"", b"hello", &[0x5a; 100], b"world"];
+    for &msg in messages.iter() {
+        codec
+            .encode(Bytes::copy_from_slice(msg), &mut encoded)
+            .unwrap();
+    }
+
+    // Feed the encoded stream a few bytes at a time
+    let mut src = BytesMut::new();
+    let mut decoded = Vec::new();
+    for chunk in encoded.chunks(3) {
+        src.put_slice(chunk);
+        while let Some(msg) = codec.decode(&mut src).unwrap() {
+            decoded.push(msg);
+        }
+    }
+    assert!(src.is_empty());
+    assert_eq!(decoded.len(), messages.len());
+    for (a, b) in decoded.iter().zip(messages.iter()) {
+        assert_eq!(&a[..], *b);
+    }
+}
+
+#[test]
+fn codec_rejects_invalid_frames() {
+    let mut codec = TxPaddingCodec::<consts::U8>::with_max_frame_len(64);
+
+    let mut src = BytesMut::from(&b"\x00\x00\x00\x0c"[..]);
+    assert!(codec.decode(&mut src).is_err());
+
+    let mut src = BytesMut::from(&b"\x00\x00\x01\x00"[..]);
+    assert!(codec.decode(&mut src).is_err());
+
+    let mut src = BytesMut::from(&b"\x00\x00\x00\x10\xf8\x00\x00\x00\x00\x00\x00\x00"[..]);
+    assert_eq!(codec.decode(&mut src).unwrap(), None);
+    src.put_slice(b"\x00\x01\x00\x00\x00\x00\x00\x00");
+    assert_eq!(
+        codec.decode(&mut src).unwrap_err().kind(),
+        std::io::ErrorKind::InvalidData
+    );
+
+    let mut dst = BytesMut::new();
+    assert!(codec
+        .encode(Bytes::from_static(&[0; 64]), &mut dst)
+        .is_err());
+}
+
+#[test]
+fn codec_failed_encode_leaves_dst_unchanged() {
+    let mut codec = TxPaddingCodec::<consts::U16, FailingRng>::new();
+    let mut dst = BytesMut::from(&b"previous frame"[..]);
+    let err = codec
+        .encode(Bytes::from_static(b"hello world"), &mut dst)
+        .unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::Other);
+    assert_eq!(&dst[..], b"previous frame");
+
+    let mut codec = TxPaddingCodec::<consts::U16>::with_max_frame_len(32);
+    let err = codec
+        .encode(Bytes::from_static(&[0; 32]), &mut dst)
+        .unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+    assert_eq!(&dst[..], b"previous frame");
+}
@@ -0,0 +1,37 @@
+//! Fixtures shared by the integration tests
+
+use core::num::NonZeroU32;
+
+use rand::{CryptoRng, Error, RngCore};
+
+/// A generator that always fails
+///
+/// The padding methods only draw through `try_fill_bytes`, so the infallible methods panic.
+#[derive(Default)]
+pub struct FailingRng;
+
+impl RngCore for FailingRng {
+    fn next_u32(&mut self) -> u32 {
+        let mut bytes = [0; 4];
+        self.fill_bytes(&mut bytes);
+        u32::from_le_bytes(bytes)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let mut bytes = [0; 8];
+        self.fill_bytes(&mut bytes);
+        u64::from_le_bytes(bytes)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        if let Err(e) = self.try_fill_bytes(dest) {
+            panic!("FailingRng used through an infallible method: {}", e);
+        }
+    }
+
+    fn try_fill_bytes(&mut self, _dest: &mut [u8]) -> Result<(), Error> {
+        Err(Error::from(NonZeroU32::new(Error::CUSTOM_START).unwrap()))
+    }
+}
+
+impl CryptoRng for FailingRng {}
@@ -0,0 +1,12 @@
+//! Test that unsupported block sizes are rejected at compile time
+// The cases go through `Padding`, which also reports a missing default RNG
+#![cfg(any(feature = "os-rng", feature = "thread-rng"))]
+
+// The expected diagnostics depend on the compiler version, so they are only checked on stable
+// compilers recent enough to support `#[diagnostic::on_unimplemented]`.
+#[rustversion::attr(any(not(stable), before(1.78)), ignore)]
+#[test]
+fn invalid_block_sizes() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/compile_fail/*.rs");
+}
@@ -0,0 +1,6 @@
+use tx_padding::consts::U1;
+use tx_padding::{Padding, TxPadding};
+
+fn main() {
+    let _ = <TxPadding<U1> as Padding>::unpad(&[0; 16]);
+}
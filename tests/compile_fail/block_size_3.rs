@@ -0,0 +1,6 @@
+use tx_padding::consts::U3;
+use tx_padding::{Padding, TxPadding};
+
+fn main() {
+    let _ = <TxPadding<U3> as Padding>::unpad(&[0; 16]);
+}
@@ -0,0 +1,64 @@
+//! Test stacking paddings
+#![cfg(any(feature = "os-rng", feature = "thread-rng"))]
+#![no_std]
+
+use block_padding::{Iso7816, Pkcs7};
+
+use tx_padding::consts;
+use tx_padding::{Compose, Padding, TxPadding, TxTailPadding};
+
+macro_rules! test_compose_round_trip {
+    ($name:ident, $outer:ty, $inner:ty, $block_size:expr, $extra:expr) => {
+        #[test]
+        fn $name() {
+            type Layered = Compose<$outer, $inner>;
+            for n in 0..40 {
+                let msg = &[0x5a; 40][..n];
+                let mut buf = [0xff; 128];
+                buf[..n].copy_from_slice(msg);
+                let inner_len = <$inner>::pad(&mut buf.clone(), n, $block_size)
+                    .unwrap()
+                    .len();
+                let padded_msg = Layered::pad(&mut buf, n, $block_size).unwrap();
+                assert_eq!(padded_msg.len(), inner_len + $extra);
+                assert_eq!(padded_msg.len() % $block_size, 0);
+                assert_eq!(Layered::unpad(padded_msg).unwrap(), msg);
+            }
+        }
+    };
+}
+
+test_compose_round_trip!(pkcs7_over_tx, Pkcs7, TxPadding<consts::U8>, 8, 8);
+test_compose_round_trip!(iso7816_over_tx, Iso7816, TxPadding<consts::U16>, 16, 16);
+test_compose_round_trip!(
+    tx_over_tail,
+    TxPadding<consts::U8>,
+    TxTailPadding<consts::U8>,
+    8,
+    16
+);
+
+#[test]
+fn tx_over_pkcs7() {
+    type Layered = Compose<TxPadding<consts::U8>, Pkcs7>;
+    for n in 0..40 {
+        let msg = &[0x5a; 40][..n];
+        let mut buf = [0xff; 128];
+        buf[..n].copy_from_slice(msg);
+        let padded_msg = Layered::pad(&mut buf, n, 8).unwrap();
+        let pkcs7_len = (n / 8 + 1) * 8;
+        assert_eq!(
+            padded_msg.len(),
+            TxPadding::<consts::U8>::padded_len(pkcs7_len)
+        );
+        assert_eq!(Layered::unpad(padded_msg).unwrap(), msg);
+    }
+}
+
+#[test]
+fn compose_insufficient_space() {
+    type Layered = Compose<Pkcs7, TxPadding<consts::U8>>;
+    let mut buf = [0; 16];
+    assert!(Layered::pad(&mut buf, 4, 8).is_err());
+    assert!(Layered::unpad(&[0xf8, 0, 0, 0, 0, 0, 0, 0, 1]).is_err());
+}
@@ -0,0 +1,132 @@
+//! Test that the const generic API agrees with the typenum one
+#![cfg(any(feature = "os-rng", feature = "thread-rng"))]
+#![no_std]
+
+use rand::rngs::mock::StepRng;
+use typenum::marker_traits::Unsigned;
+
+use tx_padding::consts;
+use tx_padding::{InsecureRng, Padding, TxPadding, TxPaddingConst};
+
+macro_rules! create_agreement_test {
+    ($name:ident, $block_size:ty, $n:expr) => {
+        #[test]
+        fn $name() {
+            let mut a = [0x5a; 3 * $n];
+            let mut b = [0x5a; 3 * $n];
+            for pos in 0..$n {
+                let mut rng_a = InsecureRng(StepRng::new(1, 1));
+                let mut rng_b = InsecureRng(StepRng::new(1, 1));
+                let padded_a =
+                    TxPadding::<$block_size>::pad_with_rng(&mut rng_a, &mut a, pos).unwrap();
+                let padded_b = TxPaddingConst::<$n>::pad_with_rng(&mut rng_b, &mut b, pos).unwrap();
+                assert_eq!(padded_a, padded_b);
+                assert_eq!(
+                    TxPaddingConst::<$n>::unpad(padded_b).unwrap(),
+                    TxPadding::<$block_size>::unpad(padded_a).unwrap()
+                );
+                assert_eq!(
+                    TxPaddingConst::<$n>::unpad_strict(padded_b).ok(),
+                    TxPadding::<$block_size>::unpad_strict(padded_a).ok()
+                );
+                assert!(TxPaddingConst::<$n>::is_valid(padded_b));
+                assert!(!TxPaddingConst::<$n>::is_valid(&padded_b[..1]));
+                assert_eq!(
+                    TxPaddingConst::<$n>::unpad_trusted(padded_b).unwrap(),
+                    TxPadding::<$block_size>::unpad_trusted(padded_a).unwrap()
+                );
+                assert_eq!(
+                    TxPaddingConst::<$n>::unpad_range(padded_b).ok(),
+                    TxPadding::<$block_size>::unpad_range(padded_a).ok()
+                );
+                assert_eq!(
+                    TxPaddingConst::<$n>::padded_len(pos),
+                    TxPadding::<$block_size>::padded_len(pos)
+                );
+                let blocks = padded_b.len() / $n;
+                assert_eq!(
+                    TxPaddingConst::<$n>::unpad_prefix(&b, blocks).unwrap(),
+                    TxPadding::<$block_size>::unpad_prefix(&a, blocks).unwrap()
+                );
+
+                let mut min_a = [0x5a; 4 * $n + 16];
+                let mut min_b = [0x5a; 4 * $n + 16];
+                let padded_a = TxPadding::<$block_size>::pad_min_with_rng(
+                    &mut InsecureRng(StepRng::new(1, 1)),
+                    &mut min_a,
+                    pos,
+                    3 * $n,
+                )
+                .unwrap();
+                let padded_b = TxPaddingConst::<$n>::pad_min_with_rng(
+                    &mut InsecureRng(StepRng::new(1, 1)),
+                    &mut min_b,
+                    pos,
+                    3 * $n,
+                )
+                .unwrap();
+                assert_eq!(padded_a, padded_b);
+                assert_eq!(
+                    TxPaddingConst::<$n>::unpad_min(padded_b).unwrap(),
+                    TxPadding::<$block_size>::unpad_min(padded_a).unwrap()
+                );
+                assert_eq!(
+                    TxPaddingConst::<$n>::padded_min_len(pos, 3 * $n),
+                    TxPadding::<$block_size>::padded_min_len(pos, 3 * $n)
+                );
+
+                let mut aligned_a = [0x5a; 4 * $n + 16];
+                let mut aligned_b = [0x5a; 4 * $n + 16];
+                let padded_a = TxPadding::<$block_size>::pad_aligned_with_rng(
+                    &mut InsecureRng(StepRng::new(1, 1)),
+                    &mut aligned_a,
+                    pos,
+                    2,
+                )
+                .unwrap();
+                let padded_b = TxPaddingConst::<$n>::pad_aligned_with_rng(
+                    &mut InsecureRng(StepRng::new(1, 1)),
+                    &mut aligned_b,
+                    pos,
+                    2,
+                )
+                .unwrap();
+                assert_eq!(padded_a, padded_b);
+                assert_eq!(
+                    TxPaddingConst::<$n>::unpad_aligned(padded_b).unwrap(),
+                    TxPadding::<$block_size>::unpad_aligned(padded_a).unwrap()
+                );
+                assert_eq!(
+                    TxPaddingConst::<$n>::padded_aligned_len(pos, 2),
+                    TxPadding::<$block_size>::padded_aligned_len(pos, 2)
+                );
+            }
+            assert_eq!(<$block_size>::to_usize(), $n);
+            assert_eq!(
+                TxPaddingConst::<$n>::MAX_MSG_LEN,
+                TxPadding::<$block_size>::MAX_MSG_LEN
+            );
+            assert_eq!(
+                TxPaddingConst::<$n>::MIN_OVERHEAD,
+                TxPadding::<$block_size>::MIN_OVERHEAD
+            );
+            assert_eq!(
+                TxPaddingConst::<$n>::MAX_OVERHEAD,
+                TxPadding::<$block_size>::MAX_OVERHEAD
+            );
+            assert_eq!(TxPaddingConst::<$n>::BLOCK_SIZE, $n);
+        }
+    };
+}
+
+create_agreement_test!(agree_bs_2, consts::U2, 2);
+create_agreement_test!(agree_bs_8, consts::U8, 8);
+create_agreement_test!(agree_bs_64, consts::U64, 64);
+create_agreement_test!(agree_bs_256, consts::U256, 256);
+
+#[test]
+fn reject_mismatch_size() {
+    let mut buf = [0; 16];
+    assert!(TxPaddingConst::<8>::pad(&mut buf, 3, 4).is_err());
+    assert!(TxPaddingConst::<8>::pad(&mut buf, 3, 8).is_ok());
+}
@@ -0,0 +1,76 @@
+//! Test the checksummed sibling scheme
+#![cfg(any(feature = "os-rng", feature = "thread-rng"))]
+#![no_std]
+
+use rand::rngs::mock::StepRng;
+
+use tx_padding::consts;
+use tx_padding::{InsecureRng, Padding, TxPadding, TxPaddingCrc};
+
+/// Bitwise reference implementation of the CRC-32 used for blocks of 8 bytes and more
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &v in data {
+        crc ^= v as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ 0xedb8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
+
+macro_rules! test_crc_round_trip {
+    ($name:ident, $n:ty, $block_size:expr) => {
+        #[test]
+        fn $name() {
+            for n in 0..40 {
+                let msg = &[0x5a; 40][..n];
+                let mut buf = [0xff; 1024];
+                buf[..n].copy_from_slice(msg);
+                let padded_msg = TxPaddingCrc::<$n>::pad_with_rng(
+                    &mut InsecureRng(StepRng::new(1, 1)),
+                    &mut buf,
+                    n,
+                )
+                .unwrap();
+                let l = padded_msg.len();
+                assert_eq!(l, TxPadding::<$n>::padded_len(n));
+                assert_eq!(TxPaddingCrc::<$n>::unpad(padded_msg).unwrap(), msg);
+                for i in 0..l {
+                    padded_msg[i] ^= 0x10;
+                    assert!(TxPaddingCrc::<$n>::unpad(padded_msg).is_err());
+                    padded_msg[i] ^= 0x10;
+                }
+            }
+        }
+    };
+}
+
+test_crc_round_trip!(crc_round_trip_u2, consts::U2, 2);
+test_crc_round_trip!(crc_round_trip_u4, consts::U4, 4);
+test_crc_round_trip!(crc_round_trip_u8, consts::U8, 8);
+test_crc_round_trip!(crc_round_trip_u256, consts::U256, 256);
+
+#[test]
+fn crc32_matches_reference() {
+    let mut buf = [0; 32];
+    buf[..11].copy_from_slice(b"hello world");
+    let padded_msg = TxPaddingCrc::<consts::U8>::pad(&mut buf, 11, 8).unwrap();
+    let l = padded_msg.len();
+    let crc = crc32(&padded_msg[..l - 7]);
+    assert_eq!(&padded_msg[l - 7..l - 3], &crc.to_be_bytes());
+    assert_eq!(&padded_msg[l - 3..], &[0; 3]);
+    assert_eq!(crc32(b"123456789"), 0xcbf4_3926);
+}
+
+#[test]
+fn txpadding_rejects_checksummed() {
+    let mut buf = [0; 16];
+    buf[..4].copy_from_slice(b"test");
+    let padded_msg = TxPaddingCrc::<consts::U8>::pad(&mut buf, 4, 8).unwrap();
+    assert!(TxPadding::<consts::U8>::unpad(padded_msg).is_err());
+}
@@ -0,0 +1,40 @@
+//! Test that the constant-time unpadding agrees with the regular one
+#![cfg(feature = "subtle")]
+#![no_std]
+
+use tx_padding::consts;
+use tx_padding::{Padding, TxPadding};
+
+#[test]
+fn unpad_ct_matches_unpad() {
+    let mut data = [0u8; 24];
+    for l in 0..=data.len() {
+        for header in 0..=255u8 {
+            for tail in 0..3 {
+                data[0] = header;
+                if l > 1 && tail > 0 {
+                    data[l - tail] = 1;
+                }
+                let expected = TxPadding::<consts::U8>::unpad(&data[..l]).ok();
+                let got: Option<&[u8]> = TxPadding::<consts::U8>::unpad_ct(&data[..l]).into();
+                assert_eq!(got, expected);
+                assert_eq!(
+                    bool::from(TxPadding::<consts::U8>::is_valid_ct(&data[..l])),
+                    expected.is_some()
+                );
+                if l > 1 && tail > 0 {
+                    data[l - tail] = 0;
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn unpad_ct_padded_message() {
+    let mut buf = [0; 32];
+    buf[..7].copy_from_slice(b"\x01\x02\x03\x04\x05\x06\x07");
+    let padded_msg = TxPadding::<consts::U16>::pad(&mut buf, 7, 16).unwrap();
+    let msg: Option<&[u8]> = TxPadding::<consts::U16>::unpad_ct(padded_msg).into();
+    assert_eq!(msg, Some(&b"\x01\x02\x03\x04\x05\x06\x07"[..]));
+}
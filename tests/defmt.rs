@@ -0,0 +1,24 @@
+//! Test the error types can be logged with `defmt`
+#![cfg(feature = "defmt")]
+
+use tx_padding::TxPadError;
+
+// Formatting needs a global logger, which only firmware provides, so only the bounds are checked
+fn assert_format<T: defmt::Format>() {}
+
+#[test]
+fn errors_implement_format() {
+    assert_format::<TxPadError>();
+}
+
+#[cfg(feature = "diagnostics")]
+#[test]
+fn unpad_error_kind_implements_format() {
+    assert_format::<tx_padding::UnpadErrorKind>();
+}
+
+#[cfg(feature = "embedded-io")]
+#[test]
+fn embedded_io_error_implements_format() {
+    assert_format::<tx_padding::embedded::Error<TxPadError>>();
+}
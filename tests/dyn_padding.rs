@@ -0,0 +1,71 @@
+//! Test padding through trait objects selected at runtime
+#![cfg(any(feature = "os-rng", feature = "thread-rng"))]
+
+use std::boxed::Box;
+use std::marker::PhantomData;
+use std::vec::Vec;
+
+use tx_padding::consts::{U16, U8};
+use tx_padding::{DynPadding, TxPadding, TxPaddingDyn};
+
+#[test]
+fn select_supported_block_sizes() {
+    for shift in 1..=8 {
+        let block_size = 1 << shift;
+        let scheme = <TxPaddingDyn>::select(block_size).unwrap();
+        assert_eq!(scheme.block_size(), block_size);
+        for n in 0..40 {
+            let mut buf = [0x5a; 1024];
+            let padded_msg = scheme.pad(&mut buf, n).unwrap();
+            assert_eq!(padded_msg.len(), scheme.padded_len(n).unwrap());
+            assert_eq!(
+                <TxPaddingDyn>::unpad(padded_msg, block_size).unwrap(),
+                &[0x5a; 40][..n]
+            );
+            assert_eq!(scheme.unpad(padded_msg).unwrap(), &[0x5a; 40][..n]);
+        }
+    }
+}
+
+#[test]
+fn select_rejects_unsupported_block_sizes() {
+    for &block_size in &[0, 1, 3, 12, 512] {
+        assert!(<TxPaddingDyn>::select(block_size).is_none());
+    }
+}
+
+#[test]
+fn as_dyn_matches_static_scheme() {
+    let scheme = TxPadding::<U8>::as_dyn();
+    assert_eq!(scheme.block_size(), 8);
+    assert_eq!(scheme.padded_len(4), Some(TxPadding::<U8>::padded_len(4)));
+    assert_eq!(scheme.padded_len(usize::MAX), None);
+    let mut buf = [0; 16];
+    buf[..4].copy_from_slice(b"test");
+    let padded_msg = scheme.pad(&mut buf, 4).unwrap();
+    assert_eq!(TxPadding::<U8>::unpad(padded_msg).unwrap(), b"test");
+}
+
+#[test]
+fn boxed_schemes() {
+    let schemes: Vec<Box<dyn DynPadding>> = vec![
+        Box::new(PhantomData::<TxPadding<U8>>),
+        Box::new(PhantomData::<TxPadding<U16>>),
+    ];
+    for scheme in &schemes {
+        let mut buf = [0; 64];
+        buf[..5].copy_from_slice(b"hello");
+        let padded_msg = scheme.pad(&mut buf, 5).unwrap();
+        assert_eq!(padded_msg.len() % scheme.block_size(), 0);
+        assert_eq!(scheme.unpad(padded_msg).unwrap(), b"hello");
+    }
+}
+
+#[test]
+fn report_errors() {
+    let scheme = TxPadding::<U16>::as_dyn();
+    let mut buf = [0; 16];
+    assert!(scheme.pad(&mut buf, 4).is_err());
+    assert!(scheme.unpad(&[]).is_err());
+    assert!(scheme.unpad(&[0xf0, 0, 0]).is_err());
+}
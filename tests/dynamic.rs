@@ -0,0 +1,79 @@
+//! Test the padding scheme with block sizes chosen at runtime
+#![no_std]
+
+use rand::rngs::mock::StepRng;
+
+use tx_padding::consts;
+use tx_padding::{InsecureRng, TxPadError, TxPadding, TxPaddingDyn};
+
+#[test]
+fn agree_with_typenum() {
+    let mut a = [0; 48];
+    let mut b = [0; 48];
+    for pos in 0..16 {
+        let padded_a = TxPadding::<consts::U16>::pad_with_rng(
+            &mut InsecureRng(StepRng::new(1, 1)),
+            &mut a,
+            pos,
+        )
+        .unwrap();
+        let padded_b =
+            <TxPaddingDyn>::pad_with_rng(&mut InsecureRng(StepRng::new(1, 1)), &mut b, pos, 16)
+                .unwrap();
+        assert_eq!(padded_a, padded_b);
+        assert_eq!(
+            <TxPaddingDyn>::unpad(padded_b, 16).unwrap(),
+            TxPadding::<consts::U16>::unpad(padded_a).unwrap()
+        );
+        assert_eq!(
+            <TxPaddingDyn>::unpad_strict(padded_b, 16).ok(),
+            TxPadding::<consts::U16>::unpad_strict(padded_a).ok()
+        );
+        assert!(<TxPaddingDyn>::is_valid(padded_b, 16));
+        assert!(!<TxPaddingDyn>::is_valid(padded_b, 12));
+        assert_eq!(
+            <TxPaddingDyn>::unpad_trusted(padded_b, 16).unwrap(),
+            TxPadding::<consts::U16>::unpad_trusted(padded_a).unwrap()
+        );
+        assert_eq!(
+            <TxPaddingDyn>::padded_len(pos, 16),
+            Some(TxPadding::<consts::U16>::padded_len(pos))
+        );
+    }
+}
+
+#[test]
+fn reject_invalid_block_size() {
+    let mut buf = [0; 64];
+    for &block_size in &[0, 1, 3, 12, 512] {
+        assert_eq!(
+            <TxPaddingDyn>::pad_with_rng(
+                &mut InsecureRng(StepRng::new(1, 1)),
+                &mut buf,
+                3,
+                block_size
+            ),
+            Err(TxPadError::InvalidBlockSize { got: block_size })
+        );
+        assert!(<TxPaddingDyn>::unpad(&buf, block_size).is_err());
+        assert!(<TxPaddingDyn>::unpad_strict(&buf, block_size).is_err());
+        assert_eq!(<TxPaddingDyn>::padded_len(3, block_size), None);
+    }
+}
+
+#[cfg(any(feature = "os-rng", feature = "thread-rng"))]
+#[test]
+fn round_trip_all_block_sizes() {
+    let mut buf = [0; 600];
+    let mut block_size = 2;
+    while block_size <= 256 {
+        buf[..5].copy_from_slice(b"\x01\x02\x03\x04\x05");
+        let padded_msg = <TxPaddingDyn>::pad(&mut buf, 5, block_size).unwrap();
+        assert_eq!(padded_msg.len() % block_size, 0);
+        assert_eq!(
+            <TxPaddingDyn>::unpad(padded_msg, block_size).unwrap(),
+            b"\x01\x02\x03\x04\x05"
+        );
+        block_size *= 2;
+    }
+}
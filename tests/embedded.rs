@@ -0,0 +1,82 @@
+//! Test the `embedded_io` adapters
+#![cfg(feature = "embedded-io")]
+#![no_std]
+
+use embedded_io::{Error as _, ErrorKind, Read, Write};
+use rand::rngs::mock::StepRng;
+
+use tx_padding::consts;
+use tx_padding::embedded::{Error, PadWriter, UnpadReader};
+use tx_padding::{InsecureRng, Padding, TxPadding};
+
+#[test]
+fn pad_writer_matches_pad() {
+    let mut message = [0; 1000];
+    for (i, v) in message.iter_mut().enumerate() {
+        *v = (i * 7) as u8;
+    }
+    for &n in &[0, 1, 15, 16, 17, 1000] {
+        let mut expected = [0; 1024];
+        expected[..n].copy_from_slice(&message[..n]);
+        let expected = TxPadding::<consts::U16>::pad_with_rng(
+            &mut InsecureRng(StepRng::new(3, 5)),
+            &mut expected,
+            n,
+        )
+        .unwrap();
+
+        for &chunk in &[1, 13, 1000] {
+            let mut out = [0; 1024];
+            let mut writer = PadWriter::<_, consts::U16>::with_rng(
+                &mut out[..],
+                &mut InsecureRng(StepRng::new(3, 5)),
+                n,
+            )
+            .unwrap();
+            for piece in message[..n].chunks(chunk) {
+                writer.write_all(piece).unwrap();
+            }
+            writer.finish().unwrap();
+            assert_eq!(&out[..expected.len()], expected);
+        }
+    }
+}
+
+#[test]
+fn unpad_reader_round_trip() {
+    let mut buf = [0; 1024];
+    buf[..1000].copy_from_slice(&[0x5a; 1000]);
+    let padded_msg = TxPadding::<consts::U16>::pad(&mut buf, 1000, 16).unwrap();
+
+    let mut reader = UnpadReader::<_, consts::U16>::new(&padded_msg[..]);
+    let mut out = [0; 1000];
+    reader.read_exact(&mut out).unwrap();
+    assert_eq!(reader.read(&mut [0; 16]).unwrap(), 0);
+    assert_eq!(&out[..], &[0x5a; 1000][..]);
+}
+
+#[test]
+fn error_kinds() {
+    let mut out = [0; 32];
+    let mut writer = PadWriter::<_, consts::U8>::new(&mut out[..], 3).unwrap();
+    assert_eq!(
+        writer.write_all(b"\x01\x02\x03\x04").unwrap_err().kind(),
+        ErrorKind::InvalidInput
+    );
+
+    let padded_msg = [0xF8, 0, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0];
+    let mut reader = UnpadReader::<_, consts::U8>::new(&padded_msg[..]);
+    let mut out = [0; 16];
+    let mut err = None;
+    for _ in 0..4 {
+        if let Err(e) = reader.read(&mut out) {
+            err = Some(e);
+            break;
+        }
+    }
+    assert_eq!(err, Some(Error::MalformedPadding));
+    assert_eq!(
+        Error::<ErrorKind>::MalformedPadding.kind(),
+        ErrorKind::InvalidData
+    );
+}
@@ -0,0 +1,62 @@
+//! Test padding to and from hex and base64
+#![cfg(all(feature = "encoding", feature = "os-rng"))]
+
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+
+use tx_padding::consts::{U16, U8};
+use tx_padding::TxPadding;
+
+#[test]
+fn hex_round_trip() {
+    let msg = [0x5a; 40];
+    for n in 0..40 {
+        let padded_msg = TxPadding::<U8>::pad_to_hex(&msg[..n]).unwrap();
+        assert_eq!(padded_msg.len(), 2 * TxPadding::<U8>::padded_len(n));
+        assert!(padded_msg
+            .bytes()
+            .all(|c| c.is_ascii_hexdigit() && !c.is_ascii_uppercase()));
+        let decoded = hex::decode(&padded_msg).unwrap();
+        assert_eq!(TxPadding::<U8>::unpad(&decoded).unwrap(), &msg[..n]);
+        assert_eq!(
+            TxPadding::<U8>::unpad_from_hex(&padded_msg).unwrap(),
+            &msg[..n]
+        );
+    }
+}
+
+#[test]
+fn base64_round_trip() {
+    let msg = [0x5a; 40];
+    for n in 0..40 {
+        let padded_msg = TxPadding::<U16>::pad_to_base64(&msg[..n]).unwrap();
+        let decoded = BASE64.decode(&padded_msg).unwrap();
+        assert_eq!(decoded.len(), TxPadding::<U16>::padded_len(n));
+        assert_eq!(TxPadding::<U16>::unpad(&decoded).unwrap(), &msg[..n]);
+        assert_eq!(
+            TxPadding::<U16>::unpad_from_base64(&padded_msg).unwrap(),
+            &msg[..n]
+        );
+    }
+}
+
+#[test]
+fn reject_invalid_encoding() {
+    assert!(TxPadding::<U8>::unpad_from_hex("not hex").is_err());
+    assert!(TxPadding::<U8>::unpad_from_hex("abc").is_err());
+    assert!(TxPadding::<U8>::unpad_from_base64("not base64!").is_err());
+    assert!(TxPadding::<U8>::unpad_from_hex("").is_err());
+    assert!(TxPadding::<U8>::unpad_from_base64("").is_err());
+}
+
+#[test]
+fn reject_malformed_padding() {
+    let mut padded_msg = TxPadding::<U8>::pad_to_vec(b"test").unwrap();
+    *padded_msg.last_mut().unwrap() = 1;
+    assert!(TxPadding::<U8>::unpad_from_hex(&hex::encode(&padded_msg)).is_err());
+    assert!(TxPadding::<U8>::unpad_from_base64(&BASE64.encode(&padded_msg)).is_err());
+
+    // Valid padding of another block size
+    let padded_msg = TxPadding::<U8>::pad_to_hex(b"test").unwrap();
+    assert!(TxPadding::<U16>::unpad_from_hex(&padded_msg).is_err());
+}
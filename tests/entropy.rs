@@ -0,0 +1,49 @@
+//! Test replacing the entropy source of the default RNG
+#![cfg(all(feature = "entropy-source", not(feature = "thread-rng")))]
+#![no_std]
+
+use rand::Error;
+
+use tx_padding::consts;
+use tx_padding::{set_entropy_source, Padding, RandomSource, TxPadding};
+#[cfg(not(feature = "os-rng"))]
+use tx_padding::{TxPadError, NO_ENTROPY_SOURCE};
+
+fn constant_source(dest: &mut [u8]) -> Result<(), Error> {
+    dest.fill(0xa5);
+    Ok(())
+}
+
+#[test]
+fn registered_source_fills_prefix() {
+    let mut buf = [0; 16];
+    buf[..3].copy_from_slice(b"\x01\x02\x03");
+    let padded = TxPadding::<consts::U8, RandomSource>::pad(&mut buf, 3, 8);
+    #[cfg(feature = "os-rng")]
+    assert_eq!(
+        TxPadding::<consts::U8>::unpad(padded.unwrap()).unwrap(),
+        b"\x01\x02\x03"
+    );
+    #[cfg(not(feature = "os-rng"))]
+    {
+        use rand::RngCore;
+
+        assert!(padded.is_err());
+        let mut buf = [0; 16];
+        assert_eq!(
+            TxPadding::<consts::U8>::pad_with_rng(&mut RandomSource, &mut buf, 3),
+            Err(TxPadError::RngFailure)
+        );
+        let e = RandomSource.try_fill_bytes(&mut buf).unwrap_err();
+        assert_eq!(e.code().map(|code| code.get()), Some(NO_ENTROPY_SOURCE));
+    }
+
+    set_entropy_source(constant_source);
+    let mut buf = [0; 16];
+    buf[..3].copy_from_slice(b"\x01\x02\x03");
+    let padded_msg = TxPadding::<consts::U8>::pad(&mut buf, 3, 8).unwrap();
+    assert_eq!(
+        padded_msg,
+        b"\xfb\xa5\xa5\xa5\xa5\xa5\x01\x02\x03\x00\x00\x00\x00\x00\x00\x00"
+    );
+}
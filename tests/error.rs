@@ -0,0 +1,70 @@
+//! Test the error types compose with `std::error::Error`
+#![cfg(feature = "std")]
+
+use std::boxed::Box;
+use std::error::Error;
+use std::io::Write;
+
+use rand::rngs::mock::StepRng;
+
+use tx_padding::consts::U8;
+use tx_padding::{InsecureRng, PadWriter, TxPadError, TxPadding};
+
+fn pad_too_small() -> Result<(), Box<dyn Error>> {
+    let mut buf = [0; 8];
+    TxPadding::<U8>::pad_with_rng(&mut InsecureRng(StepRng::new(1, 1)), &mut buf, 4)?;
+    Ok(())
+}
+
+#[test]
+fn tx_pad_error_boxes() {
+    let err = pad_too_small().unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "buffer too small: 16 bytes required, got 8"
+    );
+    assert_eq!(
+        err.downcast_ref::<TxPadError>(),
+        Some(&TxPadError::BufferTooSmall {
+            required: 16,
+            got: 8
+        })
+    );
+    assert!(err.source().is_none());
+}
+
+#[test]
+fn io_error_carries_tx_pad_error() {
+    let mut writer =
+        PadWriter::<_, U8>::with_rng(Vec::new(), &mut InsecureRng(StepRng::new(1, 1)), 3).unwrap();
+    let err = writer.write_all(b"\x01\x02\x03\x04").unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+    assert_eq!(
+        err.get_ref().and_then(|e| e.downcast_ref::<TxPadError>()),
+        Some(&TxPadError::MessageLengthMismatch {
+            expected: 3,
+            got: 4
+        })
+    );
+}
+
+#[cfg(feature = "diagnostics")]
+#[test]
+fn unpad_error_kind_boxes() {
+    let err: Box<dyn Error> = Box::new(tx_padding::UnpadErrorKind::EmptyInput);
+    assert_eq!(err.to_string(), "empty input");
+}
+
+#[cfg(feature = "embedded-io")]
+#[test]
+fn embedded_error_source() {
+    use tx_padding::embedded::Error as EmbeddedError;
+
+    let err = EmbeddedError::<()>::Pad(TxPadError::MessageTooLong);
+    assert_eq!(err.to_string(), "padding failed: message too long");
+    assert_eq!(
+        err.source().and_then(|e| e.downcast_ref::<TxPadError>()),
+        Some(&TxPadError::MessageTooLong)
+    );
+    assert!(EmbeddedError::<()>::MalformedPadding.source().is_none());
+}
@@ -0,0 +1,30 @@
+//! Test the non-cryptographic RNG
+#![cfg(feature = "insecure-fast-rng")]
+#![no_std]
+
+use tx_padding::consts;
+use tx_padding::{InsecureFastRng, Padding, TxPadding};
+
+#[test]
+fn fast_rng_round_trip() {
+    let msg = [0x5a; 64];
+    for n in 0..64 {
+        let mut buf = [0; 96];
+        buf[..n].copy_from_slice(&msg[..n]);
+        let padded_msg = TxPadding::<consts::U16, InsecureFastRng>::pad(&mut buf, n, 16).unwrap();
+        assert_eq!(
+            TxPadding::<consts::U16>::unpad(padded_msg).unwrap(),
+            &msg[..n]
+        );
+    }
+}
+
+#[test]
+fn fast_rng_is_reproducible() {
+    let (mut a, mut b) = ([0; 32], [0; 32]);
+    TxPadding::<consts::U16>::pad_with_rng(&mut InsecureFastRng::seed_from_u64(7), &mut a, 3)
+        .unwrap();
+    TxPadding::<consts::U16>::pad_with_rng(&mut InsecureFastRng::seed_from_u64(7), &mut b, 3)
+        .unwrap();
+    assert_eq!(a, b);
+}
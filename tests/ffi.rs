@@ -0,0 +1,76 @@
+//! Test the C interface
+#![cfg(feature = "ffi")]
+#![no_std]
+
+use tx_padding::consts;
+use tx_padding::ffi::{
+    txpad_pad, txpad_padded_len, txpad_unpad, TXPAD_ERR_BUFFER_TOO_SMALL,
+    TXPAD_ERR_INVALID_BLOCK_SIZE, TXPAD_ERR_INVALID_PADDING, TXPAD_ERR_NULL_POINTER, TXPAD_OK,
+};
+use tx_padding::TxPadding;
+
+#[test]
+fn ffi_round_trip() {
+    let msg = [0x5a; 300];
+    for &block_size in &[2, 4, 8, 16, 32, 64, 128, 256] {
+        for n in 0..300 {
+            let mut buf = [0; 1024];
+            buf[..n].copy_from_slice(&msg[..n]);
+            let mut padded_len = 0;
+            let code =
+                unsafe { txpad_pad(buf.as_mut_ptr(), buf.len(), n, block_size, &mut padded_len) };
+            assert_eq!(code, TXPAD_OK);
+            assert_eq!(padded_len, txpad_padded_len(n, block_size));
+
+            let (mut offset, mut len) = (0, 0);
+            let code =
+                unsafe { txpad_unpad(buf.as_ptr(), padded_len, block_size, &mut offset, &mut len) };
+            assert_eq!(code, TXPAD_OK);
+            assert_eq!(&buf[offset..offset + len], &msg[..n]);
+        }
+    }
+}
+
+#[test]
+fn ffi_errors() {
+    let mut buf = [0; 16];
+    let mut out = 0;
+    assert_eq!(txpad_padded_len(4, 12), 0);
+    assert_eq!(txpad_padded_len(usize::MAX, 16), 0);
+    assert_eq!(
+        unsafe { txpad_pad(buf.as_mut_ptr(), 16, 4, 12, &mut out) },
+        TXPAD_ERR_INVALID_BLOCK_SIZE
+    );
+    assert_eq!(
+        unsafe { txpad_pad(buf.as_mut_ptr(), 16, 4, 16, &mut out) },
+        TXPAD_ERR_BUFFER_TOO_SMALL
+    );
+    assert_eq!(
+        unsafe { txpad_pad(core::ptr::null_mut(), 16, 4, 8, &mut out) },
+        TXPAD_ERR_NULL_POINTER
+    );
+    let (mut offset, mut len) = (0, 0);
+    assert_eq!(
+        unsafe { txpad_unpad([0xff; 16].as_ptr(), 16, 8, &mut offset, &mut len) },
+        TXPAD_ERR_INVALID_PADDING
+    );
+    assert_eq!(
+        unsafe { txpad_unpad(buf.as_ptr(), 16, 8, core::ptr::null_mut(), &mut len) },
+        TXPAD_ERR_NULL_POINTER
+    );
+}
+
+#[test]
+fn ffi_matches_tx_padding() {
+    let mut buf = [0; 32];
+    buf[..3].copy_from_slice(b"ack");
+    let mut padded_len = 0;
+    assert_eq!(
+        unsafe { txpad_pad(buf.as_mut_ptr(), buf.len(), 3, 16, &mut padded_len) },
+        TXPAD_OK
+    );
+    assert_eq!(
+        TxPadding::<consts::U16>::unpad_strict(&buf[..padded_len]).unwrap(),
+        b"ack"
+    );
+}
@@ -0,0 +1,116 @@
+//! Test padding of fixed-size arrays
+#![no_std]
+#![cfg(feature = "generic-array")]
+
+mod common;
+
+use generic_array::GenericArray;
+use rand::rngs::mock::StepRng;
+
+use tx_padding::consts::{U0, U12, U15, U16, U2, U256, U4, U48, U7, U768, U8};
+use tx_padding::{InsecureRng, PaddedLen, TxPadError, TxPadding};
+
+use common::FailingRng;
+
+macro_rules! test_pad_fixed {
+    ($name:ident, $n:ty, $m:ty, $padded:ty) => {
+        #[test]
+        fn $name() {
+            let mut msg = GenericArray::<u8, $m>::default();
+            for (i, v) in msg.iter_mut().enumerate() {
+                *v = i as u8;
+            }
+            let padded_msg: GenericArray<u8, $padded> =
+                TxPadding::<$n>::pad_fixed_with_rng(&mut InsecureRng(StepRng::new(1, 1)), &msg)
+                    .unwrap();
+            let mut expected = [0; 1024];
+            expected[..msg.len()].copy_from_slice(&msg);
+            let expected = TxPadding::<$n>::pad_with_rng(
+                &mut InsecureRng(StepRng::new(1, 1)),
+                &mut expected,
+                msg.len(),
+            )
+            .unwrap();
+            assert_eq!(padded_msg.as_slice(), &expected[..]);
+            assert_eq!(TxPadding::<$n>::unpad(&padded_msg).unwrap(), msg.as_slice());
+        }
+    };
+}
+
+test_pad_fixed!(pad_fixed_empty, U8, U0, U16);
+test_pad_fixed!(pad_fixed_u2, U2, U7, U12);
+test_pad_fixed!(pad_fixed_u8, U8, U4, U16);
+test_pad_fixed!(pad_fixed_u16_max_overhead, U16, U15, U48);
+test_pad_fixed!(pad_fixed_u16_aligned, U16, U16, U48);
+test_pad_fixed!(pad_fixed_u256, U256, U256, U768);
+
+#[test]
+fn padded_len_matches() {
+    let msg = GenericArray::<u8, U15>::default();
+    let padded_msg: GenericArray<u8, PaddedLen<U15, U16>> =
+        TxPadding::<U16>::pad_fixed(&msg).unwrap();
+    assert_eq!(padded_msg.len(), TxPadding::<U16>::padded_len(15));
+}
+
+#[test]
+fn pad_fixed_reports_rng_failure() {
+    let msg = GenericArray::<u8, U4>::default();
+    assert_eq!(
+        TxPadding::<U8>::pad_fixed_with_rng(&mut FailingRng, &msg),
+        Err(TxPadError::RngFailure)
+    );
+}
+
+#[test]
+fn padded_blocks_cover_message() {
+    let mut buf = [0; 64];
+    buf[..20].copy_from_slice(&[0x5a; 20]);
+    let padded_msg =
+        TxPadding::<U16>::pad_with_rng(&mut InsecureRng(StepRng::new(1, 1)), &mut buf, 20).unwrap();
+    let mut expected = [0; 64];
+    expected[..padded_msg.len()].copy_from_slice(padded_msg);
+
+    let mut blocks = 0;
+    for (i, block) in TxPadding::<U16>::padded_blocks(padded_msg)
+        .unwrap()
+        .enumerate()
+    {
+        assert_eq!(&block[..], &expected[i * 16..(i + 1) * 16]);
+        blocks += 1;
+    }
+    assert_eq!(blocks * 16, padded_msg.len());
+
+    for block in TxPadding::<U16>::padded_blocks_mut(padded_msg).unwrap() {
+        block[0] ^= 0xff;
+    }
+    for (i, v) in padded_msg.iter().enumerate() {
+        let flip = if i % 16 == 0 { 0xff } else { 0 };
+        assert_eq!(*v, expected[i] ^ flip);
+    }
+}
+
+#[test]
+fn padded_blocks_reject_partial_block() {
+    assert_eq!(
+        TxPadding::<U16>::padded_blocks(&[0; 15]).map(|_| ()),
+        Err(TxPadError::Misaligned {
+            len: 15,
+            block_size: 16
+        })
+    );
+    assert_eq!(
+        TxPadding::<U16>::padded_blocks(&[0; 40]).map(|_| ()),
+        Err(TxPadError::Misaligned {
+            len: 40,
+            block_size: 16
+        })
+    );
+    assert_eq!(
+        TxPadding::<U16>::padded_blocks_mut(&mut [0; 33]).map(|_| ()),
+        Err(TxPadError::Misaligned {
+            len: 33,
+            block_size: 16
+        })
+    );
+    assert_eq!(TxPadding::<U16>::padded_blocks(&[]).unwrap().count(), 0);
+}
@@ -0,0 +1,61 @@
+//! Test the self-delimiting frames
+#![cfg(feature = "frame")]
+#![no_std]
+
+use rand::rngs::mock::StepRng;
+
+use tx_padding::consts;
+use tx_padding::{InsecureRng, TxPadError, TxPadding};
+
+#[test]
+fn framed_round_trip() {
+    let msg = [0x5a; 300];
+    for n in 0..msg.len() {
+        let mut out = [0; 512];
+        let frame = TxPadding::<consts::U16>::pad_framed_with_rng(
+            &mut InsecureRng(StepRng::new(1, 1)),
+            &msg[..n],
+            &mut out,
+        )
+        .unwrap();
+        let l = frame.len();
+        assert_eq!(l, TxPadding::<consts::U16>::framed_len(n));
+        let padded_len = TxPadding::<consts::U16>::padded_len(n);
+        assert_eq!(l - padded_len, if padded_len < 128 { 1 } else { 2 });
+        assert_eq!(
+            TxPadding::<consts::U16>::unpad_framed(&out).unwrap(),
+            (&msg[..n], l)
+        );
+    }
+}
+
+#[test]
+fn framed_insufficient_space() {
+    let mut out = [0; 16];
+    assert_eq!(
+        TxPadding::<consts::U8>::pad_framed(b"test", &mut out),
+        Err(TxPadError::BufferTooSmall {
+            required: 17,
+            got: 16
+        })
+    );
+}
+
+#[test]
+fn unpad_framed_rejects_illformed() {
+    let mut out = [0; 17];
+    TxPadding::<consts::U8>::pad_framed(b"test", &mut out).unwrap();
+    assert!(TxPadding::<consts::U8>::unpad_framed(&out[..16]).is_err());
+    for data in [
+        &[][..],
+        &[0],
+        &[0x80],
+        &[12, 0xf8, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0],
+        &[0x90, 0x00],
+        &[
+            0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x01,
+        ],
+    ] {
+        assert!(TxPadding::<consts::U8>::unpad_framed(data).is_err());
+    }
+}
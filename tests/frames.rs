@@ -0,0 +1,119 @@
+//! Test unpadding streams of length-delimited padded messages
+#![no_std]
+
+use rand::rngs::mock::StepRng;
+
+use tx_padding::consts;
+use tx_padding::{InsecureRng, TxPadding};
+
+/// Write the frames of the messages `msgs[..n]` for every `n` into `out`, returning their length
+fn write_frames(msg: &[u8], out: &mut [u8]) -> usize {
+    let mut len = 0;
+    for n in 0..msg.len() {
+        let padded_len = TxPadding::<consts::U8>::padded_len(n);
+        out[len..len + 4].copy_from_slice(&(padded_len as u32).to_be_bytes());
+        TxPadding::<consts::U8>::pad_into_with_rng(
+            &mut InsecureRng(StepRng::new(1, 1)),
+            &msg[..n],
+            &mut out[len + 4..],
+        )
+        .unwrap();
+        len += 4 + padded_len;
+    }
+    len
+}
+
+#[test]
+fn unpad_iter_walks_frames() {
+    let msg = [0x5a; 40];
+    let mut out = [0; 2048];
+    let len = write_frames(&msg, &mut out);
+    let mut count = 0;
+    for (n, unpadded) in TxPadding::<consts::U8>::unpad_iter(&out[..len]).enumerate() {
+        assert_eq!(unpadded.unwrap(), &msg[..n]);
+        count += 1;
+    }
+    assert_eq!(count, msg.len());
+
+    let (unpadded, consumed) = TxPadding::<consts::U8>::unpad_with_consumed(&out[..len]).unwrap();
+    assert_eq!(unpadded, b"");
+    assert_eq!(consumed, 4 + 16);
+}
+
+#[test]
+fn unpad_iter_stops_at_malformed_frame() {
+    let msg = [0x5a; 3];
+    let mut out = [0; 256];
+    let len = write_frames(&msg, &mut out);
+    let mut iter = TxPadding::<consts::U8>::unpad_iter(&out[..len - 1]);
+    assert!(iter.next().unwrap().is_ok());
+    assert!(iter.next().unwrap().is_ok());
+    assert!(iter.next().unwrap().is_err());
+    assert!(iter.next().is_none());
+
+    for data in [
+        &[][..],
+        &[0, 0, 0],
+        &[0, 0, 0, 0],
+        &[0, 0, 0, 7, 0xf8, 0, 0, 0, 0, 0, 0],
+    ] {
+        assert!(TxPadding::<consts::U8>::unpad_with_consumed(data).is_err());
+    }
+}
+
+#[test]
+fn unpad_prefix_ignores_trailing_bytes() {
+    let msg = [0x5a; 40];
+    for n in 0..msg.len() {
+        let mut buf = [0xa5; 128];
+        let padded_len = TxPadding::<consts::U8>::pad_into_with_rng(
+            &mut InsecureRng(StepRng::new(1, 1)),
+            &msg[..n],
+            &mut buf,
+        )
+        .unwrap()
+        .len();
+        let blocks = padded_len / 8;
+        for end in padded_len..buf.len() {
+            assert_eq!(
+                TxPadding::<consts::U8>::unpad_prefix(&buf[..end], blocks).unwrap(),
+                (&msg[..n], padded_len)
+            );
+        }
+        assert!(TxPadding::<consts::U8>::unpad_prefix(&buf[..padded_len - 1], blocks).is_err());
+        assert!(TxPadding::<consts::U8>::unpad_prefix(&buf, blocks + 1).is_err());
+    }
+}
+
+#[test]
+fn unpad_prefix_keeps_zeros_at_block_boundary() {
+    // The message holds `N - 1` zeros ending at a block boundary of the padded message
+    let mut msg = [0x5a; 12];
+    msg[4..7].fill(0);
+    let mut buf = [0xa5; 64];
+    let padded_len = TxPadding::<consts::U4>::pad_into_with_rng(
+        &mut InsecureRng(StepRng::new(1, 1)),
+        &msg,
+        &mut buf,
+    )
+    .unwrap()
+    .len();
+    assert_eq!(padded_len, 20);
+    assert_eq!(&buf[9..12], [0, 0, 0]);
+
+    assert_eq!(
+        TxPadding::<consts::U4>::unpad_prefix(&buf, padded_len / 4).unwrap(),
+        (&msg[..], padded_len)
+    );
+}
+
+#[test]
+fn unpad_prefix_rejects_invalid_input() {
+    for data in [
+        &[][..],
+        &[0xf8, 0, 0],
+        &[0xf8, 0, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0],
+    ] {
+        assert!(TxPadding::<consts::U8>::unpad_prefix(data, 2).is_err());
+    }
+}
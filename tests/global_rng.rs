@@ -0,0 +1,40 @@
+//! Test the RNG registered at runtime
+#![cfg(all(feature = "custom-rng", not(feature = "thread-rng")))]
+
+use rand::Error;
+
+use tx_padding::consts;
+use tx_padding::{register_rng, Padding, SecureRng, TxPadError, TxPadding};
+
+/// A generator standing in for a TRNG peripheral
+struct ConstTrng;
+
+impl SecureRng for ConstTrng {
+    fn try_fill_bytes(&self, dest: &mut [u8]) -> Result<(), Error> {
+        dest.fill(0xa5);
+        Ok(())
+    }
+}
+
+static TRNG: ConstTrng = ConstTrng;
+
+#[test]
+fn registered_rng_fills_prefix() {
+    let mut buf = [0; 16];
+    buf[..3].copy_from_slice(b"\x01\x02\x03");
+    assert_eq!(
+        TxPadding::<consts::U8>::pad_with_rng(&mut tx_padding::GlobalRng, &mut buf, 3),
+        Err(TxPadError::RngFailure)
+    );
+
+    register_rng(&TRNG);
+    let padded_msg = TxPadding::<consts::U8>::pad(&mut buf, 3, 8).unwrap();
+    assert_eq!(
+        padded_msg,
+        b"\xfb\xa5\xa5\xa5\xa5\xa5\x01\x02\x03\x00\x00\x00\x00\x00\x00\x00"
+    );
+    assert_eq!(
+        TxPadding::<consts::U8>::unpad(padded_msg).unwrap(),
+        b"\x01\x02\x03"
+    );
+}
@@ -0,0 +1,54 @@
+//! Test padding into and unpadding from fixed-capacity vectors
+#![cfg(feature = "heapless")]
+#![no_std]
+
+use tx_padding::consts;
+use tx_padding::{TxPadError, TxPadding, TxPaddingConst};
+
+#[test]
+fn pad_heapless_sizes() {
+    let msg = [0x5a; 40];
+    for n in 0..msg.len() {
+        let padded_msg = TxPadding::<consts::U8>::pad_heapless::<64>(&msg[..n]).unwrap();
+        assert_eq!(padded_msg.len(), TxPadding::<consts::U8>::padded_len(n));
+        assert_eq!(
+            TxPadding::<consts::U8>::unpad_heapless::<40>(&padded_msg).unwrap(),
+            &msg[..n]
+        );
+        let padded_msg = TxPaddingConst::<8>::pad_heapless::<64>(&msg[..n]).unwrap();
+        assert_eq!(
+            TxPaddingConst::<8>::unpad_heapless::<40>(&padded_msg).unwrap(),
+            &msg[..n]
+        );
+    }
+}
+
+#[test]
+fn reject_insufficient_capacity() {
+    assert_eq!(
+        TxPadding::<consts::U8>::pad_heapless::<15>(b"test"),
+        Err(TxPadError::BufferTooSmall {
+            required: 16,
+            got: 15
+        })
+    );
+    let padded_msg = TxPadding::<consts::U8>::pad_heapless::<16>(b"test").unwrap();
+    assert!(TxPadding::<consts::U8>::unpad_heapless::<3>(&padded_msg).is_err());
+    assert!(TxPadding::<consts::U8>::unpad_heapless::<3>(&[0xf8, 0, 0]).is_err());
+}
+
+#[test]
+fn unpad_truncate_heapless() {
+    let msg = [0x5a; 40];
+    for n in 0..msg.len() {
+        let mut buf = TxPadding::<consts::U8>::pad_heapless::<64>(&msg[..n]).unwrap();
+        TxPadding::<consts::U8>::unpad_truncate_heapless(&mut buf).unwrap();
+        assert_eq!(buf, &msg[..n]);
+        let mut buf = TxPaddingConst::<8>::pad_heapless::<64>(&msg[..n]).unwrap();
+        TxPaddingConst::<8>::unpad_truncate_heapless(&mut buf).unwrap();
+        assert_eq!(buf, &msg[..n]);
+    }
+    let mut buf = heapless::Vec::<u8, 3>::from_slice(&[0xf8, 0, 0]).unwrap();
+    assert!(TxPadding::<consts::U8>::unpad_truncate_heapless(&mut buf).is_err());
+    assert_eq!(buf, [0xf8, 0, 0]);
+}
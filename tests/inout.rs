@@ -0,0 +1,66 @@
+//! Test padding into `inout` buffers
+#![no_std]
+#![cfg(feature = "inout")]
+
+use inout::InOutBufReserved;
+use rand::rngs::mock::StepRng;
+
+use tx_padding::consts;
+use tx_padding::{InsecureRng, TxPadError, TxPadding};
+
+macro_rules! test_inout_matches_pad {
+    ($name:ident, $n:ty) => {
+        #[test]
+        fn $name() {
+            for n in 0..40 {
+                let msg = [0x5a; 40];
+                let mut expected = [0; 128];
+                expected[..n].copy_from_slice(&msg[..n]);
+                let expected = TxPadding::<$n>::pad_with_rng(
+                    &mut InsecureRng(StepRng::new(1, 1)),
+                    &mut expected,
+                    n,
+                )
+                .unwrap();
+
+                let mut out = [0xff; 128];
+                let buf = InOutBufReserved::from_slices(&msg[..n], &mut out).unwrap();
+                let mut padded_msg =
+                    TxPadding::<$n>::pad_inout_with_rng(&mut InsecureRng(StepRng::new(1, 1)), buf)
+                        .unwrap();
+                assert_eq!(padded_msg.get_in(), &expected[..]);
+                assert_eq!(padded_msg.get_out(), &expected[..]);
+
+                let mut in_place = [0; 128];
+                in_place[..n].copy_from_slice(&msg[..n]);
+                let buf = InOutBufReserved::from_mut_slice(&mut in_place, n).unwrap();
+                let padded_msg =
+                    TxPadding::<$n>::pad_inout_with_rng(&mut InsecureRng(StepRng::new(1, 1)), buf)
+                        .unwrap();
+                assert_eq!(padded_msg.get_in(), &expected[..]);
+                assert_eq!(
+                    TxPadding::<$n>::unpad(padded_msg.get_in()).unwrap(),
+                    &msg[..n]
+                );
+            }
+        }
+    };
+}
+
+test_inout_matches_pad!(inout_matches_pad_u2, consts::U2);
+test_inout_matches_pad!(inout_matches_pad_u8, consts::U8);
+test_inout_matches_pad!(inout_matches_pad_u16, consts::U16);
+
+#[test]
+fn inout_insufficient_space() {
+    let msg = [0; 8];
+    let mut out = [0; 16];
+    let buf = InOutBufReserved::from_slices(&msg, &mut out).unwrap();
+    assert_eq!(
+        TxPadding::<consts::U8>::pad_inout(buf).map(|_| ()),
+        Err(TxPadError::BufferTooSmall {
+            required: 24,
+            got: 16
+        })
+    );
+}
@@ -0,0 +1,106 @@
+//! Test the block cipher mode wrappers
+#![no_std]
+#![cfg(feature = "integration")]
+
+use aes::Aes128;
+use cipher::KeyIvInit;
+use rand::rngs::mock::StepRng;
+
+use tx_padding::consts::{U16, U32, U8};
+use tx_padding::integration::{TxDecryptor, TxEncryptor};
+use tx_padding::{InsecureRng, TxPadError, TxPadding};
+
+const KEY: [u8; 16] = [0x42; 16];
+const IV: [u8; 16] = [0x24; 16];
+
+fn encryptor() -> cbc::Encryptor<Aes128> {
+    cbc::Encryptor::new(&KEY.into(), &IV.into())
+}
+
+fn decryptor() -> cbc::Decryptor<Aes128> {
+    cbc::Decryptor::new(&KEY.into(), &IV.into())
+}
+
+macro_rules! test_round_trip {
+    ($name:ident, $n:ty, $len:expr) => {
+        #[test]
+        fn $name() {
+            let msg = [0xabu8; $len];
+            let mut buf = [0; TxPadding::<$n>::padded_len($len)];
+            buf[..$len].copy_from_slice(&msg);
+            let ciphertext = TxEncryptor::<_, $n>::new(encryptor())
+                .encrypt_padded(&mut buf, $len)
+                .unwrap();
+            assert_eq!(ciphertext.len(), TxPadding::<$n>::padded_len($len));
+            let decrypted = TxDecryptor::<_, $n>::new(decryptor())
+                .decrypt_unpadded(&mut buf)
+                .unwrap();
+            assert_eq!(decrypted, &msg[..]);
+        }
+    };
+}
+
+test_round_trip!(round_trip_u16_empty, U16, 0);
+test_round_trip!(round_trip_u16_15, U16, 15);
+test_round_trip!(round_trip_u16_33, U16, 33);
+test_round_trip!(round_trip_u32_17, U32, 17);
+
+#[test]
+fn decrypts_to_tx_padding() {
+    let mut buf = [0; 32];
+    buf[..3].copy_from_slice(b"abc");
+    TxEncryptor::<_, U16>::new(encryptor())
+        .encrypt_padded_with_rng(&mut InsecureRng(StepRng::new(0, 0)), &mut buf, 3)
+        .unwrap();
+    let padded_msg = {
+        use cipher::block_padding::NoPadding;
+        use cipher::BlockDecryptMut;
+        decryptor()
+            .decrypt_padded_mut::<NoPadding>(&mut buf)
+            .unwrap()
+    };
+    assert_eq!(TxPadding::<U16>::unpad(padded_msg).unwrap(), b"abc");
+}
+
+#[test]
+fn reject_incompatible_block_size() {
+    let mut buf = [0; 16];
+    assert_eq!(
+        TxEncryptor::<_, U8>::new(encryptor()).encrypt_padded(&mut buf, 3),
+        Err(TxPadError::BlockSizeMismatch {
+            expected: 16,
+            got: 8
+        })
+    );
+    assert!(TxDecryptor::<_, U8>::new(decryptor())
+        .decrypt_unpadded(&mut buf)
+        .is_err());
+}
+
+#[test]
+fn reject_small_buffer() {
+    let mut buf = [0; 16];
+    assert_eq!(
+        TxEncryptor::<_, U16>::new(encryptor()).encrypt_padded(&mut buf, 3),
+        Err(TxPadError::BufferTooSmall {
+            required: 32,
+            got: 16
+        })
+    );
+}
+
+#[test]
+fn reject_corrupted_ciphertext() {
+    let mut buf = [0; 32];
+    buf[..3].copy_from_slice(b"abc");
+    TxEncryptor::<_, U16>::new(encryptor())
+        .encrypt_padded(&mut buf, 3)
+        .unwrap();
+    buf[31] ^= 1;
+    assert!(TxDecryptor::<_, U16>::new(decryptor())
+        .decrypt_unpadded(&mut buf)
+        .is_err());
+    assert!(TxDecryptor::<_, U16>::new(decryptor())
+        .decrypt_unpadded(&mut buf[..31])
+        .is_err());
+}
@@ -0,0 +1,129 @@
+//! Test the `std::io` adapters
+#![cfg(feature = "std")]
+
+use std::io::{Read, Write};
+
+use rand::rngs::mock::StepRng;
+
+use tx_padding::consts;
+use tx_padding::{InsecureRng, PadWriter, Padding, TxPadding, UnpadReader};
+
+fn message(n: usize) -> Vec<u8> {
+    (0..n).map(|i| (i * 7) as u8).collect()
+}
+
+#[test]
+fn pad_writer_matches_pad() {
+    for &n in &[0, 1, 15, 16, 17, 5000, 10000] {
+        let message = message(n);
+        let mut expected = vec![0; TxPadding::<consts::U16>::padded_len(n)];
+        expected[..n].copy_from_slice(&message);
+        TxPadding::<consts::U16>::pad_with_rng(
+            &mut InsecureRng(StepRng::new(3, 5)),
+            &mut expected,
+            n,
+        )
+        .unwrap();
+
+        for &chunk in &[1, 13, 4096, 10000] {
+            let mut writer = PadWriter::<_, consts::U16>::with_rng(
+                Vec::new(),
+                &mut InsecureRng(StepRng::new(3, 5)),
+                n,
+            )
+            .unwrap();
+            for piece in message.chunks(chunk) {
+                writer.write_all(piece).unwrap();
+            }
+            assert_eq!(writer.finish().unwrap(), expected);
+        }
+    }
+}
+
+#[test]
+fn pad_writer_round_trip() {
+    let message = message(100);
+    let mut writer = PadWriter::<_, consts::U8>::new(Vec::new(), message.len()).unwrap();
+    writer.write_all(&message).unwrap();
+    let padded_msg = writer.finish().unwrap();
+    assert_eq!(
+        TxPadding::<consts::U8>::unpad(&padded_msg).unwrap(),
+        &message[..]
+    );
+}
+
+#[test]
+fn pad_writer_rejects_length_mismatch() {
+    let mut writer = PadWriter::<_, consts::U8>::new(Vec::new(), 3).unwrap();
+    assert!(writer.write_all(b"\x01\x02\x03\x04").is_err());
+    writer.write_all(b"\x01\x02").unwrap();
+    assert!(writer.finish().is_err());
+}
+
+#[test]
+fn unpad_reader_round_trip() {
+    for &n in &[0, 1, 15, 16, 17, 5000, 10000] {
+        let message = message(n);
+        let mut padded_msg = vec![0; TxPadding::<consts::U16>::padded_len(n)];
+        padded_msg[..n].copy_from_slice(&message);
+        TxPadding::<consts::U16>::pad(&mut padded_msg, n, 16).unwrap();
+
+        let mut reader = UnpadReader::<_, consts::U16>::new(&padded_msg[..]);
+        let mut out = Vec::new();
+        reader.read_to_end(&mut out).unwrap();
+        assert_eq!(out, message);
+
+        let mut reader = UnpadReader::<_, consts::U16>::new(&padded_msg[..]);
+        let mut out = Vec::new();
+        let mut buf = [0; 7];
+        loop {
+            match reader.read(&mut buf).unwrap() {
+                0 => break,
+                k => out.extend_from_slice(&buf[..k]),
+            }
+        }
+        assert_eq!(out, message);
+    }
+}
+
+#[test]
+fn unpad_reader_rejects_illformed() {
+    let padded_msg = [0xF8, 0, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0];
+    let mut out = Vec::new();
+    let err = UnpadReader::<_, consts::U8>::new(&padded_msg[..])
+        .read_to_end(&mut out)
+        .unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+
+    let mut out = Vec::new();
+    assert!(UnpadReader::<_, consts::U8>::new(&padded_msg[..3])
+        .read_to_end(&mut out)
+        .is_err());
+}
+
+#[cfg(feature = "frame")]
+#[test]
+fn framed_io_round_trip() {
+    let mut stream = Vec::new();
+    for n in 0..300 {
+        TxPadding::<consts::U16>::write_framed(&mut stream, &message(n)).unwrap();
+    }
+    let mut reader = &stream[..];
+    let mut buf = Vec::new();
+    for n in 0..300 {
+        let msg = TxPadding::<consts::U16>::read_framed(&mut reader, &mut buf).unwrap();
+        assert_eq!(msg, &message(n)[..]);
+    }
+    assert!(reader.is_empty());
+
+    let mut reader = &stream[..stream.len() - 1];
+    for _ in 0..299 {
+        TxPadding::<consts::U16>::read_framed(&mut reader, &mut buf).unwrap();
+    }
+    assert_eq!(
+        TxPadding::<consts::U16>::read_framed(&mut reader, &mut buf)
+            .unwrap_err()
+            .kind(),
+        std::io::ErrorKind::UnexpectedEof
+    );
+}
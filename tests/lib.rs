@@ -1,13 +1,17 @@
 //! Test the padding scheme of different block sizes and message length
 #![no_std]
 
+#[cfg(any(feature = "os-rng", feature = "thread-rng"))]
 use typenum::marker_traits::Unsigned;
 
 use tx_padding::consts;
-use tx_padding::{Padding, TxPadding};
+#[cfg(any(feature = "os-rng", feature = "thread-rng"))]
+use tx_padding::Padding;
+use tx_padding::{InsecureRng, TxPadError, TxPadding};
 
 macro_rules! create_passing_test {
     ($name:ident, $block_size:ty, $buf_len:expr, $message:expr, $padding_start:expr, $padded_msg:expr) => {
+        #[cfg(any(feature = "os-rng", feature = "thread-rng"))]
         #[test]
         fn $name() {
             let message = $message;
@@ -18,7 +22,7 @@ macro_rules! create_passing_test {
                 TxPadding::<$block_size>::pad(&mut buf, n, <$block_size>::to_usize()).unwrap();
             assert_eq!(&padded_msg[$padding_start..], $padded_msg);
             assert_eq!(
-                (padded_msg[0] & (<$block_size>::to_u8() - 1)) as usize + 2,
+                (padded_msg[0] & (<$block_size>::to_usize() - 1) as u8) as usize + 2,
                 $padding_start - 1
             );
             assert_eq!(
@@ -123,6 +127,75 @@ create_passing_test!(
     b"\x01\x02\x03\x04\x05\x06\x07\x00\x00\x00\x00\x00\x00\x00"
 );
 
+macro_rules! create_round_trip_test {
+    ($name:ident, $block_size:ty) => {
+        #[cfg(any(feature = "os-rng", feature = "thread-rng"))]
+        #[test]
+        fn $name() {
+            const N: usize = <$block_size>::USIZE;
+            let msg = [0x5a; 3 * N];
+            for n in 0..=3 * N {
+                let mut buf = [0xff; TxPadding::<$block_size>::padded_len(3 * N)];
+                buf[..n].copy_from_slice(&msg[..n]);
+                let padded_msg = TxPadding::<$block_size>::pad(&mut buf, n, N).unwrap();
+                let l = padded_msg.len();
+                let pad_len = (padded_msg[0] & (N - 1) as u8) as usize + 2;
+                assert_eq!(l, TxPadding::<$block_size>::padded_len(n));
+                assert_eq!(l % N, 0);
+                assert!(pad_len <= N + 1);
+                assert_eq!(padded_msg[0] | (N - 1) as u8, 0xff);
+                assert_eq!(&padded_msg[1 + pad_len..l - (N - 1)], &msg[..n]);
+                assert!(padded_msg[l - (N - 1)..].iter().all(|&v| v == 0));
+                assert_eq!(
+                    TxPadding::<$block_size>::unpad(padded_msg).unwrap(),
+                    &msg[..n]
+                );
+            }
+        }
+    };
+}
+
+create_round_trip_test!(round_trip_bs_2, consts::U2);
+create_round_trip_test!(round_trip_bs_4, consts::U4);
+create_round_trip_test!(round_trip_bs_8, consts::U8);
+create_round_trip_test!(round_trip_bs_16, consts::U16);
+create_round_trip_test!(round_trip_bs_32, consts::U32);
+create_round_trip_test!(round_trip_bs_64, consts::U64);
+create_round_trip_test!(round_trip_bs_128, consts::U128);
+create_round_trip_test!(round_trip_bs_256, consts::U256);
+
+#[cfg(any(feature = "os-rng", feature = "thread-rng"))]
+#[test]
+fn header_fills_byte_at_bs_256() {
+    let mut buf = [0; TxPadding::<consts::U256>::padded_len(255)];
+    let padded_msg = TxPadding::<consts::U256>::pad(&mut buf, 255, 256).unwrap();
+    assert_eq!(padded_msg[0], 0xff);
+    assert_eq!(padded_msg.len(), 1 + 257 + 255 + 255);
+    assert_eq!(
+        TxPadding::<consts::U256>::unpad(padded_msg).unwrap(),
+        &[0; 255][..]
+    );
+
+    let mut buf = [0; TxPadding::<consts::U256>::padded_len(254)];
+    let padded_msg = TxPadding::<consts::U256>::pad(&mut buf, 254, 256).unwrap();
+    assert_eq!(padded_msg[0], 0x00);
+    assert_eq!(padded_msg.len(), 1 + 2 + 254 + 255);
+    assert_eq!(
+        TxPadding::<consts::U256>::unpad(padded_msg).unwrap(),
+        &[0; 254][..]
+    );
+}
+
+#[test]
+fn unpad_longest_prefix_at_bs_256() {
+    let mut data = [0; 1 + 257 + 255];
+    data[0] = 0xff;
+    assert!(TxPadding::<consts::U256>::unpad(&data[..258]).is_err());
+    assert!(TxPadding::<consts::U256>::unpad(&data[..512]).is_err());
+    assert_eq!(TxPadding::<consts::U256>::unpad(&data).unwrap(), b"");
+}
+
+#[cfg(any(feature = "os-rng", feature = "thread-rng"))]
 #[test]
 fn reject_insufficient_space() {
     let message = b"\x01\x02\x03";
@@ -132,6 +205,7 @@ fn reject_insufficient_space() {
     assert!(TxPadding::<consts::U8>::pad(&mut buf, n, 8).is_err());
 }
 
+#[cfg(any(feature = "os-rng", feature = "thread-rng"))]
 #[test]
 fn reject_mismatch_size() {
     let message = b"\x01\x02\x03";
@@ -152,3 +226,475 @@ fn reject_illformed_padded_message() {
             .is_err()
     );
 }
+
+#[test]
+fn report_pad_error_reason() {
+    let message = b"\x01\x02\x03";
+    let mut buf = [0; 15];
+    let n = message.len();
+    buf[..n].copy_from_slice(message);
+    let mut rng = InsecureRng(rand::rngs::mock::StepRng::new(0, 1));
+    assert_eq!(
+        TxPadding::<consts::U8>::pad_with_rng(&mut rng, &mut buf, n),
+        Err(TxPadError::BufferTooSmall {
+            required: 16,
+            got: 15
+        })
+    );
+}
+
+#[cfg(feature = "diagnostics")]
+#[test]
+fn report_first_non_zero_byte() {
+    use tx_padding::UnpadErrorKind;
+
+    let mut buf = [0; 768];
+    let l = TxPadding::<consts::U256>::pad(&mut buf, 0, 256)
+        .unwrap()
+        .len();
+    for i in l - 255..l {
+        let mut data = buf;
+        data[i] = 1;
+        data[l - 1] = 1;
+        assert_eq!(
+            TxPadding::<consts::U256>::unpad_verbose(&data[..l]),
+            Err(UnpadErrorKind::NonZeroTail { index: i })
+        );
+    }
+}
+
+#[cfg(feature = "diagnostics")]
+#[test]
+fn report_unpad_error_reason() {
+    use tx_padding::UnpadErrorKind;
+
+    assert_eq!(
+        TxPadding::<consts::U8>::unpad_verbose(&[]),
+        Err(UnpadErrorKind::EmptyInput)
+    );
+    assert_eq!(
+        TxPadding::<consts::U8>::unpad_verbose(&[0xF8, 0, 0]),
+        Err(UnpadErrorKind::TooShort)
+    );
+    assert_eq!(
+        TxPadding::<consts::U8>::unpad_verbose(&[
+            0xF8, 0, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0
+        ]),
+        Err(UnpadErrorKind::NonZeroTail { index: 9 })
+    );
+    assert_eq!(
+        TxPadding::<consts::U8>::unpad_verbose(&[0xF8, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]),
+        Ok(&[0; 5][..])
+    );
+    assert_eq!(
+        TxPadding::<consts::U8>::unpad_strict_verbose(&[
+            0xF8, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0
+        ]),
+        Err(UnpadErrorKind::LengthNotBlockAligned)
+    );
+}
+
+#[test]
+fn unpad_strict_rejects_misaligned() {
+    let data = [
+        0xF8, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+    ];
+    for n in 0..data.len() {
+        let strict = TxPadding::<consts::U8>::unpad_strict(&data[..n]);
+        if n % 8 == 0 {
+            assert_eq!(strict.ok(), TxPadding::<consts::U8>::unpad(&data[..n]).ok());
+        } else {
+            assert!(strict.is_err());
+        }
+    }
+}
+
+#[test]
+fn padded_len_matches_pad() {
+    let mut buf = [0; 64];
+    let mut rng = InsecureRng(rand::rngs::mock::StepRng::new(0, 1));
+    for n in 0..40 {
+        let padded_msg = TxPadding::<consts::U8>::pad_with_rng(&mut rng, &mut buf, n).unwrap();
+        assert_eq!(padded_msg.len(), TxPadding::<consts::U8>::padded_len(n));
+        assert_eq!(padded_msg.len() - n, TxPadding::<consts::U8>::overhead(n));
+        assert!(TxPadding::<consts::U8>::overhead(n) <= TxPadding::<consts::U8>::max_overhead());
+        assert!(TxPadding::<consts::U8>::overhead(n) >= TxPadding::<consts::U8>::MIN_OVERHEAD);
+    }
+    assert_eq!(
+        TxPadding::<consts::U8>::overhead(7),
+        TxPadding::<consts::U8>::max_overhead()
+    );
+    assert_eq!(
+        TxPadding::<consts::U8>::overhead(6),
+        TxPadding::<consts::U8>::MIN_OVERHEAD
+    );
+}
+
+#[test]
+fn associated_consts() {
+    assert_eq!(TxPadding::<consts::U2>::BLOCK_SIZE, 2);
+    assert_eq!(TxPadding::<consts::U256>::BLOCK_SIZE, 256);
+    assert_eq!(TxPadding::<consts::U16>::MIN_OVERHEAD, 18);
+    assert_eq!(TxPadding::<consts::U16>::MAX_OVERHEAD, 33);
+    assert_eq!(
+        TxPadding::<consts::U16>::MAX_OVERHEAD,
+        TxPadding::<consts::U16>::max_overhead()
+    );
+    let buf = [0u8; 40 + TxPadding::<consts::U16>::MAX_OVERHEAD];
+    for n in 0..=40 {
+        assert!(TxPadding::<consts::U16>::padded_len(n) <= buf.len());
+    }
+}
+
+#[test]
+fn reserve_finish_matches_pad() {
+    use rand::rngs::mock::StepRng;
+
+    for n in 0..40 {
+        let msg = [0x5a; 40];
+        let mut expected = [0; 128];
+        expected[..n].copy_from_slice(&msg[..n]);
+        let expected = TxPadding::<consts::U16>::pad_with_rng(
+            &mut InsecureRng(StepRng::new(1, 1)),
+            &mut expected,
+            n,
+        )
+        .unwrap();
+
+        let mut buf = [0xff; 128];
+        let offset = TxPadding::<consts::U16>::reserve(&buf, n).unwrap();
+        buf[offset..offset + n].copy_from_slice(&msg[..n]);
+        let padded_msg = TxPadding::<consts::U16>::finish_with_rng(
+            &mut InsecureRng(StepRng::new(1, 1)),
+            &mut buf,
+            n,
+        )
+        .unwrap();
+        assert_eq!(padded_msg, expected);
+    }
+}
+
+#[test]
+fn reserve_insufficient_space() {
+    let buf = [0; 16];
+    assert_eq!(
+        TxPadding::<consts::U8>::reserve(&buf, 7),
+        Err(TxPadError::BufferTooSmall {
+            required: 24,
+            got: 16
+        })
+    );
+}
+
+#[test]
+fn pad_into_matches_pad() {
+    use rand::rngs::mock::StepRng;
+
+    for n in 0..40 {
+        let msg = [0x5a; 40];
+        let mut expected = [0; 128];
+        expected[..n].copy_from_slice(&msg[..n]);
+        let expected = TxPadding::<consts::U16>::pad_with_rng(
+            &mut InsecureRng(StepRng::new(1, 1)),
+            &mut expected,
+            n,
+        )
+        .unwrap();
+
+        let mut out = [0xff; 128];
+        let padded_msg = TxPadding::<consts::U16>::pad_into_with_rng(
+            &mut InsecureRng(StepRng::new(1, 1)),
+            &msg[..n],
+            &mut out,
+        )
+        .unwrap();
+        assert_eq!(padded_msg, expected);
+    }
+}
+
+#[test]
+fn pad_assume_zeroed_matches_pad() {
+    use rand::rngs::mock::StepRng;
+
+    for n in 0..40 {
+        let mut expected = [0xff; 128];
+        expected[..n].fill(0x5a);
+        let expected = TxPadding::<consts::U16>::pad_with_rng(
+            &mut InsecureRng(StepRng::new(1, 1)),
+            &mut expected,
+            n,
+        )
+        .unwrap();
+
+        let mut buf = [0; 128];
+        buf[..n].fill(0x5a);
+        let padded_msg = TxPadding::<consts::U16>::pad_assume_zeroed_with_rng(
+            &mut InsecureRng(StepRng::new(1, 1)),
+            &mut buf,
+            n,
+        )
+        .unwrap();
+        assert_eq!(padded_msg, expected);
+    }
+}
+
+#[cfg(any(feature = "os-rng", feature = "thread-rng"))]
+#[test]
+fn pad_into_insufficient_space() {
+    let mut out = [0; 16];
+    assert_eq!(
+        TxPadding::<consts::U8>::pad_into(b"1234567", &mut out),
+        Err(TxPadError::BufferTooSmall {
+            required: 24,
+            got: 16
+        })
+    );
+}
+
+#[cfg(feature = "uninit")]
+#[test]
+fn pad_uninit_matches_pad() {
+    use core::mem::MaybeUninit;
+    use rand::rngs::mock::StepRng;
+
+    for n in 0..40 {
+        let msg = [0x5a; 40];
+        let mut expected = [0; 128];
+        expected[..n].copy_from_slice(&msg[..n]);
+        let expected = TxPadding::<consts::U16>::pad_with_rng(
+            &mut InsecureRng(StepRng::new(1, 1)),
+            &mut expected,
+            n,
+        )
+        .unwrap();
+
+        let mut out = [MaybeUninit::uninit(); 128];
+        let padded_msg = TxPadding::<consts::U16>::pad_uninit_with_rng(
+            &mut InsecureRng(StepRng::new(1, 1)),
+            &msg[..n],
+            &mut out,
+        )
+        .unwrap();
+        assert_eq!(padded_msg, expected);
+    }
+}
+
+#[cfg(any(feature = "os-rng", feature = "thread-rng"))]
+#[test]
+fn unpad_range_matches_unpad() {
+    for n in 0..40 {
+        let mut buf = [0x5a; 128];
+        let padded_msg = TxPadding::<consts::U16>::pad(&mut buf, n, 16).unwrap();
+        let range = TxPadding::<consts::U16>::unpad_range(padded_msg).unwrap();
+        assert_eq!(
+            &padded_msg[range],
+            TxPadding::<consts::U16>::unpad(padded_msg).unwrap()
+        );
+    }
+    assert!(TxPadding::<consts::U8>::unpad_range(&[]).is_err());
+    assert!(TxPadding::<consts::U8>::unpad_range(&[0xf8, 0, 0]).is_err());
+}
+
+#[test]
+fn is_valid_matches_unpad() {
+    let mut data = [0u8; 24];
+    for l in 0..=data.len() {
+        for header in 0..=255u8 {
+            data[0] = header;
+            data[l.saturating_sub(1)] |= (l % 3 == 0) as u8;
+            assert_eq!(
+                TxPadding::<consts::U8>::is_valid(&data[..l]),
+                TxPadding::<consts::U8>::unpad(&data[..l]).is_ok()
+            );
+            data[1..].fill(0);
+        }
+    }
+}
+
+#[cfg(any(feature = "os-rng", feature = "thread-rng"))]
+#[test]
+fn unpad_trusted_skips_tail_check() {
+    for n in 0..40 {
+        let mut buf = [0x5a; 128];
+        let padded_msg = TxPadding::<consts::U16>::pad(&mut buf, n, 16).unwrap();
+        let msg = TxPadding::<consts::U16>::unpad(padded_msg).unwrap();
+        assert_eq!(
+            TxPadding::<consts::U16>::unpad_trusted(padded_msg).unwrap(),
+            msg
+        );
+        let l = padded_msg.len();
+        padded_msg[l - 1] = 1;
+        assert!(TxPadding::<consts::U16>::unpad(padded_msg).is_err());
+        assert_eq!(
+            TxPadding::<consts::U16>::unpad_trusted(padded_msg).unwrap(),
+            &[0x5a; 40][..n]
+        );
+    }
+    assert!(TxPadding::<consts::U8>::unpad_trusted(&[]).is_err());
+    assert!(TxPadding::<consts::U8>::unpad_trusted(&[0xf8, 0, 0]).is_err());
+    assert!(TxPadding::<consts::U8>::unpad_trusted(&[0xff; 16]).is_err());
+}
+
+#[cfg(any(feature = "os-rng", feature = "thread-rng"))]
+#[test]
+fn peek_msg_len_matches_unpad() {
+    for n in 0..40 {
+        let mut buf = [0x5a; 128];
+        let padded_msg = TxPadding::<consts::U16>::pad(&mut buf, n, 16).unwrap();
+        let l = padded_msg.len();
+        assert_eq!(
+            TxPadding::<consts::U16>::peek_msg_len(&padded_msg[..16], l).unwrap(),
+            n
+        );
+    }
+    assert!(TxPadding::<consts::U8>::peek_msg_len(&[], 16).is_err());
+    assert!(TxPadding::<consts::U8>::peek_msg_len(&[0xff], 16).is_err());
+    assert!(TxPadding::<consts::U8>::peek_msg_len(&[0xf8], 10).is_ok());
+}
+
+#[cfg(any(feature = "os-rng", feature = "thread-rng"))]
+#[test]
+fn unpad_in_place_scrubs_prefix() {
+    for n in 0..40 {
+        let mut buf = [0x5a; 128];
+        let padded_msg = TxPadding::<consts::U16>::pad(&mut buf, n, 16).unwrap();
+        let prefix_len = padded_msg.len() - 15 - n;
+        let msg = TxPadding::<consts::U16>::unpad_in_place(padded_msg).unwrap();
+        assert_eq!(msg, &[0x5a; 40][..n]);
+        assert!(buf[..prefix_len].iter().all(|&v| v == 0));
+    }
+}
+
+#[test]
+fn unpad_in_place_keeps_illformed() {
+    let mut buf = [0xf8, 1, 2, 3, 4, 5, 6, 7, 8, 1, 0, 0, 0, 0, 0, 0];
+    let copy = buf;
+    assert!(TxPadding::<consts::U8>::unpad_in_place(&mut buf).is_err());
+    assert_eq!(buf, copy);
+}
+
+#[test]
+fn random_header_round_trip() {
+    use rand::rngs::mock::StepRng;
+
+    let mut rng = InsecureRng(StepRng::new(0x5a5a_5a5a_5a5a_5a5a, 0x0101_0101_0101_0101));
+    let mut seen_upper = 0u8;
+    for n in 0..40 {
+        let mut buf = [0x5a; 128];
+        let padded_msg =
+            TxPadding::<consts::U16>::pad_random_header_with_rng(&mut rng, &mut buf, n).unwrap();
+        seen_upper |= !padded_msg[0] & 0xf0;
+        assert_eq!(
+            TxPadding::<consts::U16>::unpad(padded_msg).unwrap(),
+            &[0x5a; 40][..n]
+        );
+    }
+    assert_ne!(seen_upper, 0);
+}
+
+#[cfg(any(feature = "os-rng", feature = "thread-rng"))]
+#[test]
+fn filled_round_trip() {
+    for &fill in &[0x00, 0xff, 0x5a] {
+        for n in 0..40 {
+            let mut buf = [0x5a; 128];
+            let padded_msg = TxPadding::<consts::U16>::pad_filled(&mut buf, n, fill).unwrap();
+            let l = padded_msg.len();
+            assert!(padded_msg[l - 15..].iter().all(|&v| v == fill));
+            assert_eq!(
+                TxPadding::<consts::U16>::unpad_filled(padded_msg, fill).unwrap(),
+                &[0x5a; 40][..n]
+            );
+            if fill != 0 {
+                assert!(TxPadding::<consts::U16>::unpad(padded_msg).is_err());
+                assert!(TxPadding::<consts::U16>::unpad_filled(padded_msg, !fill).is_err());
+            }
+        }
+    }
+}
+
+#[test]
+fn reject_overflowing_length() {
+    let mut buf = [0; 16];
+    assert_eq!(
+        TxPadding::<consts::U8>::checked_padded_len(usize::MAX),
+        None
+    );
+    assert_eq!(
+        TxPadding::<consts::U8>::checked_padded_len(usize::MAX - 5),
+        None
+    );
+    assert_eq!(TxPadding::<consts::U8>::checked_padded_len(4), Some(16));
+    assert_eq!(TxPadding::<consts::U8>::checked_padded_len(7), Some(24));
+    for &pos in &[usize::MAX, usize::MAX - 1, isize::MAX as usize] {
+        assert_eq!(
+            TxPadding::<consts::U8>::pad_with_rng(
+                &mut InsecureRng(rand::rngs::mock::StepRng::new(0, 1)),
+                &mut buf,
+                pos
+            ),
+            Err(TxPadError::PositionOutOfBounds { pos, len: 16 })
+        );
+        assert_eq!(
+            TxPadding::<consts::U8>::reserve(&buf, pos),
+            Err(TxPadError::MessageTooLong)
+        );
+    }
+}
+
+macro_rules! create_max_msg_len_test {
+    ($name:ident, $block_size:ty) => {
+        #[test]
+        fn $name() {
+            let max = TxPadding::<$block_size>::MAX_MSG_LEN;
+            let buf = [0; 16];
+            let padded_len = TxPadding::<$block_size>::checked_padded_len(max).unwrap();
+            assert!(padded_len <= isize::MAX as usize);
+            assert!(TxPadding::<$block_size>::checked_padded_len(max + 1)
+                .map_or(true, |l| l > isize::MAX as usize));
+            assert_eq!(
+                TxPadding::<$block_size>::reserve(&buf, max),
+                Err(TxPadError::BufferTooSmall {
+                    required: padded_len,
+                    got: 16
+                })
+            );
+            assert_eq!(
+                TxPadding::<$block_size>::reserve(&buf, max + 1),
+                Err(TxPadError::MessageTooLong)
+            );
+        }
+    };
+}
+
+create_max_msg_len_test!(max_msg_len_bs_2, consts::U2);
+create_max_msg_len_test!(max_msg_len_bs_16, consts::U16);
+create_max_msg_len_test!(max_msg_len_bs_256, consts::U256);
+
+#[test]
+fn checked_padded_len_matches() {
+    for n in 0..100 {
+        assert_eq!(
+            TxPadding::<consts::U16>::checked_padded_len(n),
+            Some(TxPadding::<consts::U16>::padded_len(n))
+        );
+    }
+}
+
+#[cfg(any(feature = "os-rng", feature = "thread-rng"))]
+#[test]
+fn reject_pos_out_of_bounds() {
+    let mut buf = [0; 16];
+    assert!(TxPadding::<consts::U8>::pad(&mut buf, 17, 8).is_err());
+    assert_eq!(
+        TxPadding::<consts::U8>::pad_random_header(&mut buf, usize::MAX),
+        Err(TxPadError::PositionOutOfBounds {
+            pos: usize::MAX,
+            len: 16
+        })
+    );
+    assert_eq!(
+        TxPadding::<consts::U8>::pad_min(&mut buf, 17, 0),
+        Err(TxPadError::PositionOutOfBounds { pos: 17, len: 16 })
+    );
+}
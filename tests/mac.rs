@@ -0,0 +1,64 @@
+//! Test the authenticated sibling scheme
+#![cfg(feature = "mac")]
+#![no_std]
+
+use hmac::{Hmac, Mac};
+use rand::rngs::mock::StepRng;
+use sha2::Sha256;
+
+use tx_padding::consts;
+use tx_padding::{InsecureRng, TxPadding, TxPaddingMac};
+
+type HmacSha256 = Hmac<Sha256>;
+
+fn mac(key: &[u8]) -> HmacSha256 {
+    HmacSha256::new_from_slice(key).unwrap()
+}
+
+macro_rules! test_mac_round_trip {
+    ($name:ident, $n:ty, $tag_len:expr) => {
+        #[test]
+        fn $name() {
+            type Scheme = TxPaddingMac<$n, HmacSha256>;
+            assert_eq!(Scheme::tag_len(), $tag_len);
+            for n in 0..40 {
+                let msg = &[0x5a; 40][..n];
+                let mut buf = [0xff; 1024];
+                buf[..n].copy_from_slice(msg);
+                let padded_msg = Scheme::pad_with_rng(
+                    &mut InsecureRng(StepRng::new(1, 1)),
+                    mac(b"key"),
+                    &mut buf,
+                    n,
+                )
+                .unwrap();
+                let l = padded_msg.len();
+                assert_eq!(l, TxPadding::<$n>::padded_len(n));
+                assert_eq!(Scheme::unpad(mac(b"key"), padded_msg).unwrap(), msg);
+                assert!(Scheme::unpad(mac(b"other key"), padded_msg).is_err());
+                for i in 0..l {
+                    padded_msg[i] ^= 0x10;
+                    assert!(Scheme::unpad(mac(b"key"), padded_msg).is_err());
+                    padded_msg[i] ^= 0x10;
+                }
+            }
+        }
+    };
+}
+
+test_mac_round_trip!(mac_round_trip_u16, consts::U16, 15);
+test_mac_round_trip!(mac_round_trip_u64, consts::U64, 32);
+
+#[test]
+fn tag_matches_hmac() {
+    let mut buf = [0; 32];
+    buf[..11].copy_from_slice(b"hello world");
+    let padded_msg =
+        TxPaddingMac::<consts::U16, HmacSha256>::pad(mac(b"key"), &mut buf, 11).unwrap();
+    let l = padded_msg.len();
+    let tag = mac(b"key")
+        .chain_update(&padded_msg[..l - 15])
+        .finalize()
+        .into_bytes();
+    assert_eq!(&padded_msg[l - 15..], &tag[..15]);
+}
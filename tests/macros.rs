@@ -0,0 +1,43 @@
+//! Test the setup macros
+#![no_std]
+
+use rand::rngs::mock::StepRng;
+
+use tx_padding::consts::{U16, U2, U256, U8};
+use tx_padding::{padded_buf, InsecureRng, TxPadding};
+
+const MSG_LEN: usize = 20;
+
+#[test]
+fn padded_buf_is_zeroed() {
+    let buf = padded_buf!(U8, 4);
+    assert_eq!(buf.len(), 16);
+    assert!(buf.iter().all(|&v| v == 0));
+}
+
+#[test]
+fn padded_buf_matches_padded_len() {
+    assert_eq!(padded_buf!(U2, 0).len(), TxPadding::<U2>::padded_len(0));
+    assert_eq!(padded_buf!(U16, 15).len(), TxPadding::<U16>::padded_len(15));
+    assert_eq!(
+        padded_buf!(U16, MSG_LEN).len(),
+        TxPadding::<U16>::padded_len(MSG_LEN)
+    );
+    assert_eq!(
+        padded_buf!(U256, 2 * MSG_LEN).len(),
+        TxPadding::<U256>::padded_len(2 * MSG_LEN)
+    );
+}
+
+#[test]
+fn padded_buf_round_trip() {
+    let mut buf = padded_buf!(U16, MSG_LEN);
+    buf[..MSG_LEN].fill(0x5a);
+    let padded_msg =
+        TxPadding::<U16>::pad_with_rng(&mut InsecureRng(StepRng::new(1, 1)), &mut buf, MSG_LEN)
+            .unwrap();
+    assert_eq!(
+        TxPadding::<U16>::unpad(padded_msg).unwrap(),
+        &[0x5a; MSG_LEN][..]
+    );
+}
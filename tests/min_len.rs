@@ -0,0 +1,63 @@
+//! Test padding to a minimum output length
+#![no_std]
+
+use rand::rngs::mock::StepRng;
+
+use tx_padding::consts;
+use tx_padding::{InsecureRng, TxPadError, TxPadding};
+
+#[test]
+fn pad_min_round_trip() {
+    let msg = [0x5a; 300];
+    for &min_len in &[0, 1, 64, 100, 256] {
+        for n in 0..300 {
+            let mut buf = [0xff; 512];
+            buf[..n].copy_from_slice(&msg[..n]);
+            let padded_msg = TxPadding::<consts::U16>::pad_min_with_rng(
+                &mut InsecureRng(StepRng::new(1, 1)),
+                &mut buf,
+                n,
+                min_len,
+            )
+            .unwrap();
+            let l = padded_msg.len();
+            assert_eq!(l, TxPadding::<consts::U16>::padded_min_len(n, min_len));
+            assert!(l >= min_len);
+            assert_eq!(l % 16, 0);
+            assert!(l <= TxPadding::<consts::U16>::padded_len(8 + n).max(min_len + 15));
+            assert!(padded_msg[l - 15..].iter().all(|&v| v == 0));
+            assert_eq!(
+                TxPadding::<consts::U16>::unpad_min(padded_msg).unwrap(),
+                &msg[..n]
+            );
+        }
+    }
+}
+
+#[cfg(any(feature = "os-rng", feature = "thread-rng"))]
+#[test]
+fn extra_bytes_are_zeros() {
+    let mut buf = [0xff; 256];
+    buf[..3].copy_from_slice(b"ack");
+    let padded_msg = TxPadding::<consts::U16>::pad_min(&mut buf, 3, 256).unwrap();
+    let start = 1 + ((padded_msg[0] & 0xf) as usize + 2);
+    assert_eq!(&padded_msg[start..start + 11], b"\0\0\0\0\0\0\0\x03ack");
+    assert!(padded_msg[start + 11..].iter().all(|&v| v == 0));
+}
+
+#[test]
+fn pad_min_insufficient_space() {
+    let mut buf = [0; 128];
+    assert_eq!(
+        TxPadding::<consts::U16>::pad_min_with_rng(
+            &mut InsecureRng(StepRng::new(1, 1)),
+            &mut buf,
+            3,
+            256
+        ),
+        Err(TxPadError::BufferTooSmall {
+            required: 256,
+            got: 128
+        })
+    );
+}
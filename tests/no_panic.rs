@@ -0,0 +1,110 @@
+//! Test that the padding and unpadding paths cannot panic
+//!
+//! Only meaningful in an optimized build, where linking this test fails if any of the functions
+//! called keeps a panicking path: `cargo test --release --features no-panic --test no_panic`
+#![no_std]
+#![cfg(feature = "no-panic")]
+
+use rand::rngs::mock::StepRng;
+
+use tx_padding::consts;
+use tx_padding::{InsecureRng, TxPadding, TxPaddingConst};
+
+type Scheme = TxPadding<consts::U16>;
+
+/// Hide `value` from the optimizer so the checks are not constant folded away
+fn opaque<T: Copy>(value: T) -> T {
+    // SAFETY: reading from a valid reference
+    unsafe { core::ptr::read_volatile(&value) }
+}
+
+#[test]
+fn pad_and_unpad() {
+    let mut rng = InsecureRng(StepRng::new(1, 1));
+    for pos in [0, 1, 14, 15, 16, 40, 64] {
+        let mut buf = [0x5a; 64];
+        let padded_msg = Scheme::pad_with_rng(&mut rng, &mut buf, opaque(pos));
+        if let Ok(padded_msg) = padded_msg {
+            assert_eq!(Scheme::unpad(padded_msg).unwrap(), &[0x5a; 64][..pos]);
+            assert_eq!(
+                Scheme::unpad_strict(padded_msg).unwrap(),
+                &[0x5a; 64][..pos]
+            );
+            assert_eq!(
+                Scheme::unpad_trusted(padded_msg).unwrap(),
+                &[0x5a; 64][..pos]
+            );
+            assert_eq!(
+                Scheme::unpad_in_place(padded_msg).unwrap(),
+                &[0x5a; 64][..pos]
+            );
+        } else {
+            assert!(pos > 40);
+        }
+    }
+}
+
+#[test]
+fn pad_variants() {
+    let mut rng = InsecureRng(StepRng::new(1, 1));
+    let msg = [0x5a; 20];
+    let msg = &msg[..opaque(20)];
+    let mut buf = [0; 48];
+    buf[..20].copy_from_slice(msg);
+    let padded_msg = Scheme::pad_random_header_with_rng(&mut rng, &mut buf, msg.len()).unwrap();
+    assert_eq!(Scheme::unpad(padded_msg).unwrap(), msg);
+
+    buf[..20].copy_from_slice(msg);
+    let padded_msg = Scheme::pad_filled_with_rng(&mut rng, &mut buf, msg.len(), 0xff).unwrap();
+    assert_eq!(Scheme::unpad_filled(padded_msg, 0xff).unwrap(), msg);
+
+    let padded_msg = Scheme::pad_into_with_rng(&mut rng, msg, &mut buf).unwrap();
+    assert_eq!(Scheme::unpad(padded_msg).unwrap(), msg);
+
+    let mut zeroed = [0; 48];
+    zeroed[..20].copy_from_slice(msg);
+    let padded_msg = Scheme::pad_assume_zeroed_with_rng(&mut rng, &mut zeroed, msg.len()).unwrap();
+    assert_eq!(Scheme::unpad(padded_msg).unwrap(), msg);
+
+    #[cfg(feature = "uninit")]
+    {
+        let mut out = [core::mem::MaybeUninit::uninit(); 48];
+        let padded_msg = Scheme::pad_uninit_with_rng(&mut rng, msg, &mut out).unwrap();
+        assert_eq!(Scheme::unpad(padded_msg).unwrap(), msg);
+    }
+
+    let offset = Scheme::reserve(&buf, msg.len()).unwrap();
+    buf[offset..offset + 20].copy_from_slice(msg);
+    let padded_msg = Scheme::finish_with_rng(&mut rng, &mut buf, msg.len()).unwrap();
+    assert_eq!(Scheme::unpad(padded_msg).unwrap(), msg);
+}
+
+#[test]
+fn const_generic() {
+    let mut rng = InsecureRng(StepRng::new(1, 1));
+    let mut buf = [0x5a; 48];
+    let padded_msg = TxPaddingConst::<16>::pad_with_rng(&mut rng, &mut buf, opaque(20)).unwrap();
+    assert_eq!(
+        TxPaddingConst::<16>::unpad(padded_msg).unwrap(),
+        &[0x5a; 20]
+    );
+    assert_eq!(
+        TxPaddingConst::<16>::unpad_strict(padded_msg).unwrap(),
+        &[0x5a; 20]
+    );
+}
+
+#[test]
+fn malformed_input() {
+    for data in [&[][..], &[0xf0; 16], &[0xff; 64], &[0xf0; 47]] {
+        let data = opaque(data);
+        assert!(Scheme::unpad(data).is_err());
+        assert!(Scheme::unpad_strict(data).is_err());
+        assert!(Scheme::unpad_range(data).is_err());
+        assert!(Scheme::peek_msg_len(data, opaque(15)).is_err());
+        assert!(TxPaddingConst::<16>::unpad(data).is_err());
+    }
+    for data in [&[][..], &[0xf0; 16], &[0xff; 32]] {
+        assert!(Scheme::unpad_trusted(opaque(data)).is_err());
+    }
+}
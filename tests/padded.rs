@@ -0,0 +1,47 @@
+//! Test the structured view of padded messages
+#![no_std]
+
+use core::convert::TryFrom;
+
+use rand::rngs::mock::StepRng;
+
+use tx_padding::consts;
+use tx_padding::{InsecureRng, Padded, TxPadding};
+
+macro_rules! test_view_parts {
+    ($name:ident, $n:ty) => {
+        #[test]
+        fn $name() {
+            let msg = [0x5a; 300];
+            for n in 0..300 {
+                let mut buf = [0; 1024];
+                buf[..n].copy_from_slice(&msg[..n]);
+                let padded_msg = TxPadding::<$n>::pad_with_rng(
+                    &mut InsecureRng(StepRng::new(1, 1)),
+                    &mut buf,
+                    n,
+                )
+                .unwrap();
+                let padded = Padded::<$n>::try_from(&padded_msg[..]).unwrap();
+                assert_eq!(padded.header(), padded_msg[0]);
+                assert_eq!(padded.message(), &msg[..n]);
+                assert_eq!(
+                    1 + padded.random_prefix().len() + n + padded.zero_tail_len(),
+                    padded_msg.len()
+                );
+                assert_eq!(padded.as_bytes(), &padded_msg[..]);
+            }
+        }
+    };
+}
+
+test_view_parts!(view_parts_bs_2, consts::U2);
+test_view_parts!(view_parts_bs_16, consts::U16);
+test_view_parts!(view_parts_bs_256, consts::U256);
+
+#[test]
+fn view_rejects_illformed() {
+    assert!(Padded::<consts::U8>::try_from(&[][..]).is_err());
+    assert!(Padded::<consts::U8>::try_from(&[0xf8, 0, 0][..]).is_err());
+    assert!(Padded::<consts::U8>::try_from(&[0xf8, 1, 2, 0, 0, 0, 0, 0, 0, 1][..]).is_err());
+}
@@ -0,0 +1,127 @@
+//! Test serializing owned padded messages
+#![cfg(feature = "serde")]
+
+use std::convert::TryFrom;
+use std::vec::Vec;
+
+use rand::rngs::mock::StepRng;
+use serde::de::value::{Error, SeqDeserializer};
+use serde::de::{Deserialize, Deserializer, Visitor};
+use serde::forward_to_deserialize_any;
+use tx_padding::consts::{U16, U8};
+use tx_padding::{InsecureRng, PaddedMessage, TxPadding};
+
+/// Deserializer of a non-human-readable format handing out its bytes, either as a byte string
+/// or as a sequence of integers
+struct BinaryDeserializer<'a> {
+    data: &'a [u8],
+    as_seq: bool,
+}
+
+impl<'de, 'a> Deserializer<'de> for BinaryDeserializer<'a> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        if self.as_seq {
+            visitor.visit_seq(SeqDeserializer::new(self.data.iter().copied()))
+        } else {
+            visitor.visit_bytes(self.data)
+        }
+    }
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct enum identifier ignored_any
+    }
+}
+
+fn padded_msg(msg: &[u8]) -> PaddedMessage<U8> {
+    PaddedMessage::pad_with_rng(&mut InsecureRng(StepRng::new(1, 1)), msg).unwrap()
+}
+
+#[test]
+fn pad_matches_tx_padding() {
+    for n in 0..40 {
+        let msg = [0x5a; 40];
+        let padded = padded_msg(&msg[..n]);
+        let mut buf = [0; 64];
+        buf[..n].copy_from_slice(&msg[..n]);
+        let expected =
+            TxPadding::<U8>::pad_with_rng(&mut InsecureRng(StepRng::new(1, 1)), &mut buf, n)
+                .unwrap();
+        assert_eq!(padded.as_bytes(), expected);
+        assert_eq!(padded.message(), &msg[..n]);
+    }
+}
+
+#[test]
+fn json_round_trip_as_hex() {
+    let padded = padded_msg(b"test");
+    let json = serde_json::to_string(&padded).unwrap();
+    assert_eq!(json, format!("\"{}\"", hex::encode(padded.as_bytes())));
+    let decoded: PaddedMessage<U8> = serde_json::from_str(&json).unwrap();
+    assert_eq!(decoded, padded);
+    assert_eq!(decoded.message(), b"test");
+}
+
+#[test]
+fn json_rejects_malformed() {
+    assert!(serde_json::from_str::<PaddedMessage<U8>>("\"not hex\"").is_err());
+    assert!(serde_json::from_str::<PaddedMessage<U8>>("\"\"").is_err());
+
+    let mut data = padded_msg(b"test").into_vec();
+    *data.last_mut().unwrap() = 1;
+    let json = format!("\"{}\"", hex::encode(&data));
+    assert!(serde_json::from_str::<PaddedMessage<U8>>(&json).is_err());
+
+    // Valid padding of another block size
+    let json = serde_json::to_string(&padded_msg(b"test")).unwrap();
+    assert!(serde_json::from_str::<PaddedMessage<U16>>(&json).is_err());
+}
+
+#[test]
+fn binary_deserialize_bytes() {
+    let padded = padded_msg(b"test");
+    let decoded = PaddedMessage::<U8>::deserialize(BinaryDeserializer {
+        data: padded.as_bytes(),
+        as_seq: false,
+    })
+    .unwrap();
+    assert_eq!(decoded, padded);
+    let malformed = BinaryDeserializer {
+        data: &[0xff; 16],
+        as_seq: false,
+    };
+    assert!(PaddedMessage::<U8>::deserialize(malformed).is_err());
+}
+
+#[test]
+fn binary_deserialize_sequence() {
+    let padded = padded_msg(b"test");
+    let decoded = PaddedMessage::<U8>::deserialize(BinaryDeserializer {
+        data: padded.as_bytes(),
+        as_seq: true,
+    })
+    .unwrap();
+    assert_eq!(decoded, padded);
+}
+
+#[test]
+fn try_from_validates() {
+    let padded = padded_msg(b"test");
+    assert_eq!(
+        PaddedMessage::<U8>::try_from(padded.clone().into_vec()).unwrap(),
+        padded
+    );
+    assert!(PaddedMessage::<U8>::try_from(vec![0xff; 16]).is_err());
+    assert!(PaddedMessage::<U8>::try_from(Vec::new()).is_err());
+    assert_eq!(
+        TxPadding::<U8>::unpad(&Vec::from(padded.clone())).unwrap(),
+        b"test"
+    );
+}
@@ -0,0 +1,64 @@
+//! Test the Padmé length-hiding padding
+#![no_std]
+
+use rand::rngs::mock::StepRng;
+
+use tx_padding::consts;
+#[cfg(any(feature = "os-rng", feature = "thread-rng"))]
+use tx_padding::Padding;
+use tx_padding::{InsecureRng, Padme, TxPadding};
+
+macro_rules! test_padme_round_trip {
+    ($name:ident, $n:ty, $block_size:expr) => {
+        #[test]
+        fn $name() {
+            let msg = [0x5a; 2048];
+            let mut buf = [0; 4096];
+            for n in (0..2048).step_by(7) {
+                buf[..n].copy_from_slice(&msg[..n]);
+                let padded_msg =
+                    Padme::<$n>::pad_with_rng(&mut InsecureRng(StepRng::new(1, 1)), &mut buf, n)
+                        .unwrap();
+                assert_eq!(padded_msg.len(), Padme::<$n>::padded_len(n));
+                assert_eq!(padded_msg.len() % $block_size, 0);
+                assert!(padded_msg.len() >= TxPadding::<$n>::padded_len(8 + n));
+                assert_eq!(Padme::<$n>::unpad(padded_msg).unwrap(), &msg[..n]);
+            }
+        }
+    };
+}
+
+test_padme_round_trip!(padme_round_trip_u2, consts::U2, 2);
+test_padme_round_trip!(padme_round_trip_u16, consts::U16, 16);
+test_padme_round_trip!(padme_round_trip_u256, consts::U256, 256);
+
+#[test]
+fn padme_sizes_are_sparse() {
+    // Between 1 KiB and 2 KiB, Padmé only leaves the lowest 5 bits of the length at zero
+    let mut last = 0;
+    let mut sizes = 0;
+    for n in 1024..2048 {
+        let l = Padme::<consts::U2>::padded_len(n);
+        if l != last {
+            sizes += 1;
+            last = l;
+        }
+    }
+    assert!(sizes <= 33);
+    assert!(Padme::<consts::U2>::padded_len(2000) * 100 <= (2000 + 8 + 5) * 112);
+}
+
+#[cfg(any(feature = "os-rng", feature = "thread-rng"))]
+#[test]
+fn padme_rejects_bad_length() {
+    let mut buf = [0; 64];
+    buf[..3].copy_from_slice(b"abc");
+    let padded_msg = Padme::<consts::U8>::pad(&mut buf, 3, 8).unwrap();
+    let l = padded_msg.len();
+    let start = 1 + ((padded_msg[0] & 7) as usize + 2);
+    padded_msg[start + 7] = 0xff;
+    assert!(Padme::<consts::U8>::unpad(&padded_msg[..l]).is_err());
+    assert!(
+        Padme::<consts::U8>::unpad(&[0xf8, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]).is_err()
+    );
+}
@@ -0,0 +1,33 @@
+//! Test padding and unpadding many messages in parallel
+#![cfg(feature = "rayon")]
+
+use tx_padding::consts;
+use tx_padding::TxPadding;
+
+#[test]
+fn par_batch_round_trip() {
+    let msgs: Vec<Vec<u8>> = (0..1000).map(|n| vec![n as u8; n % 100]).collect();
+    let mut out = vec![[0xff; 128]; msgs.len()];
+    let frames: Vec<_> = msgs
+        .iter()
+        .map(|m| &m[..])
+        .zip(out.iter_mut().map(|o| &mut o[..]))
+        .collect();
+    TxPadding::<consts::U16>::par_pad_batch(frames).unwrap();
+    let padded: Vec<_> = msgs
+        .iter()
+        .zip(&out)
+        .map(|(m, o)| &o[..TxPadding::<consts::U16>::padded_len(m.len())])
+        .collect();
+    let unpadded = TxPadding::<consts::U16>::par_unpad_batch(padded).unwrap();
+    assert!(unpadded.iter().zip(&msgs).all(|(u, m)| u == m));
+}
+
+#[test]
+fn par_batch_reports_errors() {
+    let mut a = [0; 16];
+    let mut b = [0; 8];
+    let frames = vec![(&b"ping"[..], &mut a[..]), (b"pong", &mut b[..])];
+    assert!(TxPadding::<consts::U8>::par_pad_batch(frames).is_err());
+    assert!(TxPadding::<consts::U8>::par_unpad_batch(vec![&a[..], &[0xf8, 0, 0][..]]).is_err());
+}
@@ -0,0 +1,58 @@
+//! Test the generators of messages and padded frames
+#![cfg(any(feature = "arbitrary", feature = "proptest"))]
+
+use tx_padding::consts;
+use tx_padding::{PaddedFrame, TxPadding, TxPaddingDyn};
+
+#[cfg(feature = "arbitrary")]
+#[test]
+fn arbitrary_frames_are_valid() {
+    use arbitrary::{Arbitrary, Unstructured};
+    use tx_padding::SizedMessage;
+
+    let data: Vec<u8> = (0..4096u32).map(|i| (i * 31 + i / 7) as u8).collect();
+    let mut u = Unstructured::new(&data);
+    while !u.is_empty() {
+        let frame = PaddedFrame::<consts::U16>::arbitrary(&mut u).unwrap();
+        assert_eq!(
+            TxPadding::<consts::U16>::unpad(frame.as_bytes()).unwrap(),
+            frame.message()
+        );
+        assert_eq!(frame.padded().message(), frame.message());
+
+        let sized = SizedMessage::arbitrary(&mut u).unwrap();
+        assert!(<TxPaddingDyn>::is_valid_block_size(sized.block_size));
+    }
+}
+
+#[cfg(feature = "proptest")]
+mod prop {
+    use proptest::prelude::*;
+
+    use super::*;
+    use tx_padding::SizedMessage;
+
+    proptest! {
+        #[test]
+        fn proptest_frames_are_valid(frame in any::<PaddedFrame<consts::U32>>()) {
+            prop_assert_eq!(
+                TxPadding::<consts::U32>::unpad_strict(frame.as_bytes()).unwrap(),
+                frame.message()
+            );
+            prop_assert_eq!(frame.padded().zero_tail_len(), 31);
+        }
+
+        #[test]
+        fn proptest_sized_messages_round_trip(sized in any::<SizedMessage>()) {
+            let len = <TxPaddingDyn>::padded_len(sized.msg.len(), sized.block_size).unwrap();
+            let mut buf = vec![0; len];
+            buf[..sized.msg.len()].copy_from_slice(&sized.msg);
+            let padded_msg = <TxPaddingDyn>::pad(&mut buf, sized.msg.len(), sized.block_size)
+                .unwrap();
+            prop_assert_eq!(
+                <TxPaddingDyn>::unpad(padded_msg, sized.block_size).unwrap(),
+                &sized.msg[..]
+            );
+        }
+    }
+}
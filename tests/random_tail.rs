@@ -0,0 +1,69 @@
+//! Test the random tail sibling scheme
+#![no_std]
+
+use rand::rngs::mock::StepRng;
+
+use tx_padding::consts;
+use tx_padding::{InsecureRng, TxPadding, TxRandomTailPadding};
+
+macro_rules! test_random_tail_round_trip {
+    ($name:ident, $n:ty, $block_size:expr) => {
+        #[test]
+        fn $name() {
+            for n in 0..40 {
+                let msg = &[0x5a; 40][..n];
+                let mut a = [0xff; 1024];
+                let mut b = [0xff; 1024];
+                a[..n].copy_from_slice(msg);
+                b[..n].copy_from_slice(msg);
+                let padded_msg = TxRandomTailPadding::<$n>::pad_with_rng(
+                    &mut InsecureRng(StepRng::new(0x5a5a_5a5a_5a5a_5a5a, 1)),
+                    &mut a,
+                    n,
+                )
+                .unwrap();
+                let expected = TxPadding::<$n>::pad_with_rng(
+                    &mut InsecureRng(StepRng::new(0x5a5a_5a5a_5a5a_5a5a, 1)),
+                    &mut b,
+                    n,
+                )
+                .unwrap();
+                let l = expected.len();
+                assert_eq!(padded_msg.len(), l);
+                assert_eq!(
+                    &padded_msg[..l - ($block_size - 1)],
+                    &expected[..l - ($block_size - 1)]
+                );
+                assert_eq!(TxRandomTailPadding::<$n>::unpad(padded_msg).unwrap(), msg);
+                assert_eq!(TxRandomTailPadding::<$n>::unpad(expected).unwrap(), msg);
+            }
+        }
+    };
+}
+
+test_random_tail_round_trip!(random_tail_round_trip_u2, consts::U2, 2);
+test_random_tail_round_trip!(random_tail_round_trip_u8, consts::U8, 8);
+test_random_tail_round_trip!(random_tail_round_trip_u256, consts::U256, 256);
+
+#[test]
+fn tail_is_random() {
+    let mut buf = [0; 16];
+    let padded_msg = TxRandomTailPadding::<consts::U8>::pad_with_rng(
+        &mut InsecureRng(StepRng::new(0xffff_ffff_ffff_ffff, 0)),
+        &mut buf,
+        3,
+    )
+    .unwrap();
+    assert_eq!(&padded_msg[9..], &[0xff; 7]);
+}
+
+#[cfg(any(feature = "os-rng", feature = "thread-rng"))]
+#[test]
+fn random_tail_rejects_short() {
+    assert!(TxRandomTailPadding::<consts::U8>::unpad(&[]).is_err());
+    assert!(TxRandomTailPadding::<consts::U8>::unpad(&[0xf8, 0, 0, 0, 0, 0, 0, 0, 0]).is_err());
+    assert!(TxRandomTailPadding::<consts::U8>::unpad(&[
+        0xf8, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 1
+    ])
+    .is_ok());
+}
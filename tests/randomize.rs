@@ -0,0 +1,47 @@
+//! Test prepending a random block to other paddings
+#![cfg(any(feature = "os-rng", feature = "thread-rng"))]
+#![no_std]
+
+use block_padding::{Iso7816, Pkcs7};
+use rand::rngs::mock::StepRng;
+
+use tx_padding::consts;
+use tx_padding::{InsecureRng, Padding, Randomize};
+
+macro_rules! test_randomize_round_trip {
+    ($name:ident, $p:ty, $n:ty, $block_size:expr) => {
+        #[test]
+        fn $name() {
+            for n in 0..40 {
+                let msg = &[0x5a; 40][..n];
+                let mut expected = [0xff; 128];
+                expected[..n].copy_from_slice(msg);
+                let expected = <$p>::pad(&mut expected, n, $block_size).unwrap();
+
+                let mut buf = [0xff; 128];
+                buf[..n].copy_from_slice(msg);
+                let padded_msg = Randomize::<$p, $n>::pad_with_rng(
+                    &mut InsecureRng(StepRng::new(1, 1)),
+                    &mut buf,
+                    n,
+                )
+                .unwrap();
+                assert_eq!(&padded_msg[$block_size..], &expected[..]);
+                assert_eq!(Randomize::<$p, $n>::unpad(padded_msg).unwrap(), msg);
+            }
+        }
+    };
+}
+
+test_randomize_round_trip!(randomize_pkcs7, Pkcs7, consts::U8, 8);
+test_randomize_round_trip!(randomize_iso7816, Iso7816, consts::U16, 16);
+
+#[test]
+fn randomize_rejects_bad_input() {
+    let mut buf = [0; 16];
+    assert!(Randomize::<Pkcs7, consts::U8>::pad(&mut buf, 4, 16).is_err());
+    assert!(Randomize::<Pkcs7, consts::U8>::pad(&mut buf, 9, 8).is_err());
+    assert!(Randomize::<Pkcs7, consts::U8>::pad(&mut buf, 17, 8).is_err());
+    assert!(Randomize::<Pkcs7, consts::U8>::unpad(&[8; 7]).is_err());
+    assert!(Randomize::<Pkcs7, consts::U8>::unpad(&[8; 8]).is_err());
+}
@@ -0,0 +1,50 @@
+//! Test the RNG drawing from the RDRAND and RDSEED instructions
+#![cfg(all(feature = "rdrand", any(target_arch = "x86", target_arch = "x86_64")))]
+#![no_std]
+
+use rand::RngCore;
+
+use tx_padding::consts;
+use tx_padding::{Padding, RdRandRng, TxPadding};
+
+#[test]
+fn rdrand_round_trip() {
+    if !RdRandRng::is_available() && cfg!(not(feature = "os-rng")) {
+        return;
+    }
+    let msg = [0x5a; 64];
+    for n in 0..64 {
+        let mut buf = [0; 96];
+        buf[..n].copy_from_slice(&msg[..n]);
+        let padded_msg = TxPadding::<consts::U16, RdRandRng>::pad(&mut buf, n, 16).unwrap();
+        assert_eq!(
+            TxPadding::<consts::U16, RdRandRng>::unpad(padded_msg).unwrap(),
+            &msg[..n]
+        );
+    }
+}
+
+#[test]
+fn rdrand_fills_partial_words() {
+    if !RdRandRng::is_available() && cfg!(not(feature = "os-rng")) {
+        return;
+    }
+    for n in 0..=17 {
+        let mut bufs = [[0; 17]; 8];
+        for buf in &mut bufs {
+            RdRandRng.try_fill_bytes(&mut buf[..n]).unwrap();
+            assert!(buf[n..].iter().all(|&v| v == 0));
+        }
+        if n >= 4 {
+            assert!(bufs.iter().any(|buf| buf != &bufs[0]));
+        }
+    }
+}
+
+#[test]
+fn rdrand_fails_without_fallback() {
+    if RdRandRng::is_available() || cfg!(feature = "os-rng") {
+        return;
+    }
+    assert!(RdRandRng.try_fill_bytes(&mut [0; 8]).is_err());
+}
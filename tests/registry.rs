@@ -0,0 +1,107 @@
+//! Test dispatching on the identifier byte of a padding scheme
+#![no_std]
+
+use core::convert::TryFrom;
+
+#[cfg(any(feature = "os-rng", feature = "thread-rng"))]
+use rand::rngs::OsRng;
+
+#[cfg(any(feature = "os-rng", feature = "thread-rng"))]
+use tx_padding::consts::{U16, U8};
+#[cfg(any(feature = "os-rng", feature = "thread-rng"))]
+use tx_padding::{BucketPadding, Padme, SchemeRegistry, TxPadding, TxRandomTailPadding};
+use tx_padding::{SchemeId, TxPadError};
+
+const IDS: [SchemeId; 4] = [
+    SchemeId::TxZeroTail,
+    SchemeId::TxRandomTail,
+    SchemeId::Padme,
+    SchemeId::Bucketed,
+];
+
+#[test]
+fn scheme_id_round_trip() {
+    for &id in &IDS {
+        assert_eq!(SchemeId::try_from(u8::from(id)), Ok(id));
+    }
+    for id in 4..=255 {
+        assert_eq!(
+            SchemeId::try_from(id),
+            Err(TxPadError::UnknownScheme { id })
+        );
+    }
+}
+
+#[cfg(any(feature = "os-rng", feature = "thread-rng"))]
+#[test]
+fn defaults_match_static_schemes() {
+    let registry = SchemeRegistry::with_defaults_rng::<U16, OsRng>();
+    for n in 0..40 {
+        let mut buf = [0x5a; 128];
+        let padded_msg = registry.pad(0, &mut buf, n).unwrap();
+        assert_eq!(
+            TxPadding::<U16>::unpad(padded_msg).unwrap(),
+            &[0x5a; 40][..n]
+        );
+
+        let mut buf = [0x5a; 128];
+        let padded_msg = registry.pad(1, &mut buf, n).unwrap();
+        assert_eq!(
+            TxRandomTailPadding::<U16>::unpad(padded_msg).unwrap(),
+            &[0x5a; 40][..n]
+        );
+
+        let mut buf = [0x5a; 128];
+        let padded_msg = registry.pad(2, &mut buf, n).unwrap();
+        assert_eq!(padded_msg.len(), Padme::<U16>::padded_len(n));
+        assert_eq!(Padme::<U16>::unpad(padded_msg).unwrap(), &[0x5a; 40][..n]);
+    }
+}
+
+#[cfg(any(feature = "os-rng", feature = "thread-rng"))]
+#[test]
+fn unpad_auto_dispatches_on_id() {
+    let buckets = BucketPadding::<U8>::new(&[64, 256]);
+    let mut registry = SchemeRegistry::with_defaults::<U8>();
+    assert!(registry.get(3).is_none());
+    assert!(registry.register(SchemeId::Bucketed, &buckets).is_none());
+    for &id in &IDS {
+        let id = u8::from(id);
+        let scheme = registry.get(id).unwrap();
+        assert_eq!(scheme.block_size(), 8);
+        let mut buf = [0; 256];
+        buf[..5].copy_from_slice(b"hello");
+        let padded_msg = registry.pad(id, &mut buf, 5).unwrap();
+        assert_eq!(padded_msg.len(), scheme.padded_len(5).unwrap());
+        assert_eq!(registry.unpad_auto(id, padded_msg).unwrap(), b"hello");
+    }
+}
+
+#[cfg(any(feature = "os-rng", feature = "thread-rng"))]
+#[test]
+fn reject_unknown_or_unregistered_id() {
+    let registry = SchemeRegistry::with_defaults::<U8>();
+    let mut buf = [0; 64];
+    assert_eq!(
+        registry.pad(3, &mut buf, 4),
+        Err(TxPadError::UnknownScheme { id: 3 })
+    );
+    assert_eq!(
+        registry.pad(42, &mut buf, 4),
+        Err(TxPadError::UnknownScheme { id: 42 })
+    );
+    let padded_msg = registry.pad(0, &mut buf, 4).unwrap();
+    assert!(registry.unpad_auto(0, padded_msg).is_ok());
+    assert!(registry.unpad_auto(3, padded_msg).is_err());
+    assert!(registry.unpad_auto(42, padded_msg).is_err());
+    assert!(SchemeRegistry::new().unpad_auto(0, padded_msg).is_err());
+}
+
+#[cfg(any(feature = "os-rng", feature = "thread-rng"))]
+#[test]
+fn register_replaces_scheme() {
+    let mut registry = SchemeRegistry::with_defaults::<U8>();
+    let replaced = registry.register(SchemeId::TxZeroTail, TxPadding::<U16>::as_dyn());
+    assert_eq!(replaced.unwrap().block_size(), 8);
+    assert_eq!(registry.get(0).unwrap().block_size(), 16);
+}
@@ -0,0 +1,59 @@
+//! Test recording and replaying the random bytes of padded messages
+#![cfg(feature = "replay")]
+#![no_std]
+
+use rand::rngs::mock::StepRng;
+
+use tx_padding::consts;
+use tx_padding::{InsecureRng, ReplayRng, TxPadError, TxPadding};
+
+#[test]
+fn replay_reproduces_padded_msg() {
+    let msg = [0x5a; 40];
+    for n in 0..40 {
+        let mut buf = [0; 64];
+        buf[..n].copy_from_slice(&msg[..n]);
+        let mut record = [0; 17];
+        let (padded_msg, len) = TxPadding::<consts::U16>::pad_recorded_with_rng(
+            &mut InsecureRng(StepRng::new(n as u64, 0x0101_0101)),
+            &mut buf,
+            n,
+            &mut record,
+        )
+        .unwrap();
+        assert_eq!(len, (padded_msg[0] & 0xf) as usize + 2);
+
+        let mut replayed = [0; 64];
+        replayed[..n].copy_from_slice(&msg[..n]);
+        let mut rng = InsecureRng(ReplayRng::new(&record[..len]));
+        let replayed_msg =
+            TxPadding::<consts::U16>::pad_with_rng(&mut rng, &mut replayed, n).unwrap();
+        assert_eq!(replayed_msg, padded_msg);
+        assert!(rng.0.remaining().is_empty());
+    }
+}
+
+#[test]
+fn record_too_small() {
+    let mut buf = [0; 32];
+    assert_eq!(
+        TxPadding::<consts::U16>::pad_recorded(&mut buf, 0, &mut [0; 4]).map(|(_, n)| n),
+        Err(TxPadError::BufferTooSmall {
+            required: 16,
+            got: 4
+        })
+    );
+}
+
+#[test]
+fn replay_exhausted() {
+    let mut buf = [0; 32];
+    assert_eq!(
+        TxPadding::<consts::U16>::pad_with_rng(
+            &mut InsecureRng(ReplayRng::new(&[0; 4])),
+            &mut buf,
+            0
+        ),
+        Err(TxPadError::RngFailure)
+    );
+}
@@ -0,0 +1,148 @@
+//! Test the padding scheme with different RNG backends
+#![no_std]
+
+mod common;
+
+use rand::rngs::mock::StepRng;
+use rand::{CryptoRng, Error, RngCore};
+
+use tx_padding::consts;
+use tx_padding::{
+    DefaultRng, InsecureRng, NoDefaultRng, Padding, TxPadError, TxPadding, TxPaddingConst,
+};
+
+use common::FailingRng;
+
+/// A "generator" that always yields the same byte, so the random prefix is predictable
+#[derive(Default)]
+struct ConstRng;
+
+impl RngCore for ConstRng {
+    fn next_u32(&mut self) -> u32 {
+        0xa5a5_a5a5
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        0xa5a5_a5a5_a5a5_a5a5
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        for b in dest.iter_mut() {
+            *b = 0xa5;
+        }
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+impl CryptoRng for ConstRng {}
+
+#[cfg(feature = "os-rng")]
+#[test]
+fn os_rng_round_trip() {
+    use rand::rngs::OsRng;
+
+    let mut buf = [0; 16];
+    buf[..3].copy_from_slice(b"\x01\x02\x03");
+    let padded_msg = TxPadding::<consts::U8, OsRng>::pad(&mut buf, 3, 8).unwrap();
+    assert_eq!(
+        TxPadding::<consts::U8, OsRng>::unpad(padded_msg).unwrap(),
+        b"\x01\x02\x03"
+    );
+}
+
+#[test]
+fn custom_rng_fills_prefix() {
+    let mut buf = [0; 16];
+    buf[..3].copy_from_slice(b"\x01\x02\x03");
+    let padded_msg = TxPadding::<consts::U8, ConstRng>::pad(&mut buf, 3, 8).unwrap();
+    assert_eq!(
+        padded_msg,
+        b"\xfb\xa5\xa5\xa5\xa5\xa5\x01\x02\x03\x00\x00\x00\x00\x00\x00\x00"
+    );
+    assert_eq!(
+        TxPadding::<consts::U8, ConstRng>::unpad(padded_msg).unwrap(),
+        b"\x01\x02\x03"
+    );
+}
+
+#[test]
+fn reject_rng_failure() {
+    let mut buf = [0; 16];
+    buf[..3].copy_from_slice(b"\x01\x02\x03");
+    assert!(TxPadding::<consts::U8, FailingRng>::pad(&mut buf, 3, 8).is_err());
+    assert_eq!(
+        TxPadding::<consts::U8>::pad_with_rng(&mut FailingRng, &mut buf, 3),
+        Err(TxPadError::RngFailure)
+    );
+}
+
+#[cfg(feature = "thread-rng")]
+#[test]
+fn thread_rng_round_trip() {
+    use rand::rngs::ThreadRng;
+
+    let mut buf = [0; 16];
+    buf[..3].copy_from_slice(b"\x01\x02\x03");
+    let padded_msg = TxPadding::<consts::U8, ThreadRng>::pad(&mut buf, 3, 8).unwrap();
+    assert_eq!(
+        TxPadding::<consts::U8, ThreadRng>::unpad(padded_msg).unwrap(),
+        b"\x01\x02\x03"
+    );
+}
+
+#[test]
+fn default_rng_follows_features() {
+    let expected = if cfg!(feature = "thread-rng") {
+        "ThreadRng"
+    } else if cfg!(feature = "custom-rng") {
+        "GlobalRng"
+    } else if cfg!(feature = "entropy-source") {
+        "RandomSource"
+    } else if cfg!(feature = "os-rng") {
+        "OsRng"
+    } else {
+        "NoDefaultRng"
+    };
+    assert!(core::any::type_name::<DefaultRng>().ends_with(expected));
+}
+
+#[test]
+fn pad_with_caller_rng() {
+    let mut rng = InsecureRng(StepRng::new(0x0807_0605_0403_0201, 0));
+    let mut buf = [0; 16];
+    buf[..3].copy_from_slice(b"\x01\x02\x03");
+    let padded_msg = TxPadding::<consts::U8>::pad_with_rng(&mut rng, &mut buf, 3).unwrap();
+    assert_eq!(
+        padded_msg,
+        b"\xfb\x01\x02\x03\x04\x05\x01\x02\x03\x00\x00\x00\x00\x00\x00\x00"
+    );
+    assert_eq!(
+        TxPadding::<consts::U8>::unpad(padded_msg).unwrap(),
+        b"\x01\x02\x03"
+    );
+}
+
+#[test]
+fn pad_with_caller_rng_insufficient_space() {
+    let mut rng = InsecureRng(StepRng::new(0, 1));
+    let mut buf = [0; 15];
+    assert!(TxPadding::<consts::U8>::pad_with_rng(&mut rng, &mut buf, 3).is_err());
+}
+
+#[test]
+fn unpad_without_default_rng() {
+    let padded_msg = b"\xfb\xa5\xa5\xa5\xa5\xa5\x01\x02\x03\x00\x00\x00\x00\x00\x00\x00";
+    assert_eq!(
+        TxPadding::<consts::U8, NoDefaultRng>::unpad(padded_msg).unwrap(),
+        b"\x01\x02\x03"
+    );
+    assert_eq!(
+        TxPaddingConst::<8, NoDefaultRng>::unpad(padded_msg).unwrap(),
+        b"\x01\x02\x03"
+    );
+    assert!(TxPadding::<consts::U8, NoDefaultRng>::unpad(&padded_msg[..15]).is_err());
+}
@@ -0,0 +1,57 @@
+//! Test the zeroizing padded message
+#![no_std]
+#![cfg(feature = "zeroize")]
+
+mod common;
+
+use rand::rngs::mock::StepRng;
+use zeroize::Zeroize;
+
+use tx_padding::consts;
+use tx_padding::{InsecureRng, TxPadError, TxPadding};
+
+use common::FailingRng;
+
+#[test]
+fn pad_zeroizing_matches_pad() {
+    for n in 0..40 {
+        let msg = [0x5a; 40];
+        let mut expected = [0; 128];
+        expected[..n].copy_from_slice(&msg[..n]);
+        let expected = TxPadding::<consts::U16>::pad_with_rng(
+            &mut InsecureRng(StepRng::new(1, 1)),
+            &mut expected,
+            n,
+        )
+        .unwrap();
+
+        let padded_msg = TxPadding::<consts::U16>::pad_zeroizing_with_rng(
+            &mut InsecureRng(StepRng::new(1, 1)),
+            &msg[..n],
+        )
+        .unwrap();
+        assert_eq!(padded_msg.as_bytes(), &expected[..]);
+        assert_eq!(padded_msg.msg(), &msg[..n]);
+        assert_eq!(
+            TxPadding::<consts::U16>::unpad(padded_msg.as_bytes()).unwrap(),
+            &msg[..n]
+        );
+    }
+}
+
+#[test]
+fn zeroize_empties() {
+    let mut padded_msg = TxPadding::<consts::U8>::pad_zeroizing(b"secret").unwrap();
+    assert_eq!(padded_msg.len(), 16);
+    padded_msg.zeroize();
+    assert!(padded_msg.is_empty());
+    assert!(padded_msg.msg().is_empty());
+}
+
+#[test]
+fn pad_zeroizing_rng_failure() {
+    assert_eq!(
+        TxPadding::<consts::U8>::pad_zeroizing_with_rng(&mut FailingRng, b"secret").map(|_| ()),
+        Err(TxPadError::RngFailure)
+    );
+}
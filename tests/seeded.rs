@@ -0,0 +1,42 @@
+//! Test padding with a prefix derived from a seed
+#![cfg(feature = "seeded")]
+#![no_std]
+
+use tx_padding::consts;
+use tx_padding::TxPadding;
+
+#[test]
+fn seeded_is_reproducible() {
+    let msg = [0x5a; 40];
+    for n in 0..40 {
+        let (mut a, mut b) = ([0; 64], [0; 64]);
+        a[..n].copy_from_slice(&msg[..n]);
+        b[..n].copy_from_slice(&msg[..n]);
+        let a = TxPadding::<consts::U16>::pad_seeded([7; 32], 3, &mut a, n).unwrap();
+        let b = TxPadding::<consts::U16>::pad_seeded([7; 32], 3, &mut b, n).unwrap();
+        assert_eq!(a, b);
+        assert_eq!(TxPadding::<consts::U16>::unpad(a).unwrap(), &msg[..n]);
+    }
+}
+
+#[test]
+fn nonce_changes_prefix() {
+    let (mut a, mut b) = ([0; 32], [0; 32]);
+    let a = TxPadding::<consts::U16>::pad_seeded([7; 32], 0, &mut a, 4).unwrap();
+    let b = TxPadding::<consts::U16>::pad_seeded([7; 32], 1, &mut b, 4).unwrap();
+    assert_eq!(a[0], b[0]);
+    assert_ne!(a[1..13], b[1..13]);
+    assert_eq!(a[13..], b[13..]);
+}
+
+#[test]
+fn prefix_is_chacha20_keystream() {
+    // Keystream of the all-zero key and nonce, from the ChaCha20 test vectors
+    let mut buf = [0; 32];
+    let padded_msg = TxPadding::<consts::U16>::pad_seeded([0; 32], 0, &mut buf, 4).unwrap();
+    assert_eq!(padded_msg[0], 0xfa);
+    assert_eq!(
+        padded_msg[1..13],
+        [0x76, 0xb8, 0xe0, 0xad, 0xa0, 0xf1, 0x3d, 0x90, 0x40, 0x5d, 0x6a, 0xe5]
+    );
+}
@@ -0,0 +1,89 @@
+//! Test the sans-io session exchanging padded messages
+#![cfg(feature = "alloc")]
+#![no_std]
+
+use rand::rngs::mock::StepRng;
+
+use tx_padding::consts;
+use tx_padding::{InsecureRng, Session, SessionEvent, TxPadError, TxPadding};
+
+type Scheme = Session<consts::U8>;
+
+/// Move every byte `from` has to transmit over to `to`, one chunk of `chunk_len` at a time
+fn transfer(from: &mut Scheme, to: &mut Scheme, chunk_len: usize) {
+    while let Ok(SessionEvent::Transmit(bytes)) = from.poll() {
+        for chunk in bytes.chunks(chunk_len) {
+            to.feed(chunk);
+        }
+    }
+}
+
+#[test]
+fn exchange_messages() {
+    let msg = [0x5a; 40];
+    for chunk_len in [1, 7, 64] {
+        let mut a = Scheme::new();
+        let mut b = Scheme::new();
+        for n in 0..msg.len() {
+            a.send(&msg[..n]).unwrap();
+        }
+        transfer(&mut a, &mut b, chunk_len);
+        for n in 0..msg.len() {
+            assert_eq!(b.poll().unwrap(), SessionEvent::Message(&msg[..n]));
+        }
+        assert_eq!(b.poll().unwrap(), SessionEvent::NeedMoreInput(4));
+        assert_eq!(a.poll().unwrap(), SessionEvent::NeedMoreInput(4));
+    }
+}
+
+#[test]
+fn report_missing_bytes() {
+    let mut a = Scheme::new();
+    let mut b = Scheme::new();
+    a.send(b"hello").unwrap();
+    let frame = match a.poll() {
+        Ok(SessionEvent::Transmit(bytes)) => bytes,
+        event => panic!("unexpected event {:?}", event),
+    };
+    assert_eq!(frame.len(), 4 + 16);
+    b.feed(&frame[..2]);
+    assert_eq!(b.poll().unwrap(), SessionEvent::NeedMoreInput(2));
+    b.feed(&frame[2..10]);
+    assert_eq!(b.poll().unwrap(), SessionEvent::NeedMoreInput(10));
+    b.feed(&frame[10..]);
+    assert_eq!(b.poll().unwrap(), SessionEvent::Message(b"hello"));
+}
+
+#[test]
+fn skip_malformed_frames() {
+    let mut b = Scheme::new();
+    let mut frame = [0; 4 + 16];
+    frame[..4].copy_from_slice(&16u32.to_be_bytes());
+    TxPadding::<consts::U8>::pad_into(b"test", &mut frame[4..]).unwrap();
+    frame[19] = 1;
+    b.feed(&frame);
+    frame[19] = 0;
+    b.feed(&frame);
+    assert!(b.poll().is_err());
+    assert_eq!(b.poll().unwrap(), SessionEvent::Message(b"test"));
+
+    for len in [0u32, 12, 24] {
+        let mut b = Scheme::with_max_frame_len(16);
+        b.feed(&len.to_be_bytes());
+        b.feed(&frame);
+        assert!(b.poll().is_err());
+        assert_eq!(b.poll().unwrap(), SessionEvent::NeedMoreInput(4));
+    }
+}
+
+#[test]
+fn reject_long_messages() {
+    let mut a = Scheme::with_max_frame_len(16);
+    assert_eq!(a.send(&[0; 8]), Err(TxPadError::MessageTooLong));
+    assert_eq!(a.poll().unwrap(), SessionEvent::NeedMoreInput(4));
+    assert_eq!(
+        a.send_with_rng(&mut InsecureRng(StepRng::new(1, 1)), &[0; 6]),
+        Ok(())
+    );
+    assert!(matches!(a.poll(), Ok(SessionEvent::Transmit(bytes)) if bytes.len() == 20));
+}
@@ -0,0 +1,136 @@
+//! Test the incremental padding of messages fed in chunks
+#![no_std]
+
+use rand::rngs::mock::StepRng;
+
+use tx_padding::consts;
+use tx_padding::{InsecureRng, Padder, TxPadError, TxPadding, Unpadder};
+
+macro_rules! create_streaming_test {
+    ($name:ident, $block_size:ty, $chunk:expr) => {
+        #[test]
+        fn $name() {
+            let mut message = [0; 300];
+            for (i, v) in message.iter_mut().enumerate() {
+                *v = i as u8;
+            }
+            for &n in &[0, 1, 2, 7, 8, 9, 100, 255, 256, 300] {
+                let message = &message[..n];
+                let mut expected = [0; 1024];
+                expected[..n].copy_from_slice(message);
+                let expected = TxPadding::<$block_size>::pad_with_rng(
+                    &mut InsecureRng(StepRng::new(7, 3)),
+                    &mut expected,
+                    n,
+                )
+                .unwrap();
+
+                let mut padder =
+                    Padder::<$block_size>::with_rng(&mut InsecureRng(StepRng::new(7, 3)), n)
+                        .unwrap();
+                assert_eq!(padder.padded_len(), expected.len());
+                let mut out = [0; 1024];
+                let mut written = 0;
+                for chunk in message.chunks($chunk) {
+                    let len = padder.output_len(chunk.len());
+                    assert_eq!(padder.update(chunk, &mut out[written..]).unwrap(), len);
+                    assert_eq!(written % <$block_size as typenum::Unsigned>::USIZE, 0);
+                    written += len;
+                }
+                written += padder.finalize(&mut out[written..]).unwrap();
+                assert_eq!(&out[..written], expected);
+            }
+        }
+    };
+}
+
+create_streaming_test!(stream_bs_2_chunk_1, consts::U2, 1);
+create_streaming_test!(stream_bs_8_chunk_3, consts::U8, 3);
+create_streaming_test!(stream_bs_8_chunk_64, consts::U8, 64);
+create_streaming_test!(stream_bs_256_chunk_7, consts::U256, 7);
+
+#[test]
+fn reject_length_mismatch() {
+    let mut out = [0; 32];
+    let mut padder =
+        Padder::<consts::U8>::with_rng(&mut InsecureRng(StepRng::new(1, 1)), 3).unwrap();
+    assert_eq!(
+        padder.update(b"\x01\x02\x03\x04", &mut out),
+        Err(TxPadError::MessageLengthMismatch {
+            expected: 3,
+            got: 4
+        })
+    );
+    padder.update(b"\x01\x02", &mut out).unwrap();
+    assert_eq!(
+        padder.finalize(&mut out),
+        Err(TxPadError::MessageLengthMismatch {
+            expected: 3,
+            got: 2
+        })
+    );
+}
+
+#[test]
+fn reject_insufficient_output() {
+    let mut out = [0; 32];
+    let mut padder =
+        Padder::<consts::U8>::with_rng(&mut InsecureRng(StepRng::new(1, 1)), 16).unwrap();
+    assert!(padder.update(&[0; 16], &mut out[..7]).is_err());
+    let n = padder.update(&[0; 16], &mut out).unwrap();
+    assert!(padder.clone().finalize(&mut out[n..n + 1]).is_err());
+    assert_eq!(padder.final_len(), padder.padded_len() - n);
+    assert_eq!(padder.finalize(&mut out[n..]).unwrap(), 32 - n);
+}
+
+macro_rules! create_unpadder_test {
+    ($name:ident, $block_size:ty, $chunk:expr) => {
+        #[test]
+        fn $name() {
+            let mut message = [0; 300];
+            for (i, v) in message.iter_mut().enumerate() {
+                *v = i as u8;
+            }
+            for &n in &[0, 1, 2, 7, 8, 9, 100, 255, 256, 300] {
+                let mut buf = [0; 1024];
+                buf[..n].copy_from_slice(&message[..n]);
+                let padded_msg = TxPadding::<$block_size>::pad_with_rng(
+                    &mut InsecureRng(StepRng::new(7, 3)),
+                    &mut buf,
+                    n,
+                )
+                .unwrap();
+
+                let mut unpadder = Unpadder::<$block_size>::new();
+                let mut out = [0; 1024];
+                let mut written = 0;
+                for chunk in padded_msg.chunks($chunk) {
+                    written += unpadder.update(chunk, &mut out[written..]).unwrap();
+                }
+                unpadder.finish().unwrap();
+                assert_eq!(&out[..written], &message[..n]);
+            }
+        }
+    };
+}
+
+create_unpadder_test!(unpadder_bs_2_chunk_1, consts::U2, 1);
+create_unpadder_test!(unpadder_bs_8_chunk_3, consts::U8, 3);
+create_unpadder_test!(unpadder_bs_8_chunk_64, consts::U8, 64);
+create_unpadder_test!(unpadder_bs_256_chunk_7, consts::U256, 7);
+
+#[test]
+fn unpadder_rejects_illformed() {
+    let inputs: [&[u8]; 4] = [
+        &[],
+        &[0xF8],
+        &[0xF8, 0, 0],
+        &[0xF8, 0, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0],
+    ];
+    for input in inputs.iter() {
+        let mut unpadder = Unpadder::<consts::U8>::new();
+        let mut out = [0; 16];
+        unpadder.update(input, &mut out).unwrap();
+        assert!(unpadder.finish().is_err());
+    }
+}
@@ -0,0 +1,87 @@
+//! Test the tail marker sibling scheme
+#![no_std]
+
+use rand::rngs::mock::StepRng;
+
+use tx_padding::consts;
+use tx_padding::{InsecureRng, TxPadding, TxTailPadding};
+
+macro_rules! test_tail_round_trip {
+    ($name:ident, $n:ty, $block_size:expr) => {
+        #[test]
+        fn $name() {
+            for n in 0..40 {
+                let msg = &[0x5a; 40][..n];
+                let mut buf = [0xff; 1024];
+                buf[..n].copy_from_slice(msg);
+                let padded_msg = TxTailPadding::<$n>::pad_with_rng(
+                    &mut InsecureRng(StepRng::new(1, 1)),
+                    &mut buf,
+                    n,
+                )
+                .unwrap();
+                assert_eq!(padded_msg.len(), TxPadding::<$n>::padded_len(n));
+                assert_eq!(padded_msg.len() % $block_size, 0);
+                assert!(padded_msg[..$block_size - 1].iter().all(|&v| v == 0));
+                assert_eq!(&padded_msg[$block_size - 1..$block_size - 1 + n], msg);
+                assert_eq!(TxTailPadding::<$n>::unpad(padded_msg).unwrap(), msg);
+            }
+        }
+    };
+}
+
+test_tail_round_trip!(tail_round_trip_u2, consts::U2, 2);
+test_tail_round_trip!(tail_round_trip_u8, consts::U8, 8);
+test_tail_round_trip!(tail_round_trip_u256, consts::U256, 256);
+
+#[test]
+fn tail_layout() {
+    let mut buf = [0; 16];
+    buf[..3].copy_from_slice(b"\x01\x02\x03");
+    let padded_msg = TxTailPadding::<consts::U8>::pad_with_rng(
+        &mut InsecureRng(StepRng::new(0x0807_0605_0403_0201, 0)),
+        &mut buf,
+        3,
+    )
+    .unwrap();
+    assert_eq!(
+        padded_msg,
+        b"\x00\x00\x00\x00\x00\x00\x00\x01\x02\x03\x01\x02\x03\x04\x05\xfb"
+    );
+}
+
+#[cfg(any(feature = "os-rng", feature = "thread-rng"))]
+#[test]
+fn tail_rejects_illformed() {
+    assert!(TxTailPadding::<consts::U8>::unpad(&[]).is_err());
+    assert!(TxTailPadding::<consts::U8>::unpad(&[0, 0, 0, 0xf8]).is_err());
+    assert!(TxTailPadding::<consts::U8>::unpad(&[
+        0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0xf8
+    ])
+    .is_err());
+    assert!(TxTailPadding::<consts::U8>::unpad(&[
+        0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0xf8
+    ])
+    .is_ok());
+}
+
+#[cfg(feature = "diagnostics")]
+#[test]
+fn tail_report_unpad_error_reason() {
+    use tx_padding::UnpadErrorKind;
+
+    assert_eq!(
+        TxTailPadding::<consts::U8>::unpad_verbose(&[]),
+        Err(UnpadErrorKind::EmptyInput)
+    );
+    assert_eq!(
+        TxTailPadding::<consts::U8>::unpad_verbose(&[0, 0, 0xf8]),
+        Err(UnpadErrorKind::TooShort)
+    );
+    assert_eq!(
+        TxTailPadding::<consts::U8>::unpad_verbose(&[
+            0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0xf8
+        ]),
+        Err(UnpadErrorKind::NonZeroTail { index: 2 })
+    );
+}
@@ -0,0 +1,58 @@
+//! Test the deterministic test utilities
+#![cfg(feature = "test-utils")]
+#![no_std]
+
+use tx_padding::consts;
+use tx_padding::testing::{pad_deterministic, round_trip, DeterministicRng};
+use tx_padding::{InsecureRng, TxPadError, TxPadding};
+
+#[test]
+fn same_seed_same_output() {
+    let mut a = [0; 24];
+    let mut b = [0; 24];
+    let a = pad_deterministic::<consts::U8>(7, b"\x01\x02\x03\x04\x05\x06\x07", &mut a).unwrap();
+    let b = pad_deterministic::<consts::U8>(7, b"\x01\x02\x03\x04\x05\x06\x07", &mut b).unwrap();
+    assert_eq!(a, b);
+}
+
+#[test]
+fn different_seed_different_prefix() {
+    let mut a = [0; 24];
+    let mut b = [0; 24];
+    let a = pad_deterministic::<consts::U8>(1, b"\x01\x02\x03\x04\x05\x06\x07", &mut a).unwrap();
+    let b = pad_deterministic::<consts::U8>(2, b"\x01\x02\x03\x04\x05\x06\x07", &mut b).unwrap();
+    assert_eq!(a[0], b[0]);
+    assert_ne!(a[1..10], b[1..10]);
+    assert_eq!(a[10..], b[10..]);
+}
+
+#[test]
+fn rng_matches_helper() {
+    let mut a = [0; 16];
+    let mut b = [0; 16];
+    a[..3].copy_from_slice(b"\x01\x02\x03");
+    let mut rng = InsecureRng(DeterministicRng::new(0));
+    let a = TxPadding::<consts::U8>::pad_with_rng(&mut rng, &mut a, 3).unwrap();
+    let b = pad_deterministic::<consts::U8>(0, b"\x01\x02\x03", &mut b).unwrap();
+    assert_eq!(a, b);
+}
+
+#[test]
+fn reject_small_buffer() {
+    let mut buf = [0; 15];
+    assert_eq!(
+        pad_deterministic::<consts::U8>(0, b"\x01\x02\x03", &mut buf),
+        Err(TxPadError::BufferTooSmall {
+            required: 16,
+            got: 15
+        })
+    );
+}
+
+#[test]
+fn round_trip_helper() {
+    let mut buf = [0; 16];
+    let padded_msg = round_trip::<consts::U4>(3, b"\x01\x02\x03", &mut buf).unwrap();
+    assert_eq!(padded_msg.len(), 12);
+    assert!(round_trip::<consts::U4>(3, b"\x01\x02\x03", &mut buf[..11]).is_err());
+}
@@ -0,0 +1,131 @@
+//! Test the trace events emitted on padding and unpadding
+#![cfg(feature = "tracing")]
+
+use std::fmt::Debug;
+use std::string::{String, ToString};
+use std::sync::{Arc, Mutex};
+use std::vec::Vec;
+
+use rand::rngs::mock::StepRng;
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Metadata, Subscriber};
+
+use tx_padding::consts::U8;
+use tx_padding::{InsecureRng, TxPadding};
+
+/// Fields of an event, formatted as `name=value`
+type Fields = Vec<String>;
+
+/// Subscriber recording the fields of every event
+#[derive(Clone, Default)]
+struct Recorder(Arc<Mutex<Vec<Fields>>>);
+
+struct FieldsVisitor<'a>(&'a mut Fields);
+
+impl Visit for FieldsVisitor<'_> {
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        self.0.push(format!("{}={:?}", field.name(), value));
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.0.push(format!("{}={}", field.name(), value));
+    }
+}
+
+impl Subscriber for Recorder {
+    fn enabled(&self, _: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, _: &Attributes<'_>) -> Id {
+        Id::from_u64(1)
+    }
+
+    fn record(&self, _: &Id, _: &Record<'_>) {}
+
+    fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        assert_eq!(*event.metadata().level(), tracing::Level::TRACE);
+        let mut fields = Fields::new();
+        event.record(&mut FieldsVisitor(&mut fields));
+        self.0.lock().unwrap().push(fields);
+    }
+
+    fn enter(&self, _: &Id) {}
+
+    fn exit(&self, _: &Id) {}
+}
+
+fn record(f: impl FnOnce()) -> Vec<Fields> {
+    let recorder = Recorder::default();
+    tracing::subscriber::with_default(recorder.clone(), f);
+    let events = recorder.0.lock().unwrap().clone();
+    events
+}
+
+fn fields(fields: &[&str]) -> Fields {
+    fields.iter().map(|f| f.to_string()).collect()
+}
+
+#[test]
+fn trace_pad_and_unpad() {
+    let events = record(|| {
+        let mut buf = [0; 16];
+        buf[..4].copy_from_slice(b"test");
+        let padded_msg =
+            TxPadding::<U8>::pad_with_rng(&mut InsecureRng(StepRng::new(1, 1)), &mut buf, 4)
+                .unwrap();
+        assert_eq!(TxPadding::<U8>::unpad(padded_msg).unwrap(), b"test");
+    });
+    assert_eq!(
+        events,
+        [
+            fields(&[
+                "message=padded message",
+                "block_size=8",
+                "msg_len=4",
+                "overhead=12",
+            ]),
+            fields(&[
+                "message=unpadded message",
+                "block_size=8",
+                "padded_len=16",
+                "msg_len=4",
+                "overhead=12",
+            ]),
+        ]
+    );
+}
+
+#[test]
+fn trace_failures() {
+    let events = record(|| {
+        let mut buf = [0; 8];
+        assert!(
+            TxPadding::<U8>::pad_with_rng(&mut InsecureRng(StepRng::new(1, 1)), &mut buf, 4)
+                .is_err()
+        );
+        let mut padded_msg = [0; 16];
+        padded_msg[15] = 1;
+        assert!(TxPadding::<U8>::unpad(&padded_msg).is_err());
+    });
+    assert_eq!(
+        events,
+        [
+            fields(&[
+                "message=padding failed",
+                "block_size=8",
+                "msg_len=4",
+                "error=buffer too small: 16 bytes required, got 8",
+            ]),
+            fields(&[
+                "message=unpadding failed",
+                "block_size=8",
+                "padded_len=16",
+                "error=non-zero byte in the padding zeros at index 15",
+            ]),
+        ]
+    );
+}
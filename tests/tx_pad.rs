@@ -0,0 +1,49 @@
+//! Test padding of byte strings at compile time
+#![no_std]
+#![cfg(all(feature = "macros", feature = "seeded"))]
+
+use rand::SeedableRng;
+use rand_chacha::ChaCha20Rng;
+
+use tx_padding::consts::{U16, U2, U256, U8};
+use tx_padding::{tx_pad, TxPadding};
+
+macro_rules! test_tx_pad {
+    ($name:ident, $n:ident, $msg:expr, $seed:expr) => {
+        #[test]
+        fn $name() {
+            const FRAME: [u8; TxPadding::<$n>::padded_len($msg.len())] =
+                tx_pad!($msg, $n, seed = $seed);
+            let mut buf = [0; TxPadding::<$n>::padded_len($msg.len())];
+            buf[..$msg.len()].copy_from_slice($msg);
+            let expected = TxPadding::<$n>::pad_with_rng(
+                &mut ChaCha20Rng::seed_from_u64($seed),
+                &mut buf,
+                $msg.len(),
+            )
+            .unwrap();
+            assert_eq!(&FRAME[..], &expected[..]);
+            assert_eq!(TxPadding::<$n>::unpad(&FRAME).unwrap(), &$msg[..]);
+        }
+    };
+}
+
+test_tx_pad!(tx_pad_empty_u2, U2, b"", 0);
+test_tx_pad!(tx_pad_u8, U8, b"test", 42);
+test_tx_pad!(tx_pad_u16, U16, b"hello world", 7);
+test_tx_pad!(
+    tx_pad_u16_aligned,
+    U16,
+    b"0123456789abcdef",
+    18446744073709551615
+);
+test_tx_pad!(tx_pad_u256, U256, b"golden frame", 1);
+
+#[test]
+fn tx_pad_is_deterministic() {
+    let a = tx_pad!(b"frame", U8, seed = 3);
+    let b = tx_pad!(b"frame", U8, seed = 3);
+    let c = tx_pad!(b"frame", U8, seed = 4);
+    assert_eq!(a, b);
+    assert_ne!(a, c);
+}
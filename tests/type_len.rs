@@ -0,0 +1,30 @@
+//! Test the padded lengths computed at the type level
+#![no_std]
+
+use typenum::Unsigned;
+
+use tx_padding::consts::{U0, U1, U100, U15, U16, U17, U2, U255, U256, U30, U31, U7, U8};
+use tx_padding::{PaddedLen, TxPadding};
+
+macro_rules! test_padded_len {
+    ($name:ident, $m:ty, $n:ty) => {
+        #[test]
+        fn $name() {
+            const LEN: usize = PaddedLen::<$m, $n>::USIZE;
+            let buf = [0u8; LEN];
+            assert_eq!(buf.len(), TxPadding::<$n>::padded_len(<$m>::USIZE));
+        }
+    };
+}
+
+test_padded_len!(padded_len_empty_u2, U0, U2);
+test_padded_len!(padded_len_1_u2, U1, U2);
+test_padded_len!(padded_len_7_u8, U7, U8);
+test_padded_len!(padded_len_8_u8, U8, U8);
+test_padded_len!(padded_len_15_u16, U15, U16);
+test_padded_len!(padded_len_17_u16, U17, U16);
+test_padded_len!(padded_len_30_u16, U30, U16);
+test_padded_len!(padded_len_31_u16, U31, U16);
+test_padded_len!(padded_len_100_u16, U100, U16);
+test_padded_len!(padded_len_255_u256, U255, U256);
+test_padded_len!(padded_len_256_u256, U256, U256);
@@ -0,0 +1,106 @@
+//! Test padding into and unpadding from allocated vectors
+#![cfg(feature = "alloc")]
+#![no_std]
+
+mod common;
+
+extern crate alloc;
+
+use alloc::format;
+
+use rand::rngs::mock::StepRng;
+
+use tx_padding::consts;
+use tx_padding::{InsecureRng, PaddedVec, TxPadError, TxPadding, TxPaddingConst};
+
+use common::FailingRng;
+
+#[test]
+fn pad_to_vec_sizes() {
+    let msg = [0x5a; 40];
+    for n in 0..msg.len() {
+        let padded_msg = TxPadding::<consts::U8>::pad_to_vec(&msg[..n]).unwrap();
+        assert_eq!(padded_msg.len() % 8, 0);
+        assert!(padded_msg.len() > n + 1);
+        assert!(padded_msg.len() <= n + 17);
+        assert_eq!(
+            TxPadding::<consts::U8>::unpad_to_vec(&padded_msg).unwrap(),
+            &msg[..n]
+        );
+    }
+}
+
+#[test]
+fn unpad_to_vec_rejects_illformed() {
+    assert!(TxPadding::<consts::U8>::unpad_to_vec(&[]).is_err());
+    assert!(TxPadding::<consts::U8>::unpad_to_vec(&[0xF8, 0, 0]).is_err());
+}
+
+#[test]
+fn padded_vec_grows() {
+    let msg = [0x5a; 40];
+    let mut padded = PaddedVec::<consts::U8>::new();
+    let mut ends = [0; 40];
+    for n in 0..msg.len() {
+        let len = padded.push_message(&msg[..n]).unwrap().len();
+        assert_eq!(len, TxPadding::<consts::U8>::padded_len(n));
+        ends[n] = padded.len();
+    }
+    let mut start = 0;
+    for (n, &end) in ends.iter().enumerate() {
+        assert_eq!(
+            TxPadding::<consts::U8>::unpad(&padded[start..end]).unwrap(),
+            &msg[..n]
+        );
+        start = end;
+    }
+    assert_eq!(start, padded.len());
+}
+
+#[test]
+fn padded_vec_unchanged_on_failure() {
+    let mut padded = PaddedVec::<consts::U8>::new();
+    padded.push_message(b"hello").unwrap();
+    assert_eq!(
+        padded.push_message_with_rng(&mut FailingRng, b"world"),
+        Err(TxPadError::RngFailure)
+    );
+    assert_eq!(padded.len(), 16);
+    assert_eq!(TxPadding::<consts::U8>::unpad(&padded).unwrap(), b"hello");
+}
+
+#[test]
+fn padded_vec_traits_do_not_depend_on_rng() {
+    // `FailingRng` implements neither `Clone`, `PartialEq` nor `Debug`
+    let mut padded = PaddedVec::<consts::U8, FailingRng>::new();
+    padded
+        .push_message_with_rng(&mut InsecureRng(StepRng::new(1, 1)), b"hello")
+        .unwrap();
+    let copy = padded.clone();
+    assert_eq!(copy, padded);
+    assert_ne!(copy, PaddedVec::new());
+    assert_eq!(
+        format!("{:?}", padded),
+        format!("PaddedVec {{ buf: {:?} }}", padded.as_bytes())
+    );
+}
+
+#[test]
+fn unpad_truncate_vec_keeps_allocation() {
+    let msg = [0x5a; 40];
+    for n in 0..msg.len() {
+        let mut buf = TxPadding::<consts::U8>::pad_to_vec(&msg[..n]).unwrap();
+        let ptr = buf.as_ptr();
+        TxPadding::<consts::U8>::unpad_truncate_vec(&mut buf).unwrap();
+        assert_eq!(buf, &msg[..n]);
+        assert_eq!(buf.as_ptr(), ptr);
+        let mut buf = TxPaddingConst::<8>::pad_to_vec(&msg[..n]).unwrap();
+        TxPaddingConst::<8>::unpad_truncate_vec(&mut buf).unwrap();
+        assert_eq!(buf, &msg[..n]);
+    }
+    let mut buf = TxPadding::<consts::U8>::pad_to_vec(b"test").unwrap();
+    buf[15] = 1;
+    let padded_msg = buf.clone();
+    assert!(TxPadding::<consts::U8>::unpad_truncate_vec(&mut buf).is_err());
+    assert_eq!(buf, padded_msg);
+}
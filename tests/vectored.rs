@@ -0,0 +1,62 @@
+//! Test padding into separate parts for vectored I/O
+#![no_std]
+
+use rand::rngs::mock::StepRng;
+
+use tx_padding::consts;
+use tx_padding::{InsecureRng, TxPadding};
+
+macro_rules! test_parts_match_pad {
+    ($name:ident, $n:ty) => {
+        #[test]
+        fn $name() {
+            let msg = [0x5a; 300];
+            for n in 0..300 {
+                let mut expected = [0; 1024];
+                expected[..n].copy_from_slice(&msg[..n]);
+                let expected = TxPadding::<$n>::pad_with_rng(
+                    &mut InsecureRng(StepRng::new(1, 1)),
+                    &mut expected,
+                    n,
+                )
+                .unwrap();
+
+                let parts = TxPadding::<$n>::pad_vectored_with_rng(
+                    &mut InsecureRng(StepRng::new(1, 1)),
+                    &msg[..n],
+                )
+                .unwrap();
+                assert_eq!(parts.len(), expected.len());
+                let (prefix, rest) = expected.split_at(parts.prefix().len());
+                let (body, tail) = rest.split_at(n);
+                assert_eq!(parts.prefix(), prefix);
+                assert_eq!(parts.header(), [prefix[0]]);
+                assert_eq!(parts.random(), &prefix[1..]);
+                assert_eq!(parts.msg(), body);
+                assert_eq!(parts.zero_tail_len(), tail.len());
+                assert_eq!(parts.zero_tail(), tail);
+            }
+        }
+    };
+}
+
+test_parts_match_pad!(parts_match_pad_u2, consts::U2);
+test_parts_match_pad!(parts_match_pad_u8, consts::U8);
+test_parts_match_pad!(parts_match_pad_u256, consts::U256);
+
+#[cfg(feature = "std")]
+#[test]
+fn write_io_slices() {
+    extern crate std;
+    use std::io::Write;
+    use std::vec::Vec;
+
+    let parts = TxPadding::<consts::U16>::pad_vectored(b"hello world").unwrap();
+    let mut padded_msg = Vec::new();
+    let n = padded_msg.write_vectored(&parts.io_slices()).unwrap();
+    assert_eq!(n, parts.len());
+    assert_eq!(
+        TxPadding::<consts::U16>::unpad(&padded_msg).unwrap(),
+        b"hello world"
+    );
+}
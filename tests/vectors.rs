@@ -0,0 +1,50 @@
+//! Test the JSON test vectors
+#![cfg(feature = "test-vectors")]
+
+use tx_padding::vectors::{Expected, TestVector, TestVectors};
+
+const VECTORS: &str = include_str!("vectors/tx_padding.json");
+
+#[test]
+fn committed_vectors_pass() {
+    let vectors = TestVectors::from_json(VECTORS).unwrap();
+    assert_eq!(vectors.algorithm, "tx-padding");
+    vectors.check().unwrap();
+}
+
+#[test]
+fn committed_vectors_are_up_to_date() {
+    // Regenerate with `TXPAD_BLESS=1 cargo test --features test-vectors --test vectors`
+    let json = TestVectors::generate().to_json() + "\n";
+    if std::env::var_os("TXPAD_BLESS").is_some() {
+        std::fs::write(
+            concat!(env!("CARGO_MANIFEST_DIR"), "/tests/vectors/tx_padding.json"),
+            &json,
+        )
+        .unwrap();
+    }
+    assert_eq!(json, VECTORS);
+}
+
+#[test]
+fn tampered_vectors_fail() {
+    let mut vectors = TestVectors::generate();
+    vectors.vectors[3].padded[0] ^= 0x80;
+    assert!(vectors.check().is_err());
+
+    let mut vectors = TestVectors::generate();
+    let invalid = vectors
+        .vectors
+        .iter_mut()
+        .find(|v| v.result == Expected::Invalid)
+        .unwrap();
+    invalid.result = Expected::Valid;
+    assert!(vectors.check().is_err());
+}
+
+#[test]
+fn wrong_random_len() {
+    assert!(TestVector::new(1, "", 8, &[0; 4], b"test").is_ok());
+    assert!(TestVector::new(1, "", 8, &[0; 3], b"test").is_err());
+    assert!(TestVector::new(1, "", 8, &[0; 5], b"test").is_err());
+}
@@ -0,0 +1,88 @@
+//! Test the scheme version stored in the header
+#![no_std]
+
+use rand::rngs::mock::StepRng;
+
+use tx_padding::consts;
+#[cfg(any(feature = "os-rng", feature = "thread-rng"))]
+use tx_padding::Padding;
+use tx_padding::{InsecureRng, TxPadError, TxPadding};
+
+macro_rules! test_versioned_round_trip {
+    ($name:ident, $n:ty) => {
+        #[test]
+        fn $name() {
+            for version in 0..4 {
+                for n in 0..40 {
+                    let mut buf = [0x5a; 256];
+                    let padded_msg = TxPadding::<$n>::pad_versioned_with_rng(
+                        &mut InsecureRng(StepRng::new(0, 1)),
+                        &mut buf,
+                        n,
+                        version,
+                    )
+                    .unwrap();
+                    let msg = &[0x5a; 40][..n];
+                    assert_eq!(TxPadding::<$n>::unpad(padded_msg).unwrap(), msg);
+                    assert_eq!(
+                        TxPadding::<$n>::unpad_versioned(padded_msg, version).unwrap(),
+                        msg
+                    );
+                    for other in 0..5 {
+                        if other != version {
+                            assert!(TxPadding::<$n>::unpad_versioned(padded_msg, other).is_err());
+                        }
+                    }
+                }
+            }
+        }
+    };
+}
+
+test_versioned_round_trip!(versioned_round_trip_u2, consts::U2);
+test_versioned_round_trip!(versioned_round_trip_u16, consts::U16);
+test_versioned_round_trip!(versioned_round_trip_u64, consts::U64);
+
+#[test]
+fn reject_invalid_version() {
+    let mut buf = [0; 16];
+    assert_eq!(
+        TxPadding::<consts::U8>::pad_versioned_with_rng(
+            &mut InsecureRng(StepRng::new(0, 1)),
+            &mut buf,
+            3,
+            4
+        ),
+        Err(TxPadError::InvalidVersion { got: 4 })
+    );
+}
+
+#[cfg(any(feature = "os-rng", feature = "thread-rng"))]
+#[test]
+fn unversioned_padding_is_version_3() {
+    let mut buf = [0; 16];
+    let padded_msg = TxPadding::<consts::U8>::pad(&mut buf, 3, 8).unwrap();
+    assert!(TxPadding::<consts::U8>::unpad_versioned(padded_msg, 3).is_ok());
+}
+
+#[cfg(feature = "diagnostics")]
+#[test]
+fn report_version_mismatch() {
+    use tx_padding::UnpadErrorKind;
+
+    let mut buf = [0; 16];
+    let padded_msg = TxPadding::<consts::U8>::pad_versioned_with_rng(
+        &mut InsecureRng(StepRng::new(0, 1)),
+        &mut buf,
+        3,
+        1,
+    )
+    .unwrap();
+    assert_eq!(
+        TxPadding::<consts::U8>::unpad_versioned_verbose(padded_msg, 2),
+        Err(UnpadErrorKind::VersionMismatch {
+            expected: 2,
+            got: 1
+        })
+    );
+}
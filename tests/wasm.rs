@@ -0,0 +1,22 @@
+//! Test the JavaScript bindings
+#![cfg(all(feature = "wasm-js", target_arch = "wasm32"))]
+
+use wasm_bindgen_test::wasm_bindgen_test;
+
+use tx_padding::wasm::{pad, unpad};
+
+#[wasm_bindgen_test]
+fn wasm_round_trip() {
+    let msg = [0x5a; 40];
+    for n in 0..40 {
+        let padded_msg = pad(&msg[..n], 16).unwrap();
+        assert_eq!(padded_msg.len() % 16, 0);
+        assert_eq!(unpad(&padded_msg, 16).unwrap(), &msg[..n]);
+    }
+}
+
+#[wasm_bindgen_test]
+fn wasm_invalid_block_size() {
+    assert!(pad(b"test", 12).is_err());
+    assert!(unpad(&[0; 16], 12).is_err());
+}
@@ -0,0 +1,69 @@
+//! Test the two-byte header variant supporting large block sizes
+#![no_std]
+
+use rand::rngs::mock::StepRng;
+
+use tx_padding::consts;
+#[cfg(any(feature = "os-rng", feature = "thread-rng"))]
+use tx_padding::Padding;
+use tx_padding::{InsecureRng, TxPaddingWide};
+
+macro_rules! test_wide_round_trip {
+    ($name:ident, $n:ty, $block_size:expr) => {
+        #[test]
+        fn $name() {
+            let msg = [0x5a; $block_size + 300];
+            let mut buf = [0xff; 3 * $block_size + 300];
+            for n in (0..300).chain(($block_size as usize).saturating_sub(4)..$block_size + 2) {
+                buf[..n].copy_from_slice(&msg[..n]);
+                let padded_msg = TxPaddingWide::<$n>::pad_with_rng(
+                    &mut InsecureRng(StepRng::new(1, 1)),
+                    &mut buf,
+                    n,
+                )
+                .unwrap();
+                let l = padded_msg.len();
+                assert_eq!(l, TxPaddingWide::<$n>::padded_len(n));
+                assert_eq!(l % $block_size, 0);
+                assert!(l >= n + $block_size + 2);
+                assert!(l <= n + 2 * $block_size + 2);
+                assert!(padded_msg[l - ($block_size - 1)..].iter().all(|&v| v == 0));
+                assert_eq!(TxPaddingWide::<$n>::unpad(padded_msg).unwrap(), &msg[..n]);
+            }
+        }
+    };
+}
+
+test_wide_round_trip!(wide_round_trip_u2, consts::U2, 2);
+test_wide_round_trip!(wide_round_trip_u256, consts::U256, 256);
+test_wide_round_trip!(wide_round_trip_u512, consts::U512, 512);
+test_wide_round_trip!(wide_round_trip_u4096, consts::U4096, 4096);
+test_wide_round_trip!(wide_round_trip_u32768, consts::U32768, 32768);
+
+#[test]
+fn wide_header_layout() {
+    let mut buf = [0; 1024];
+    buf[..3].copy_from_slice(b"abc");
+    let padded_msg = TxPaddingWide::<consts::U512>::pad_with_rng(
+        &mut InsecureRng(StepRng::new(0, 0)),
+        &mut buf,
+        3,
+    )
+    .unwrap();
+    // 2 + pad_len + 3 + 511 = 1024
+    assert_eq!(padded_msg.len(), 1024);
+    assert_eq!(&padded_msg[..2], &(0xfe00u16 | 506).to_be_bytes());
+    assert_eq!(&padded_msg[510..513], b"abc");
+}
+
+#[cfg(any(feature = "os-rng", feature = "thread-rng"))]
+#[test]
+fn wide_rejects_illformed() {
+    assert!(TxPaddingWide::<consts::U512>::unpad(&[]).is_err());
+    assert!(TxPaddingWide::<consts::U512>::unpad(&[0xfe]).is_err());
+    assert!(TxPaddingWide::<consts::U512>::unpad(&[0xfe, 0x00, 0, 0]).is_err());
+    let mut buf = [0; 1024];
+    let padded_msg = TxPaddingWide::<consts::U512>::pad(&mut buf, 3, 512).unwrap();
+    padded_msg[1000] = 1;
+    assert!(TxPaddingWide::<consts::U512>::unpad(padded_msg).is_err());
+}
@@ -0,0 +1,135 @@
+//! Model-checking harnesses for the round trip of `TxPadding`, run with Kani: `cargo kani`
+//!
+//! For every block size, the proofs establish for all messages up to [`MAX_MSG_LEN`] bytes and
+//! all random prefixes that unpadding the padded message returns the message, and for all
+//! inputs up to [`MAX_INPUT_LEN`] bytes that unpadding never reads out of bounds nor returns a
+//! message outside of its input. Kani checks every slice access and arithmetic operation for
+//! panics and overflows along the way.
+//!
+//! The properties themselves are plain functions, which the tests also run exhaustively over
+//! small inputs without Kani.
+#![no_std]
+
+use rand::{Error, RngCore};
+use tx_padding::{BlockSize, InsecureRng, TxPadding};
+
+/// Longest message the round trip is proved for
+pub const MAX_MSG_LEN: usize = 32;
+
+/// Longest input unpadding is proved to stay in bounds for
+pub const MAX_INPUT_LEN: usize = 48;
+
+/// Longest padded message of at most `MAX_MSG_LEN` bytes
+pub const MAX_PADDED_LEN: usize = MAX_MSG_LEN + 2 * 256;
+
+/// Number of random bytes [`PrefixRng`] holds, enough for any block size
+pub const MAX_RANDOM_LEN: usize = 257;
+
+/// RNG handing out the bytes of a fixed prefix
+///
+/// It is predictable by design, so the scheme under verification wraps it in [`InsecureRng`].
+#[derive(Clone, Debug)]
+pub struct PrefixRng {
+    bytes: [u8; MAX_RANDOM_LEN],
+    pos: usize,
+}
+
+impl PrefixRng {
+    /// Create a generator handing out `bytes`
+    pub fn new(bytes: [u8; MAX_RANDOM_LEN]) -> Self {
+        PrefixRng { bytes, pos: 0 }
+    }
+}
+
+impl Default for PrefixRng {
+    fn default() -> Self {
+        Self::new([0; MAX_RANDOM_LEN])
+    }
+}
+
+impl RngCore for PrefixRng {
+    fn next_u32(&mut self) -> u32 {
+        let mut bytes = [0; 4];
+        self.fill_bytes(&mut bytes);
+        u32::from_le_bytes(bytes)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let mut bytes = [0; 8];
+        self.fill_bytes(&mut bytes);
+        u64::from_le_bytes(bytes)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        dest.copy_from_slice(&self.bytes[self.pos..self.pos + dest.len()]);
+        self.pos += dest.len();
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+/// The scheme under verification
+type Scheme<N> = TxPadding<N, InsecureRng<PrefixRng>>;
+
+/// Pad the first `len` bytes of `msg` with the random bytes `random`, and check that unpadding
+/// returns them
+pub fn check_round_trip<N: BlockSize>(msg: &[u8], len: usize, random: [u8; MAX_RANDOM_LEN]) {
+    let mut buf = [0; MAX_PADDED_LEN];
+    buf[..len].copy_from_slice(&msg[..len]);
+    let padded_msg =
+        Scheme::<N>::pad_with_rng(&mut InsecureRng(PrefixRng::new(random)), &mut buf, len).unwrap();
+    assert_eq!(padded_msg.len() % N::USIZE, 0);
+    assert_eq!(padded_msg.len(), Scheme::<N>::padded_len(len));
+    assert_eq!(Scheme::<N>::unpad(padded_msg).unwrap(), &msg[..len]);
+    assert_eq!(Scheme::<N>::unpad_strict(padded_msg).unwrap(), &msg[..len]);
+}
+
+/// Unpad `data`, and check that the message returned lies within it
+pub fn check_unpad_in_bounds<N: BlockSize>(data: &[u8]) {
+    if let Ok(msg) = Scheme::<N>::unpad(data) {
+        let start = msg.as_ptr() as usize - data.as_ptr() as usize;
+        assert!(start >= 1 && start + msg.len() + N::USIZE - 1 == data.len());
+    }
+}
+
+#[cfg(kani)]
+mod proofs {
+    use tx_padding::consts::{U128, U16, U2, U256, U32, U4, U64, U8};
+
+    use super::*;
+
+    macro_rules! proofs {
+        ($round_trip:ident, $in_bounds:ident, $n:ty) => {
+            #[kani::proof]
+            #[kani::unwind(600)]
+            fn $round_trip() {
+                let msg: [u8; MAX_MSG_LEN] = kani::any();
+                let random: [u8; MAX_RANDOM_LEN] = kani::any();
+                let len: usize = kani::any();
+                kani::assume(len <= MAX_MSG_LEN);
+                check_round_trip::<$n>(&msg, len, random);
+            }
+
+            #[kani::proof]
+            #[kani::unwind(64)]
+            fn $in_bounds() {
+                let data: [u8; MAX_INPUT_LEN] = kani::any();
+                let len: usize = kani::any();
+                kani::assume(len <= MAX_INPUT_LEN);
+                check_unpad_in_bounds::<$n>(&data[..len]);
+            }
+        };
+    }
+
+    proofs!(round_trip_u2, unpad_in_bounds_u2, U2);
+    proofs!(round_trip_u4, unpad_in_bounds_u4, U4);
+    proofs!(round_trip_u8, unpad_in_bounds_u8, U8);
+    proofs!(round_trip_u16, unpad_in_bounds_u16, U16);
+    proofs!(round_trip_u32, unpad_in_bounds_u32, U32);
+    proofs!(round_trip_u64, unpad_in_bounds_u64, U64);
+    proofs!(round_trip_u128, unpad_in_bounds_u128, U128);
+    proofs!(round_trip_u256, unpad_in_bounds_u256, U256);
+}
@@ -0,0 +1,51 @@
+//! Run the properties of the proofs over every small input, without Kani
+#![no_std]
+
+use tx_padding::consts::{U128, U16, U2, U256, U32, U4, U64, U8};
+use tx_padding::BlockSize;
+use tx_padding_verification::{
+    check_round_trip, check_unpad_in_bounds, MAX_INPUT_LEN, MAX_MSG_LEN, MAX_RANDOM_LEN,
+};
+
+fn round_trip<N: BlockSize>() {
+    let msg = [0x5a; MAX_MSG_LEN];
+    for len in 0..=MAX_MSG_LEN {
+        for fill in [0x00, 0x5a, 0xff] {
+            check_round_trip::<N>(&msg, len, [fill; MAX_RANDOM_LEN]);
+        }
+    }
+}
+
+fn unpad_in_bounds<N: BlockSize>() {
+    for len in 0..=MAX_INPUT_LEN {
+        for header in 0..=255 {
+            let mut data = [0; MAX_INPUT_LEN];
+            data[0] = header;
+            check_unpad_in_bounds::<N>(&data[..len]);
+        }
+    }
+}
+
+#[test]
+fn round_trip_all_block_sizes() {
+    round_trip::<U2>();
+    round_trip::<U4>();
+    round_trip::<U8>();
+    round_trip::<U16>();
+    round_trip::<U32>();
+    round_trip::<U64>();
+    round_trip::<U128>();
+    round_trip::<U256>();
+}
+
+#[test]
+fn unpad_in_bounds_all_block_sizes() {
+    unpad_in_bounds::<U2>();
+    unpad_in_bounds::<U4>();
+    unpad_in_bounds::<U8>();
+    unpad_in_bounds::<U16>();
+    unpad_in_bounds::<U32>();
+    unpad_in_bounds::<U64>();
+    unpad_in_bounds::<U128>();
+    unpad_in_bounds::<U256>();
+}